@@ -263,11 +263,14 @@ impl App {
 
         // World system
         self.regsys(world::system);
+        self.regsys(world::pre_frame_or_tick);
+        self.regsys(world::post_frame_or_tick);
 
         // Utils systems
         self.regsys(utils::time);
         self.regsys(utils::io);
         self.regsys(utils::file_logger);
+        self.regsys(utils::per_frame_event_clean);
 
         // Audio system
         self.regsys(audio::system);
@@ -308,6 +311,12 @@ impl App {
         self.regsys(crate::systems::camera::system);
         self.regsys(crate::systems::gui::system);
 
+        // Text input system (IME)
+        self.regsys(crate::systems::text::system);
+
+        // Cursor grab / visibility system
+        self.regsys(crate::systems::cursor::system);
+
         // Fetch names and versions
         let app_name = self.app_name.clone();
         let app_version = self.app_version;
@@ -1,2 +1,4 @@
 pub mod camera;
+pub mod cursor;
 pub mod gui;
+pub mod text;
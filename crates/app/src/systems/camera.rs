@@ -57,34 +57,25 @@ fn init(world: &mut World) {
     input.bind_axis("y rotation", MouseAxis::PositionY);
 }
 
-// Hide the cursor and give control to the camera
-fn hide_ui(window: &Window, ui: &mut Interface) {
+// Lock the cursor and give control to the camera
+fn hide_ui(input: &mut Input, ui: &mut Interface) {
     ui.consumes_window_events = false;
-    window
-        .raw()
-        .set_cursor_grab(winit::window::CursorGrabMode::Confined)
-        .unwrap();
-    window.raw().set_cursor_visible(false);
+    input.set_cursor(CursorState::locked());
 }
 
-// Show the cursor and give control to the UI
-fn show_ui(window: &Window, ui: &mut Interface) {
+// Free the cursor and give control to the UI
+fn show_ui(input: &mut Input, ui: &mut Interface) {
     ui.consumes_window_events = true;
-    window
-        .raw()
-        .set_cursor_grab(winit::window::CursorGrabMode::None)
-        .unwrap();
-    window.raw().set_cursor_visible(true);
+    input.set_cursor(CursorState::free());
 }
 
 // Camera default update method
 fn update(world: &mut World) {
     let time = world.get::<Time>().unwrap();
     let time = &*time;
-    let input = world.get::<Input>().unwrap();
+    let mut input = world.get_mut::<Input>().unwrap();
     let mut scene = world.get_mut::<Scene>().unwrap();
     let mut gui = world.get_mut::<Interface>().unwrap();
-    let window = world.get::<Window>().unwrap();
 
     let camera = scene.find_mut::<(
         &mut Camera,
@@ -126,10 +117,10 @@ fn update(world: &mut World) {
     // If it isn't then exit early
     if !controller.active {
         **output = vek::Vec3::zero();
-        show_ui(&window, &mut gui);
+        show_ui(&mut input, &mut gui);
         return;
     } else {
-        hide_ui(&window, &mut gui);
+        hide_ui(&mut input, &mut gui);
     }
 
     // Ignore inputs from early frames
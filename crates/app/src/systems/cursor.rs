@@ -0,0 +1,64 @@
+use crate::prelude::*;
+use winit::window::CursorGrabMode;
+
+// Last cursor state that we gave to the window
+struct AppliedCursor(Option<CursorState>);
+
+// Insert the applied cursor resource (nothing applied yet)
+fn init(world: &mut World) {
+    world.insert(AppliedCursor(None));
+}
+
+// Grab the cursor, falling back to the other grab mode if the platform doesn't support it
+fn grab(window: &Window, grab: CursorGrab) {
+    let (mode, fallback) = match grab {
+        CursorGrab::None => (CursorGrabMode::None, None),
+        CursorGrab::Confined => (CursorGrabMode::Confined, Some(CursorGrabMode::Locked)),
+        CursorGrab::Locked => (CursorGrabMode::Locked, Some(CursorGrabMode::Confined)),
+    };
+
+    let result = window
+        .raw()
+        .set_cursor_grab(mode)
+        .or_else(|err| match fallback {
+            Some(fallback) => window.raw().set_cursor_grab(fallback),
+            None => Err(err),
+        });
+
+    if let Err(err) = result {
+        log::warn!("Could not grab the cursor: {err}");
+    }
+}
+
+// Apply the cursor state of the input resource to the window
+// The UI owns the cursor while it consumes the window events, so it is freed
+fn update(world: &mut World) {
+    let mut input = world.get_mut::<Input>().unwrap();
+    let mut applied = world.get_mut::<AppliedCursor>().unwrap();
+    let Ok(window) = world.get::<Window>() else {
+        return;
+    };
+
+    let ui = world
+        .get::<Interface>()
+        .map(|ui| ui.consumes_window_events)
+        .unwrap_or_default();
+    input.release_cursor(ui);
+
+    let state = input.applied_cursor();
+    if applied.0 == Some(state) {
+        return;
+    }
+
+    grab(&window, state.grab);
+    window.raw().set_cursor_visible(state.visible);
+    window.raw().set_cursor_icon(state.icon);
+
+    applied.0 = Some(state);
+}
+
+// Cursor system that makes the window cursor follow the cursor state of the input resource
+pub fn system(system: &mut System) {
+    system.insert_init(init);
+    system.insert_update(update).after(crate::gui::display);
+}
@@ -0,0 +1,30 @@
+use crate::prelude::*;
+
+// Last IME state that we gave to the window
+struct ImeAllowed(bool);
+
+// Insert the IME state resource (disabled by default)
+fn init(world: &mut World) {
+    world.insert(ImeAllowed(false));
+}
+
+// Enable the IME of the window while a text field is focused
+fn update(world: &mut World) {
+    let input = world.get::<Input>().unwrap();
+    let mut allowed = world.get_mut::<ImeAllowed>().unwrap();
+    let Ok(window) = world.get::<Window>() else {
+        return;
+    };
+
+    let focused = input.is_text_focused();
+    if allowed.0 != focused {
+        window.raw().set_ime_allowed(focused);
+        allowed.0 = focused;
+    }
+}
+
+// Text input system that makes the IME follow the text focus of the input resource
+pub fn system(system: &mut System) {
+    system.insert_init(init);
+    system.insert_update(update);
+}
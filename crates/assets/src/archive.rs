@@ -0,0 +1,365 @@
+use crate::ArchiveError;
+use ahash::AHashMap;
+use parking_lot::Mutex;
+use std::{
+    fs::File,
+    io::{BufReader, Cursor, Read, Seek, SeekFrom, Write},
+    path::{Component, Path},
+};
+
+// Magic bytes at the very start of every archive
+const MAGIC: [u8; 4] = *b"CFPK";
+
+/// Current version of the archive format. Archives with any other version will be rejected.
+pub const ARCHIVE_VERSION: u32 = 1;
+
+// Magic (4) + version (4) + entry count (4) + index offset (8)
+const HEADER_SIZE: u64 = 20;
+
+// Path length (2) + compression (1) + checksum (4) + offset (8) + stored (8) + size (8)
+const MIN_ENTRY_SIZE: u64 = 31;
+
+// Deflate can't compress data by more than this ratio, so bigger sizes are corrupted
+const MAX_DEFLATE_RATIO: u64 = 1032;
+
+/// Compression method that is used for a single entry within an [Archive].
+#[derive(Default, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Compression {
+    /// Store the bytes as is.
+    None,
+
+    /// Compress the bytes using deflate.
+    #[default]
+    Deflate,
+}
+
+impl Compression {
+    fn from_u8(value: u8) -> Result<Self, ArchiveError> {
+        match value {
+            0 => Ok(Self::None),
+            1 => Ok(Self::Deflate),
+            x => Err(ArchiveError::UnknownCompression(x)),
+        }
+    }
+
+    fn to_u8(self) -> u8 {
+        match self {
+            Self::None => 0,
+            Self::Deflate => 1,
+        }
+    }
+}
+
+// Index entry of a single file stored within the archive
+#[derive(Clone, Copy, Debug)]
+struct Entry {
+    offset: u64,
+    stored: u64,
+    size: u64,
+    compression: Compression,
+    checksum: u32,
+}
+
+// Something that we can read archive data from
+trait Source: Read + Seek + Send {}
+impl<T: Read + Seek + Send> Source for T {}
+
+// Convert an asset path to the path stored within the archive index ("engine/shaders/x.glsl")
+pub(crate) fn normalize(path: &Path) -> Option<String> {
+    let path = path.strip_prefix("./assets/").unwrap_or(path);
+    let mut parts = Vec::new();
+    for component in path.components() {
+        match component {
+            Component::Normal(part) => parts.push(part.to_str()?),
+            Component::CurDir => {}
+            _ => return None,
+        }
+    }
+
+    (!parts.is_empty()).then(|| parts.join("/"))
+}
+
+/// A packed asset archive that contains an index, per-entry compression, and per-entry checksums.
+/// Archives are created using an [ArchiveBuilder] (or the ``packer`` binary) and mounted using [Assets::mount_archive](crate::Assets::mount_archive).
+pub struct Archive {
+    entries: AHashMap<String, Entry>,
+    source: Mutex<Box<dyn Source>>,
+}
+
+impl Archive {
+    /// Open an archive file and read its index. The entries themselves are read lazily.
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, ArchiveError> {
+        let file = File::open(path.as_ref())?;
+        let archive = Self::from_source(Box::new(BufReader::new(file)))?;
+        log::debug!(
+            "Opened asset archive {:?} with {} entries",
+            path.as_ref(),
+            archive.len()
+        );
+        Ok(archive)
+    }
+
+    /// Read an archive that is already stored in memory.
+    pub fn from_bytes(bytes: Vec<u8>) -> Result<Self, ArchiveError> {
+        Self::from_source(Box::new(Cursor::new(bytes)))
+    }
+
+    // Read the header and the index of the archive
+    fn from_source(mut source: Box<dyn Source>) -> Result<Self, ArchiveError> {
+        let mut magic = [0u8; 4];
+        source.read_exact(&mut magic)?;
+        if magic != MAGIC {
+            return Err(ArchiveError::InvalidMagic);
+        }
+
+        let version = read_u32(&mut source)?;
+        if version != ARCHIVE_VERSION {
+            return Err(ArchiveError::UnsupportedVersion(version));
+        }
+
+        let count = read_u32(&mut source)?;
+        let index = read_u64(&mut source)?;
+
+        // Every value of the header and index is untrusted, so check them before allocating anything
+        let length = source.seek(SeekFrom::End(0))?;
+        if index < HEADER_SIZE
+            || index > length
+            || (count as u64).saturating_mul(MIN_ENTRY_SIZE) > length - index
+        {
+            return Err(ArchiveError::Corrupted);
+        }
+        source.seek(SeekFrom::Start(index))?;
+
+        let mut entries = AHashMap::with_capacity(count as usize);
+        for _ in 0..count {
+            let len = read_u16(&mut source)? as u64;
+            if len > length - source.stream_position()? {
+                return Err(ArchiveError::Corrupted);
+            }
+
+            let mut path = vec![0u8; len as usize];
+            source.read_exact(&mut path)?;
+            let path = String::from_utf8(path).map_err(|_| ArchiveError::InvalidPath)?;
+
+            let mut compression = [0u8; 1];
+            source.read_exact(&mut compression)?;
+
+            let entry = Entry {
+                compression: Compression::from_u8(compression[0])?,
+                checksum: read_u32(&mut source)?,
+                offset: read_u64(&mut source)?,
+                stored: read_u64(&mut source)?,
+                size: read_u64(&mut source)?,
+            };
+
+            let end = entry.offset.saturating_add(entry.stored);
+            let limit = match entry.compression {
+                Compression::None => entry.stored,
+                Compression::Deflate => entry.stored.saturating_mul(MAX_DEFLATE_RATIO),
+            };
+            if entry.offset < HEADER_SIZE || end > index || entry.size > limit {
+                return Err(ArchiveError::Corrupted);
+            }
+
+            entries.insert(path, entry);
+        }
+
+        Ok(Self {
+            entries,
+            source: Mutex::new(source),
+        })
+    }
+
+    /// Get the number of files stored within the archive.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Check if the archive contains no files.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Check if the archive contains the file of an asset.
+    pub fn contains(&self, path: impl AsRef<Path>) -> bool {
+        normalize(path.as_ref()).is_some_and(|path| self.entries.contains_key(&path))
+    }
+
+    /// Iterate over the paths of all the files stored within the archive.
+    pub fn paths(&self) -> impl Iterator<Item = &str> {
+        self.entries.keys().map(String::as_str)
+    }
+
+    /// Read, decompress, and verify the bytes of a file stored within the archive.
+    /// Returns None if the archive does not contain the file.
+    pub fn read(&self, path: impl AsRef<Path>) -> Option<Result<Vec<u8>, ArchiveError>> {
+        let path = normalize(path.as_ref())?;
+        let entry = *self.entries.get(&path)?;
+        Some(self.read_entry(&path, entry))
+    }
+
+    // Read a single entry from the source
+    // Offsets and stored sizes were checked against the length of the source when reading the index
+    fn read_entry(&self, path: &str, entry: Entry) -> Result<Vec<u8>, ArchiveError> {
+        let mut stored = vec![0u8; entry.stored as usize];
+        {
+            let mut source = self.source.lock();
+            source.seek(SeekFrom::Start(entry.offset))?;
+            source.read_exact(&mut stored)?;
+        }
+
+        let bytes = match entry.compression {
+            Compression::None => stored,
+            Compression::Deflate => {
+                // Entry sizes were already checked against the stored sizes when reading the index
+                let mut bytes = Vec::with_capacity(entry.size as usize);
+                flate2::read::DeflateDecoder::new(stored.as_slice())
+                    .take(entry.size + 1)
+                    .read_to_end(&mut bytes)?;
+                bytes
+            }
+        };
+
+        if bytes.len() as u64 != entry.size || crc32fast::hash(&bytes) != entry.checksum {
+            return Err(ArchiveError::ChecksumMismatch(path.to_owned()));
+        }
+
+        Ok(bytes)
+    }
+}
+
+/// Builder that packs multiple files into a single [Archive].
+#[derive(Default)]
+pub struct ArchiveBuilder {
+    files: Vec<(String, Compression, Vec<u8>)>,
+}
+
+impl ArchiveBuilder {
+    /// Create a new empty archive builder.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a file to the archive using its asset path (not global) and its raw bytes.
+    /// Files that don't get any smaller when compressed are stored as is.
+    pub fn add(
+        &mut self,
+        path: impl AsRef<Path>,
+        bytes: Vec<u8>,
+        compression: Compression,
+    ) -> Result<(), ArchiveError> {
+        let path = normalize(path.as_ref()).ok_or(ArchiveError::InvalidPath)?;
+        if path.len() > u16::MAX as usize {
+            return Err(ArchiveError::InvalidPath);
+        }
+
+        self.files.retain(|(old, _, _)| *old != path);
+        self.files.push((path, compression, bytes));
+        Ok(())
+    }
+
+    /// Recursively add all the files of a directory. The asset paths are relative to the given root directory.
+    /// Returns the number of files that were added.
+    pub fn add_directory(
+        &mut self,
+        root: impl AsRef<Path>,
+        compression: Compression,
+    ) -> Result<usize, ArchiveError> {
+        let root = root.as_ref();
+        let mut pending = vec![root.to_path_buf()];
+        let mut count = 0;
+
+        while let Some(directory) = pending.pop() {
+            for entry in std::fs::read_dir(&directory)? {
+                let path = entry?.path();
+                if path.is_dir() {
+                    pending.push(path);
+                    continue;
+                }
+
+                let bytes = std::fs::read(&path)?;
+                let local = path.strip_prefix(root).unwrap();
+                self.add(local, bytes, compression)?;
+                count += 1;
+            }
+        }
+
+        Ok(count)
+    }
+
+    /// Get the number of files that will be packed.
+    pub fn len(&self) -> usize {
+        self.files.len()
+    }
+
+    /// Check if there are no files to pack.
+    pub fn is_empty(&self) -> bool {
+        self.files.is_empty()
+    }
+
+    /// Compress all the files and write the archive into the given writer.
+    pub fn write(self, mut writer: impl Write) -> Result<(), ArchiveError> {
+        let mut data = Vec::<u8>::new();
+        let mut index = Vec::<u8>::new();
+
+        for (path, compression, bytes) in self.files.iter() {
+            let checksum = crc32fast::hash(bytes);
+
+            // Only keep the compressed bytes if they are actually smaller
+            let (compression, stored) = match compression {
+                Compression::None => (Compression::None, None),
+                Compression::Deflate => {
+                    let mut encoder = flate2::write::DeflateEncoder::new(
+                        Vec::new(),
+                        flate2::Compression::default(),
+                    );
+                    encoder.write_all(bytes)?;
+                    let compressed = encoder.finish()?;
+
+                    if compressed.len() < bytes.len() {
+                        (Compression::Deflate, Some(compressed))
+                    } else {
+                        (Compression::None, None)
+                    }
+                }
+            };
+            let stored = stored.as_deref().unwrap_or(bytes);
+
+            index.extend_from_slice(&(path.len() as u16).to_le_bytes());
+            index.extend_from_slice(path.as_bytes());
+            index.push(compression.to_u8());
+            index.extend_from_slice(&checksum.to_le_bytes());
+            index.extend_from_slice(&(HEADER_SIZE + data.len() as u64).to_le_bytes());
+            index.extend_from_slice(&(stored.len() as u64).to_le_bytes());
+            index.extend_from_slice(&(bytes.len() as u64).to_le_bytes());
+            data.extend_from_slice(stored);
+        }
+
+        writer.write_all(&MAGIC)?;
+        writer.write_all(&ARCHIVE_VERSION.to_le_bytes())?;
+        writer.write_all(&(self.files.len() as u32).to_le_bytes())?;
+        writer.write_all(&(HEADER_SIZE + data.len() as u64).to_le_bytes())?;
+        writer.write_all(&data)?;
+        writer.write_all(&index)?;
+        writer.flush()?;
+        Ok(())
+    }
+}
+
+fn read_u16(source: &mut impl Read) -> Result<u16, ArchiveError> {
+    let mut bytes = [0u8; 2];
+    source.read_exact(&mut bytes)?;
+    Ok(u16::from_le_bytes(bytes))
+}
+
+fn read_u32(source: &mut impl Read) -> Result<u32, ArchiveError> {
+    let mut bytes = [0u8; 4];
+    source.read_exact(&mut bytes)?;
+    Ok(u32::from_le_bytes(bytes))
+}
+
+fn read_u64(source: &mut impl Read) -> Result<u64, ArchiveError> {
+    let mut bytes = [0u8; 8];
+    source.read_exact(&mut bytes)?;
+    Ok(u64::from_le_bytes(bytes))
+}
@@ -1,5 +1,6 @@
-use crate::Assets;
+use crate::{AssetLoadError, Assets, ByteSources, ImportCache, ImportKey, Meta, MetaError};
 use std::{convert::Infallible, path::Path, sync::Arc};
+use world::World;
 
 /// File data what will be given to assets whenever we try to deserialize them.
 ///
@@ -12,6 +13,8 @@ pub struct Data<'a> {
     pub(super) bytes: Arc<[u8]>,
     pub(super) path: &'a Path,
     pub(crate) loader: Option<&'a Assets>,
+    pub(crate) sources: Option<ByteSources>,
+    pub(crate) imports: Option<ImportCache>,
 }
 
 impl<'a> Data<'a> {
@@ -29,9 +32,17 @@ impl<'a> Data<'a> {
             bytes,
             path,
             loader,
+            sources: None,
+            imports: None,
         }
     }
 
+    /// Use the given import cache for this data. Used to pass the import cache down to files that are decoded from within other assets.
+    pub fn with_import_cache(mut self, imports: Option<ImportCache>) -> Self {
+        self.imports = imports;
+        self
+    }
+
     /// Get the name of the loaded file.
     pub fn name(&self) -> &str {
         self.name
@@ -60,6 +71,43 @@ impl<'a> Data<'a> {
     pub fn loader(&self) -> Option<&'a Assets> {
         self.loader
     }
+
+    /// Load the raw bytes of another file that this file references, like external buffers.
+    ///
+    /// Unlike the recursive asset loader, this also works for async and deferred assets. The file is recorded as a dependency of the asset
+    ///
+    /// This is only Some when the data was given by the asset loader
+    pub fn load_bytes(&self, path: impl AsRef<Path>) -> Option<Result<Arc<[u8]>, AssetLoadError>> {
+        let sources = self.sources.as_ref()?;
+        Some(Assets::load_referenced_bytes(
+            sources,
+            self.path,
+            path.as_ref(),
+        ))
+    }
+
+    /// Get the import cache of the asset loader.
+    ///
+    /// Unlike the recursive asset loader, this also works for async and deferred assets
+    ///
+    /// This is only Some when the data was given by the asset loader (or when it was set explicitly)
+    pub fn import_cache(&self) -> Option<&ImportCache> {
+        self.imports.as_ref()
+    }
+
+    /// Fetch the processed output of an asset that was imported in a previous run.
+    /// Returns None if there is no import cache, or if the output is missing, outdated or corrupted.
+    pub fn cached_import(&self, key: ImportKey) -> Option<Vec<u8>> {
+        self.imports.as_ref()?.read(key)
+    }
+
+    /// Store the processed output of an imported asset so later loads can skip the import step.
+    /// Does nothing if there is no import cache.
+    pub fn store_import(&self, key: ImportKey, bytes: &[u8]) {
+        if let Some(imports) = self.imports.as_ref() {
+            imports.write(key, bytes);
+        }
+    }
 }
 
 /// An asset that will be loaded from a single unique file
@@ -87,6 +135,12 @@ pub trait Asset: Sized + 'static {
         context: Self::Context<'_>,
         settings: Self::Settings<'_>,
     ) -> Result<Self, Self::Err>;
+
+    /// Read the settings from the ``<asset>.meta.ron`` sidecar file next to the asset file.
+    /// Only used when the settings are not provided in code. Returns None if sidecar files are not supported
+    fn from_meta<'s>(_meta: &Meta) -> Option<Result<Self::Settings<'s>, MetaError>> {
+        None
+    }
 }
 
 /// Just for convience's sake
@@ -104,6 +158,43 @@ where
 {
 }
 
+/// An asset that is loaded in two phases, for assets that need a context that cannot be sent to other threads
+///
+/// The bytes of the asset are decoded into an intermediate value within a worker thread.
+/// The intermediate value is then finalized on the main thread during the assets system update stage.
+pub trait DeferredAsset: Sized + Send + Sync + 'static {
+    /// Intermediate value that is decoded in a worker thread
+    type Decoded: Send + 'static;
+
+    /// Context that will be fetched from the world to finalize the asset on the main thread
+    type Context<'w>;
+
+    /// Settings that will be used to decode the asset
+    type Settings: Send + 'static;
+
+    /// Possible error that we might return
+    type Err: std::error::Error + Send + Sync + 'static;
+
+    /// Possible extensions that are supported
+    /// If this is of 0 length, then all extensions are supported
+    fn extensions() -> &'static [&'static str];
+
+    /// Decode the bytes of the asset in a worker thread
+    fn decode(data: Data, settings: Self::Settings) -> Result<Self::Decoded, Self::Err>;
+
+    /// Fetch the context from the world. Returning None will retry finalizing the asset next frame
+    fn fetch(world: &World) -> Option<Self::Context<'_>>;
+
+    /// Finalize the decoded asset on the main thread
+    fn finalize(decoded: Self::Decoded, context: Self::Context<'_>) -> Result<Self, Self::Err>;
+
+    /// Read the settings from the ``<asset>.meta.ron`` sidecar file next to the asset file.
+    /// Only used when the settings are not provided in code. Returns None if sidecar files are not supported
+    fn from_meta(_meta: &Meta) -> Option<Result<Self::Settings, MetaError>> {
+        None
+    }
+}
+
 // UTF8 string decoder
 impl Asset for String {
     type Context<'ctx> = ();
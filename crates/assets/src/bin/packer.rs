@@ -0,0 +1,46 @@
+use assets::{ArchiveBuilder, Compression};
+use std::{fs::File, io::BufWriter, path::PathBuf, process::ExitCode};
+
+// Packs a whole assets directory into a single archive that can be mounted by the asset loader
+// Usage: packer <assets directory> <output archive> [--no-compression]
+fn main() -> ExitCode {
+    let mut args = std::env::args().skip(1);
+    let (Some(input), Some(output)) = (args.next(), args.next()) else {
+        eprintln!("Usage: packer <assets directory> <output archive> [--no-compression]");
+        return ExitCode::FAILURE;
+    };
+
+    let compression = match args.next().as_deref() {
+        Some("--no-compression") => Compression::None,
+        None => Compression::Deflate,
+        Some(other) => {
+            eprintln!("Unknown argument '{other}'");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let input = PathBuf::from(input);
+    let mut builder = ArchiveBuilder::new();
+    let count = match builder.add_directory(&input, compression) {
+        Ok(count) => count,
+        Err(error) => {
+            eprintln!("Could not read assets directory {input:?}: {error}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let result = File::create(&output)
+        .map_err(Into::into)
+        .and_then(|file| builder.write(BufWriter::new(file)));
+
+    match result {
+        Ok(_) => {
+            println!("Packed {count} files from {input:?} into '{output}'");
+            ExitCode::SUCCESS
+        }
+        Err(error) => {
+            eprintln!("Could not write archive '{output}': {error}");
+            ExitCode::FAILURE
+        }
+    }
+}
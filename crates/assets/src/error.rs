@@ -1,34 +1,196 @@
-use std::error::Error;
+use std::{
+    error::Error,
+    fmt::{Display, Formatter},
+    path::PathBuf,
+};
 use thiserror::Error;
 
+/// Location that the bytes of an asset were resolved from.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum AssetLocation {
+    /// Bytes that were imported directly or embedded within the executable.
+    Imported,
+
+    /// File within a named mount. Contains the global path of the file for loose directory mounts.
+    Mount(String, Option<PathBuf>),
+
+    /// File outside of the mounts that was loaded using its absolute path.
+    Absolute(PathBuf),
+}
+
+impl Display for AssetLocation {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AssetLocation::Imported => write!(f, "imported bytes"),
+            AssetLocation::Mount(name, Some(global)) => write!(f, "mount '{name}' at {global:?}"),
+            AssetLocation::Mount(name, None) => write!(f, "mount '{name}'"),
+            AssetLocation::Absolute(path) => write!(f, "absolute path {path:?}"),
+        }
+    }
+}
+
 /// Error that occurs when we try to load an asset
 #[derive(Error, Debug)]
 pub enum AssetLoadError {
     /// Invalid extension when parsing file path
-    #[error("Invalid '{0}' extension in file path")]
-    InvalidExtension(String),
+    #[error("Invalid '{extension}' extension in path {path:?} for asset {asset}, supported extensions are {supported:?}")]
+    InvalidExtension {
+        /// Requested asset path
+        path: PathBuf,
+        /// Type name of the asset
+        asset: String,
+        /// Extension of the requested path
+        extension: String,
+        /// Extensions supported by the asset type
+        supported: Vec<String>,
+    },
 
-    /// Dynamic file does not exist
-    #[error("Cannot load dynamic file at path '{0}'")]
-    DynamicNotFound(String),
+    /// Dynamic file exists but could not be read
+    #[error("Cannot read dynamic file of asset {path:?} from {location}")]
+    DynamicNotFound {
+        /// Requested asset path
+        path: PathBuf,
+        /// Location that the asset was resolved from
+        location: AssetLocation,
+        /// Underlying IO error
+        #[source]
+        source: std::io::Error,
+    },
 
-    /// Cached file does not exist
-    #[error("Cannot load cached filed bytes at path '{0}'")]
-    CachedNotFound(String),
+    /// Asset bytes are not cached and no mount contains the file
+    #[error("Cannot find asset {path:?} within the cached bytes or the mounts {mounts:?}")]
+    CachedNotFound {
+        /// Requested asset path
+        path: PathBuf,
+        /// Names of the mounts that were searched, in order
+        mounts: Vec<String>,
+    },
 
     /// Cannot convert to OS Str
-    #[error("Could not convert to OS str")]
-    InvalidOsStr,
+    #[error("Could not convert path {0:?} to OS str")]
+    InvalidOsStr(PathBuf),
 
     /// Missing extension in file path
-    #[error("Missing extension in file path")]
-    MissingExtension,
+    #[error("Missing extension in file path {0:?}")]
+    MissingExtension(PathBuf),
 
     /// User path not specified (Dynamic loading)
     #[error("User asset path was not specified")]
     UserPathNotSpecified,
 
     /// Error when deserializing asset
-    #[error("Deserialization error {0}")]
-    BoxedDeserialization(Box<dyn Error + Send + Sync>),
+    #[error("Could not deserialize asset {path:?} from {location} as {asset}")]
+    BoxedDeserialization {
+        /// Requested asset path
+        path: PathBuf,
+        /// Type name of the asset
+        asset: String,
+        /// Location that the asset was resolved from
+        location: AssetLocation,
+        /// Error returned by the asset implementation
+        #[source]
+        source: Box<dyn Error + Send + Sync>,
+    },
+
+    /// Error when reading an asset from a mounted archive
+    #[error("Cannot read asset {path:?} from archive mount '{mount}'")]
+    Archive {
+        /// Requested asset path
+        path: PathBuf,
+        /// Name of the archive mount
+        mount: String,
+        /// Underlying archive error
+        #[source]
+        source: ArchiveError,
+    },
+
+    /// Error when reading the settings from the sidecar meta file of an asset
+    #[error("Invalid meta file {path:?}")]
+    InvalidMeta {
+        /// Asset path of the meta file
+        path: PathBuf,
+        /// Underlying parsing error
+        #[source]
+        source: MetaError,
+    },
+
+    /// The asynchronous load was cancelled before it started since its handle was dropped
+    #[error("Load of asset {0:?} was cancelled")]
+    Cancelled(PathBuf),
+}
+
+impl AssetLoadError {
+    /// Format the error followed by all of its sources, one per line.
+    pub fn report(&self) -> String {
+        let mut output = self.to_string();
+        let mut source = self.source();
+
+        while let Some(error) = source {
+            output += &format!("\n  caused by: {error}");
+            source = error.source();
+        }
+
+        output
+    }
+}
+
+/// All the errors that occured when loading a batch of assets.
+/// Returned by [Assets::load_all_from_iter](crate::Assets::load_all_from_iter) and [Assets::wait_all_from_iter](crate::Assets::wait_all_from_iter).
+#[derive(Error, Debug)]
+pub struct AssetLoadErrors(pub Vec<AssetLoadError>);
+
+impl Display for AssetLoadErrors {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} assets failed to load", self.0.len())?;
+
+        for error in &self.0 {
+            write!(f, "\n- {}", error.report())?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Error that occurs when we try to parse the sidecar meta file of an asset
+#[derive(Error, Debug)]
+pub enum MetaError {
+    /// The meta file is not valid UTF-8
+    #[error("UTF-8 error {0}")]
+    Utf8(#[from] std::str::Utf8Error),
+
+    /// The meta file is not valid RON or does not match the expected settings
+    #[error("RON error {0}")]
+    Ron(#[from] ron::error::SpannedError),
+}
+
+/// Error that occurs when we try to read or write a packed asset archive
+#[derive(Error, Debug)]
+pub enum ArchiveError {
+    /// Underlying IO error
+    #[error("IO error {0}")]
+    Io(#[from] std::io::Error),
+
+    /// The file does not start with the archive magic bytes
+    #[error("File is not a packed asset archive")]
+    InvalidMagic,
+
+    /// The archive was written using another version of the format
+    #[error("Unsupported archive version {0}")]
+    UnsupportedVersion(u32),
+
+    /// The compression method of an entry is not known
+    #[error("Unknown compression method {0}")]
+    UnknownCompression(u8),
+
+    /// The path of an entry is not a valid relative asset path
+    #[error("Invalid entry path")]
+    InvalidPath,
+
+    /// The decompressed bytes of an entry do not match its checksum
+    #[error("Checksum mismatch for entry '{0}'")]
+    ChecksumMismatch(String),
+
+    /// The header or index contains offsets or sizes that don't fit within the archive
+    #[error("Archive is corrupted")]
+    Corrupted,
 }
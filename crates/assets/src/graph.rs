@@ -0,0 +1,156 @@
+use ahash::{AHashMap, AHashSet};
+use std::{
+    cell::RefCell,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+thread_local! {
+    // Stack of the assets that are currently being deserialized on this thread
+    // Used to record the dependencies of assets that recursively load other assets
+    pub(crate) static LOADING: RefCell<Vec<PathBuf>> = const { RefCell::new(Vec::new()) };
+}
+
+// Pushes an asset onto the loading stack and pops it back once dropped
+// This keeps the stack balanced even if the asset panics while deserializing
+pub(crate) struct LoadingGuard(());
+
+impl LoadingGuard {
+    // Mark the asset as being deserialized on this thread
+    pub(crate) fn push(asset: PathBuf) -> Self {
+        LOADING.with(|stack| stack.borrow_mut().push(asset));
+        Self(())
+    }
+
+    // Get the asset that is currently being deserialized on this thread
+    pub(crate) fn current() -> Option<PathBuf> {
+        LOADING.with(|stack| stack.borrow().last().cloned())
+    }
+}
+
+impl Drop for LoadingGuard {
+    fn drop(&mut self) {
+        LOADING.with(|stack| stack.borrow_mut().pop());
+    }
+}
+
+// Directed graph that stores which assets depend on which other assets
+#[derive(Default)]
+pub(crate) struct DependencyGraph {
+    dependencies: AHashMap<PathBuf, AHashSet<PathBuf>>,
+    dependents: AHashMap<PathBuf, AHashSet<PathBuf>>,
+}
+
+impl DependencyGraph {
+    // Record that the parent asset depends on the child asset
+    pub(crate) fn add(&mut self, parent: &Path, child: &Path) {
+        if parent == child {
+            return;
+        }
+
+        let added = self
+            .dependencies
+            .entry(parent.to_path_buf())
+            .or_default()
+            .insert(child.to_path_buf());
+
+        if added {
+            log::debug!("Asset {:?} depends on asset {:?}", parent, child);
+            self.dependents
+                .entry(child.to_path_buf())
+                .or_default()
+                .insert(parent.to_path_buf());
+        }
+    }
+
+    // Get the direct dependencies of an asset
+    pub(crate) fn dependencies(&self, asset: &Path) -> Vec<PathBuf> {
+        Self::direct(&self.dependencies, asset)
+    }
+
+    // Get the assets that directly depend on an asset
+    pub(crate) fn dependents(&self, asset: &Path) -> Vec<PathBuf> {
+        Self::direct(&self.dependents, asset)
+    }
+
+    // Get all the dependencies of an asset recursively (without the asset itself)
+    pub(crate) fn transitive_dependencies(&self, asset: &Path) -> Vec<PathBuf> {
+        Self::transitive(&self.dependencies, asset)
+    }
+
+    // Get all the assets that depend on an asset recursively (without the asset itself)
+    pub(crate) fn transitive_dependents(&self, asset: &Path) -> Vec<PathBuf> {
+        Self::transitive(&self.dependents, asset)
+    }
+
+    fn direct(edges: &AHashMap<PathBuf, AHashSet<PathBuf>>, asset: &Path) -> Vec<PathBuf> {
+        edges
+            .get(asset)
+            .map(|set| set.iter().cloned().collect())
+            .unwrap_or_default()
+    }
+
+    // Depth first traversal that also handles cyclic dependencies
+    fn transitive(edges: &AHashMap<PathBuf, AHashSet<PathBuf>>, asset: &Path) -> Vec<PathBuf> {
+        let mut visited = AHashSet::<&Path>::new();
+        let mut output = Vec::new();
+        let mut pending = vec![asset];
+        visited.insert(asset);
+
+        while let Some(current) = pending.pop() {
+            for next in edges.get(current).into_iter().flatten() {
+                if visited.insert(next) {
+                    output.push(next.clone());
+                    pending.push(next);
+                }
+            }
+        }
+
+        output
+    }
+}
+
+/// The loading progress of a root asset and all of its known dependencies, or of a batch of asynchronous loads.
+#[derive(Default, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct AssetProgress {
+    /// Number of assets whose bytes are loaded.
+    pub loaded: usize,
+
+    /// Total number of assets (root asset included).
+    pub total: usize,
+
+    /// Number of bytes that were read for the loaded assets.
+    pub bytes: usize,
+}
+
+impl AssetProgress {
+    /// Get the progress as a value between 0 and 1.
+    pub fn fraction(&self) -> f32 {
+        if self.total == 0 {
+            0.0
+        } else {
+            self.loaded as f32 / self.total as f32
+        }
+    }
+
+    /// Check if the root asset and all of its dependencies are loaded.
+    pub fn is_done(&self) -> bool {
+        self.total > 0 && self.loaded == self.total
+    }
+}
+
+/// Reference counted handle that keeps the cached bytes of an asset and of its dependencies alive.
+/// Created using [Assets::retain](crate::Assets::retain). Once all the handles of an asset are dropped,
+/// its cached bytes (and the bytes of dependencies that are not used by other retained assets) are freed.
+#[derive(Clone)]
+pub struct AssetRef {
+    pub(crate) path: Arc<Path>,
+    pub(crate) _counter: Arc<()>,
+}
+
+impl AssetRef {
+    /// Get the path of the retained asset.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
@@ -0,0 +1,279 @@
+use parking_lot::RwLock;
+use std::{
+    fmt::Display,
+    hash::{Hash, Hasher},
+    io::Write,
+    path::PathBuf,
+    sync::{
+        atomic::{AtomicU32, Ordering},
+        Arc,
+    },
+};
+
+// Magic bytes at the very start of every cached import
+const MAGIC: [u8; 4] = *b"CFIC";
+
+/// Current version of the import cache. Cached outputs that were created with any other version are ignored.
+pub const IMPORT_VERSION: u32 = 3;
+
+// Magic (4) + version (4) + checksum (4) + length (8)
+const HEADER_SIZE: usize = 20;
+
+// Fixed keys of the hasher used for import keys. These must never change (without bumping the import version)
+const KEYS: [u64; 2] = [0x243f_6a88_85a3_08d3, 0x1319_8a2e_0370_7344];
+
+/// Name of the hashing algorithm used for import keys. Stored within the file names of the cached imports.
+pub const IMPORT_HASHER: &str = "sip24";
+
+/// Create a hasher that uses fixed keys, so the hashes it produces are stable between runs.
+/// This uses SipHash-2-4, which is a specified algorithm, so the hashes don't depend on the toolchain, platform, or CPU features.
+pub fn stable_hasher() -> StableHasher {
+    StableHasher::new(KEYS[0], KEYS[1])
+}
+
+/// Implementation of SipHash-2-4 that always writes integers as little endian bytes.
+/// Created using [stable_hasher].
+#[derive(Clone, Debug)]
+pub struct StableHasher {
+    state: [u64; 4],
+    tail: u64,
+    filled: usize,
+    length: usize,
+}
+
+impl StableHasher {
+    // Create a hasher using the given 128 bit key
+    pub(crate) fn new(k0: u64, k1: u64) -> Self {
+        Self {
+            state: [
+                k0 ^ 0x736f_6d65_7073_6575,
+                k1 ^ 0x646f_7261_6e64_6f6d,
+                k0 ^ 0x6c79_6765_6e65_7261,
+                k1 ^ 0x7465_6462_7974_6573,
+            ],
+            tail: 0,
+            filled: 0,
+            length: 0,
+        }
+    }
+
+    // Execute a single SipRound on the state
+    fn round(state: &mut [u64; 4]) {
+        let [v0, v1, v2, v3] = state;
+        *v0 = v0.wrapping_add(*v1);
+        *v1 = v1.rotate_left(13) ^ *v0;
+        *v0 = v0.rotate_left(32);
+        *v2 = v2.wrapping_add(*v3);
+        *v3 = v3.rotate_left(16) ^ *v2;
+        *v0 = v0.wrapping_add(*v3);
+        *v3 = v3.rotate_left(21) ^ *v0;
+        *v2 = v2.wrapping_add(*v1);
+        *v1 = v1.rotate_left(17) ^ *v2;
+        *v2 = v2.rotate_left(32);
+    }
+
+    // Mix a full 8 byte word into the state
+    fn compress(&mut self, word: u64) {
+        self.state[3] ^= word;
+        Self::round(&mut self.state);
+        Self::round(&mut self.state);
+        self.state[0] ^= word;
+    }
+}
+
+impl Hasher for StableHasher {
+    fn write(&mut self, mut bytes: &[u8]) {
+        self.length = self.length.wrapping_add(bytes.len());
+
+        // Complete the word that was partially filled by the last write
+        while self.filled > 0 && !bytes.is_empty() {
+            self.tail |= (bytes[0] as u64) << (8 * self.filled);
+            self.filled = (self.filled + 1) % 8;
+            bytes = &bytes[1..];
+
+            if self.filled == 0 {
+                let word = std::mem::take(&mut self.tail);
+                self.compress(word);
+            }
+        }
+
+        if self.filled > 0 {
+            return;
+        }
+
+        let mut words = bytes.chunks_exact(8);
+        for word in words.by_ref() {
+            self.compress(u64::from_le_bytes(word.try_into().unwrap()));
+        }
+
+        for (i, byte) in words.remainder().iter().enumerate() {
+            self.tail |= (*byte as u64) << (8 * i);
+        }
+        self.filled = words.remainder().len();
+    }
+
+    fn write_u16(&mut self, i: u16) {
+        self.write(&i.to_le_bytes());
+    }
+
+    fn write_u32(&mut self, i: u32) {
+        self.write(&i.to_le_bytes());
+    }
+
+    fn write_u64(&mut self, i: u64) {
+        self.write(&i.to_le_bytes());
+    }
+
+    fn write_u128(&mut self, i: u128) {
+        self.write(&i.to_le_bytes());
+    }
+
+    fn write_usize(&mut self, i: usize) {
+        self.write_u64(i as u64);
+    }
+
+    fn write_i16(&mut self, i: i16) {
+        self.write_u16(i as u16);
+    }
+
+    fn write_i32(&mut self, i: i32) {
+        self.write_u32(i as u32);
+    }
+
+    fn write_i64(&mut self, i: i64) {
+        self.write_u64(i as u64);
+    }
+
+    fn write_i128(&mut self, i: i128) {
+        self.write_u128(i as u128);
+    }
+
+    fn write_isize(&mut self, i: isize) {
+        self.write_u64(i as u64);
+    }
+
+    fn finish(&self) -> u64 {
+        let mut state = self.state;
+        let last = ((self.length as u64) << 56) | self.tail;
+
+        state[3] ^= last;
+        Self::round(&mut state);
+        Self::round(&mut state);
+        state[0] ^= last;
+
+        state[2] ^= 0xff;
+        for _ in 0..4 {
+            Self::round(&mut state);
+        }
+
+        state[0] ^ state[1] ^ state[2] ^ state[3]
+    }
+}
+
+// Used to create unique temporary files when multiple threads write cached outputs at the same time
+static TEMPORARY: AtomicU32 = AtomicU32::new(0);
+
+/// Unique key of a single processed asset within the import cache.
+/// The key is calculated from the name of the importer, the raw bytes of the source asset and the import settings,
+/// so changing any of them will cause the asset to be imported again.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct ImportKey(u64);
+
+impl ImportKey {
+    /// Create an import key from the name of the importer, the bytes of the source asset, and the import settings.
+    pub fn new(importer: &str, source: &[u8], settings: &impl Hash) -> Self {
+        let mut hasher = stable_hasher();
+        IMPORT_HASHER.hash(&mut hasher);
+        IMPORT_VERSION.hash(&mut hasher);
+        importer.hash(&mut hasher);
+        source.hash(&mut hasher);
+        settings.hash(&mut hasher);
+        Self(hasher.finish())
+    }
+}
+
+impl Display for ImportKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{IMPORT_HASHER}-{:016x}", self.0)
+    }
+}
+
+/// Handle to the on-disk cache that stores the processed outputs of imported assets.
+/// Cheap to clone, and every clone shares the same cache directory as the asset loader it was fetched from.
+#[derive(Default, Clone)]
+pub struct ImportCache {
+    directory: Arc<RwLock<Option<PathBuf>>>,
+}
+
+impl ImportCache {
+    // Update the directory that contains the cached outputs
+    pub(crate) fn set_directory(&self, directory: Option<PathBuf>) {
+        *self.directory.write() = directory;
+    }
+
+    /// Get the directory that contains the cached outputs (if enabled).
+    pub fn directory(&self) -> Option<PathBuf> {
+        self.directory.read().clone()
+    }
+
+    /// Read a cached output. Returns None if the cache is disabled, or if the output is missing, outdated or corrupted.
+    pub fn read(&self, key: ImportKey) -> Option<Vec<u8>> {
+        let path = self.directory()?.join(format!("{key}.bin"));
+        let bytes = std::fs::read(&path).ok()?;
+
+        if bytes.len() < HEADER_SIZE || bytes[..4] != MAGIC {
+            log::warn!("Cached import {:?} is corrupted, ignoring it", path);
+            return None;
+        }
+
+        let version = u32::from_le_bytes(bytes[4..8].try_into().unwrap());
+        let checksum = u32::from_le_bytes(bytes[8..12].try_into().unwrap());
+        let length = u64::from_le_bytes(bytes[12..20].try_into().unwrap());
+        let payload = &bytes[HEADER_SIZE..];
+
+        if version != IMPORT_VERSION {
+            return None;
+        }
+
+        if payload.len() as u64 != length || crc32fast::hash(payload) != checksum {
+            log::warn!("Cached import {:?} is corrupted, ignoring it", path);
+            return None;
+        }
+
+        log::debug!("Loaded cached import {:?}", path);
+        Some(payload.to_vec())
+    }
+
+    /// Write a processed output into the cache. Does nothing if the cache is disabled.
+    /// Failing to write is not fatal, the asset will simply be imported again.
+    pub fn write(&self, key: ImportKey, bytes: &[u8]) {
+        let Some(directory) = self.directory() else {
+            return;
+        };
+
+        let path = directory.join(format!("{key}.bin"));
+        let temporary = TEMPORARY.fetch_add(1, Ordering::Relaxed);
+        let temporary = directory.join(format!("{key}.{}.{temporary}.tmp", std::process::id()));
+
+        // Write into a temporary file first so other threads and processes never read partially written outputs
+        let result = std::fs::create_dir_all(&directory)
+            .and_then(|_| {
+                let mut file = std::fs::File::create(&temporary)?;
+                file.write_all(&MAGIC)?;
+                file.write_all(&IMPORT_VERSION.to_le_bytes())?;
+                file.write_all(&crc32fast::hash(bytes).to_le_bytes())?;
+                file.write_all(&(bytes.len() as u64).to_le_bytes())?;
+                file.write_all(bytes)?;
+                file.flush()
+            })
+            .and_then(|_| std::fs::rename(&temporary, &path));
+
+        match result {
+            Ok(_) => log::debug!("Saved cached import {:?}", path),
+            Err(error) => {
+                log::warn!("Could not save cached import {:?}: {}", path, error);
+                std::fs::remove_file(&temporary).ok();
+            }
+        }
+    }
+}
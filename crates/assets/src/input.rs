@@ -11,6 +11,12 @@ pub trait AssetInput<'str, 'ctx, 'stg, A: Asset> {
 
     /// Get the path of the asset input
     fn path(&self) -> &'str str;
+
+    /// Check if the settings were provided in code.
+    /// If they were not, the settings are read from the sidecar meta file of the asset (if it exists)
+    fn has_settings(&self) -> bool {
+        false
+    }
 }
 
 // No context nor settings, assumes both are Default
@@ -71,4 +77,8 @@ impl<'str, 'ctx, 'stg, A: Asset> AssetInput<'str, 'ctx, 'stg, A>
     fn path(&self) -> &'str str {
         self.0
     }
+
+    fn has_settings(&self) -> bool {
+        true
+    }
 }
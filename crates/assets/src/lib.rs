@@ -2,16 +2,32 @@
 
 //! TODO: Docs
 
+mod archive;
 mod asset;
 mod error;
+mod graph;
+mod import;
 mod input;
 mod loader;
 mod macros;
+mod meta;
+mod mount;
+mod queue;
 mod raw;
+mod reload;
 mod system;
 mod tests;
+mod watcher;
+pub use archive::*;
 pub use asset::*;
 pub use error::*;
+pub use graph::*;
+pub use import::*;
 pub use input::*;
 pub use loader::*;
+pub use meta::*;
+pub use mount::*;
+pub use queue::*;
+pub use reload::*;
 pub use system::*;
+pub(crate) use watcher::*;
@@ -1,5 +1,11 @@
-use crate::{Asset, AssetInput, AssetLoadError, AsyncAsset};
+use crate::{
+    Archive, ArchiveError, Asset, AssetInput, AssetLoadError, AssetLoadErrors, AssetLocation,
+    AssetProgress, AssetRef, AsyncAsset, AsyncLoadState, AsyncMounts, AsyncWatcher, DeferredAsset,
+    DependencyGraph, ImportCache, ImportKey, LoadCompleted, LoadQueue, LoadingGuard, Meta,
+    MetaError, Mount, MountPoint, Watching, DEFAULT_LOAD_PRIORITY,
+};
 use ahash::AHashMap;
+use ahash::AHashSet;
 use parking_lot::{Mutex, RwLock};
 use std::{
     any::Any,
@@ -8,29 +14,92 @@ use std::{
     path::{Path, PathBuf},
     str::FromStr,
     sync::{
+        atomic::{AtomicUsize, Ordering},
         mpsc::{Receiver, Sender},
-        Arc,
+        Arc, Weak,
     },
 };
+use world::World;
 
 /// This is a handle to a specific asset that we are currently loading in asynchronously
 ///
 /// This can be used to fetch for the state of the asset and to fetch for it after it loaded completely
+///
+/// Dropping the handle cancels the load if it was not started yet
 pub struct AsyncHandle<A: Asset> {
     _phantom: PhantomData<A>,
     index: usize,
+    state: Arc<AsyncLoadState>,
+}
+
+impl<A: Asset> AsyncHandle<A> {
+    /// Get the unique ID of this load (same as the one stored within the [LoadCompleted] event).
+    pub fn id(&self) -> usize {
+        self.index
+    }
+
+    /// Get the path of the asset that is being loaded.
+    pub fn path(&self) -> &Path {
+        &self.state.path
+    }
+}
+
+impl<A: Asset> Drop for AsyncHandle<A> {
+    fn drop(&mut self) {
+        self.state.cancelled.store(true, Ordering::Relaxed);
+    }
+}
+
+/// This is a handle to a specific [deferred asset](crate::DeferredAsset) that we are currently decoding or finalizing
+///
+/// This can be used to fetch the asset once it was finalized on the main thread
+pub struct DeferredHandle<A: DeferredAsset> {
+    _phantom: PhantomData<A>,
+    index: usize,
 }
 
 // Used for async asset loading
 type AsyncBoxedResult = Result<Box<dyn Any + Send + Sync>, AssetLoadError>;
-type AsyncLoadedAssets = Mutex<Vec<Option<AsyncBoxedResult>>>;
-type AsyncChannelResult = (AsyncBoxedResult, usize);
+type AsyncLoadedAssets = Mutex<AHashMap<usize, AsyncSlot>>;
+
+// Slot of a single asynchronous or deferred load that was not taken out of the loader yet
+// The state is only set for asynchronous loads, so we can free the slot once their handle gets dropped
+struct AsyncSlot {
+    state: Option<Arc<AsyncLoadState>>,
+    result: Option<AsyncBoxedResult>,
+}
+type AsyncChannelResult = (AsyncBoxedResult, usize, Arc<AsyncLoadState>);
 type AsyncLoadedBytes = Arc<RwLock<AHashMap<PathBuf, Arc<[u8]>>>>;
 
-// Paths we can use to hijack default engine assets
-// TODO: This might not be safe but tbh I couldn't care
-type HijackPaths = AHashMap<PathBuf, PathBuf>;
-type AsyncHijackPaths = Arc<RwLock<HijackPaths>>;
+// Used for deferred asset loading
+// The finalize function gives back the decoded value if the context could not be fetched
+type DecodedBoxed = Box<dyn Any + Send>;
+// The path of the asset is kept around so finalization errors can point back to its file
+type FinalizeFn =
+    fn(DecodedBoxed, &World, &Path, &ByteSources) -> Result<AsyncBoxedResult, DecodedBoxed>;
+type AsyncDecodedResult = (
+    Result<DecodedBoxed, AssetLoadError>,
+    FinalizeFn,
+    usize,
+    PathBuf,
+);
+
+// Names of the mounts that the cached bytes of each asset were resolved from
+type AsyncResolvedMounts = Arc<RwLock<AHashMap<PathBuf, String>>>;
+
+// Dependencies between assets (shared with the async loading threads)
+type AsyncGraph = Arc<RwLock<DependencyGraph>>;
+
+// Everything that we need to fetch the bytes of an asset (shared with the async loading threads)
+#[derive(Clone)]
+pub(crate) struct ByteSources {
+    bytes: AsyncLoadedBytes,
+    mounts: AsyncMounts,
+    resolved: AsyncResolvedMounts,
+    watcher: AsyncWatcher,
+    graph: AsyncGraph,
+    imports: ImportCache,
+}
 
 pub use cfg_if;
 pub use include_dir;
@@ -45,28 +114,75 @@ pub struct Assets {
     receiver: Receiver<AsyncChannelResult>,
 
     // Keep track of the assets that were sucessfully loaded
-    // The value corresponding to each key might be None in the case that the asset did not load (yet)
+    // The result of each slot might be None in the case that the asset did not load (yet)
     loaded: AsyncLoadedAssets,
+    next: AtomicUsize,
+
+    // Asynchronous loads that were not started yet, sorted by priority
+    queue: Arc<LoadQueue>,
+
+    // Asynchronous loads that completed since their events were last taken
+    completed: Mutex<Vec<LoadCompleted>>,
 
-    // We can use these re-definitions to allow the user to change default asset paths
-    hijack: AsyncHijackPaths,
+    // Directories and archives that we will look into when loading assets dynamically
+    // These are sorted by priority so users can override the default engine assets
+    mounts: AsyncMounts,
+    resolved: AsyncResolvedMounts,
 
     // Keep track of the bytes that were loaded in other threads
     // The value might be none in the case that the bytes were not loaded
     // The path buf contains the local path of each asset
     bytes: AsyncLoadedBytes,
+
+    // Watches the files of the dynamically loaded assets for hot-reloading
+    // The receiver contains the global paths of the files that were modified
+    watcher: AsyncWatcher,
+    modified: Receiver<PathBuf>,
+
+    // Asset paths of the assets that were modified since the last time we refreshed the changes
+    changed: Mutex<Vec<PathBuf>>,
+
+    // Dependencies between assets, and the assets whose bytes must be kept alive
+    // Imported assets cannot be loaded again, so their bytes are never freed
+    graph: AsyncGraph,
+    retained: Mutex<AHashMap<PathBuf, Weak<()>>>,
+    imported: RwLock<AHashSet<PathBuf>>,
+
+    // Deferred assets that were decoded in other threads and that must be finalized on the main thread
+    decoded_sender: Sender<AsyncDecodedResult>,
+    decoded_receiver: Receiver<AsyncDecodedResult>,
+    pending: Mutex<Vec<(DecodedBoxed, FinalizeFn, usize, PathBuf)>>,
+
+    // Processed outputs of imported assets that are stored on disk between runs
+    imports: ImportCache,
 }
 
 impl Default for Assets {
     fn default() -> Self {
         let (sender, receiver) = std::sync::mpsc::channel::<AsyncChannelResult>();
+        let (modified_sender, modified) = std::sync::mpsc::channel::<PathBuf>();
+        let (decoded_sender, decoded_receiver) = std::sync::mpsc::channel::<AsyncDecodedResult>();
 
         Self {
             loaded: Default::default(),
+            queue: Default::default(),
+            next: Default::default(),
+            completed: Default::default(),
             bytes: Default::default(),
             receiver,
             sender,
-            hijack: Default::default(),
+            mounts: Default::default(),
+            resolved: Default::default(),
+            watcher: Arc::new(Mutex::new(Watching::new(modified_sender))),
+            modified,
+            changed: Default::default(),
+            graph: Default::default(),
+            retained: Default::default(),
+            imported: Default::default(),
+            decoded_sender,
+            decoded_receiver,
+            pending: Default::default(),
+            imports: Default::default(),
         }
     }
 }
@@ -88,19 +204,124 @@ impl Assets {
             .unwrap_or(path.as_ref())
             .to_path_buf();
 
+        self.imported.write().insert(path.clone());
         self.bytes
             .write()
             .entry(path)
             .or_insert_with(|| Arc::from(bytes));
     }
 
-    /// Add a "hijack" path that will overwrite the path for a specific asset.
-    /// This allows users to overwrite engine assets using their own custom assets.
-    pub fn hijack(&self, og: impl AsRef<Path>, new: impl AsRef<Path>) {
-        let mut write = self.hijack.write();
-        let og = og.as_ref().to_path_buf();
-        let new = new.as_ref().to_path_buf();
-        write.insert(og, new);
+    /// Mount a directory of loose asset files under a unique name.
+    /// Mounts with a higher priority are searched first whenever an asset is not cached.
+    pub fn mount_directory(&self, name: &str, path: impl AsRef<Path>, priority: i32) {
+        self.mount(
+            name,
+            Mount::Directory(path.as_ref().to_path_buf()),
+            priority,
+        );
+    }
+
+    /// Open a packed asset archive and mount it under a unique name.
+    /// Mounts with a higher priority are searched first whenever an asset is not cached.
+    pub fn mount_archive(
+        &self,
+        name: &str,
+        path: impl AsRef<Path>,
+        priority: i32,
+    ) -> Result<(), ArchiveError> {
+        let archive = Archive::open(path.as_ref())?;
+        self.mount(name, Mount::Archive(archive), priority);
+        Ok(())
+    }
+
+    /// Add a new mount with a unique name and a priority, replacing any mount with the same name.
+    /// Mounts with the same priority are searched in the order they were mounted.
+    pub fn mount(&self, name: &str, mount: Mount, priority: i32) {
+        let mut mounts = self.mounts.write();
+        let point = MountPoint {
+            name: name.to_owned(),
+            priority,
+            mount,
+        };
+
+        if let Some(index) = mounts.iter().position(|point| point.name == name) {
+            mounts[index] = point;
+        } else {
+            log::debug!("Mounted '{name}' with priority {priority}");
+            mounts.push(point);
+        }
+
+        mounts.sort_by_key(|point| std::cmp::Reverse(point.priority));
+        drop(mounts);
+        self.uncache_resolved();
+    }
+
+    /// Remove a mount using its name and return it.
+    pub fn unmount(&self, name: &str) -> Option<Mount> {
+        let mut mounts = self.mounts.write();
+        let index = mounts.iter().position(|point| point.name == name)?;
+        let point = mounts.remove(index);
+        drop(mounts);
+        self.uncache_resolved();
+        log::debug!("Unmounted '{name}'");
+        Some(point.mount)
+    }
+
+    /// Get the names and priorities of the current mounts, in the order they are searched.
+    pub fn mounts(&self) -> Vec<(String, i32)> {
+        self.mounts
+            .read()
+            .iter()
+            .map(|point| (point.name.clone(), point.priority))
+            .collect()
+    }
+
+    /// Get the name of the mount that the cached bytes of an asset were loaded from.
+    /// Returns None if the asset was not loaded yet or if it was imported directly.
+    pub fn resolved(&self, asset: &str) -> Option<String> {
+        self.resolved.read().get(Path::new(asset)).cloned()
+    }
+
+    /// Get the name of the mount that would be used to load an asset right now.
+    pub fn resolve(&self, asset: &str) -> Option<String> {
+        self.mounts
+            .read()
+            .iter()
+            .find(|point| point.mount.contains(Path::new(asset)))
+            .map(|point| point.name.clone())
+    }
+
+    /// List the asset paths of all the files that match a glob pattern, across all mounts and imported assets.
+    /// "*" and "?" match within a single directory, and "**" matches any number of directories.
+    pub fn list(&self, pattern: &str) -> Vec<PathBuf> {
+        let directory = crate::mount::literal_prefix(pattern);
+        let mut paths = self
+            .mounts
+            .read()
+            .iter()
+            .flat_map(|point| point.mount.paths(&directory))
+            .collect::<Vec<_>>();
+
+        paths.extend(
+            self.bytes
+                .read()
+                .keys()
+                .filter_map(|path| crate::archive::normalize(path)),
+        );
+
+        paths.retain(|path| crate::mount::matches(pattern, path));
+        paths.sort();
+        paths.dedup();
+        paths.into_iter().map(PathBuf::from).collect()
+    }
+
+    // Uncache the bytes that were loaded from mounts, since another mount might override them now
+    fn uncache_resolved(&self) {
+        let mut bytes = self.bytes.write();
+        let mut resolved = self.resolved.write();
+        for (path, _) in resolved.drain() {
+            bytes.remove(&path);
+        }
     }
 
     /// Uncache the bytes of an already cached asset. Used for hot-reloading.
@@ -112,6 +333,193 @@ impl Assets {
         Some(())
     }
 
+    /// Fetch the modified files from the file watcher, uncache their bytes, and mark their assets as changed.
+    /// This is called automatically at the start of every frame, and it clears the previously changed assets.
+    pub fn refresh_changes(&self) {
+        // Convert the global paths back to asset paths (without holding the bytes lock)
+        let assets = {
+            let watcher = self.watcher.lock();
+            watcher
+                .as_ref()
+                .map(|watching| {
+                    self.modified
+                        .try_iter()
+                        .filter_map(|global| watching.asset(&global).map(Path::to_path_buf))
+                        .collect::<Vec<_>>()
+                })
+                .unwrap_or_default()
+        };
+
+        self.mark_changed(assets);
+    }
+
+    // Uncache the bytes of the modified assets and mark them (and their dependents) as changed
+    pub(crate) fn mark_changed(&self, assets: Vec<PathBuf>) {
+        let mut changed = self.changed.lock();
+        changed.clear();
+
+        for asset in assets {
+            if !changed.contains(&asset) {
+                self.bytes.write().remove(&asset);
+                log::debug!("Asset {:?} was modified, un-cached its bytes", asset);
+                changed.push(asset);
+            }
+        }
+
+        // Assets that depend on a modified asset must be reloaded as well
+        let graph = self.graph.read();
+        for dependent in changed
+            .clone()
+            .iter()
+            .flat_map(|asset| graph.transitive_dependents(asset))
+        {
+            if !changed.contains(&dependent) {
+                log::debug!("Asset {:?} depends on a modified asset", dependent);
+                changed.push(dependent);
+            }
+        }
+    }
+
+    /// Set the directory that stores the processed outputs of imported assets between runs.
+    /// The import cache is disabled when this is None (the default).
+    pub fn set_import_cache(&self, directory: Option<PathBuf>) {
+        self.imports.set_directory(directory);
+    }
+
+    /// Get the directory that stores the processed outputs of imported assets (if enabled).
+    pub fn import_cache(&self) -> Option<PathBuf> {
+        self.imports.directory()
+    }
+
+    /// Fetch the processed output of an asset that was imported in a previous run.
+    /// Returns None if the import cache is disabled, or if the output is missing, outdated or corrupted.
+    pub fn cached_import(&self, key: ImportKey) -> Option<Vec<u8>> {
+        self.imports.read(key)
+    }
+
+    /// Store the processed output of an imported asset so later loads can skip the import step.
+    /// Does nothing if the import cache is disabled.
+    pub fn store_import(&self, key: ImportKey, bytes: &[u8]) {
+        self.imports.write(key, bytes);
+    }
+
+    /// Record that an asset depends on another asset.
+    /// Dependencies loaded recursively through [Data::loader](crate::Data::loader) are recorded automatically.
+    pub fn add_dependency(&self, parent: impl AsRef<Path>, child: impl AsRef<Path>) {
+        self.graph.write().add(parent.as_ref(), child.as_ref());
+    }
+
+    /// Get the assets that an asset directly depends on.
+    pub fn dependencies(&self, asset: &str) -> Vec<PathBuf> {
+        self.graph.read().dependencies(Path::new(asset))
+    }
+
+    /// Get the assets that directly depend on an asset.
+    pub fn dependents(&self, asset: &str) -> Vec<PathBuf> {
+        self.graph.read().dependents(Path::new(asset))
+    }
+
+    /// Get the loading progress of a root asset including all of its known dependencies.
+    pub fn progress(&self, root: &str) -> AssetProgress {
+        let root = Path::new(root);
+        let mut assets = self.graph.read().transitive_dependencies(root);
+        assets.push(root.to_path_buf());
+
+        let bytes = self.bytes.read();
+        let loaded = assets.iter().filter_map(|x| bytes.get(x));
+        AssetProgress {
+            loaded: loaded.clone().count(),
+            total: assets.len(),
+            bytes: loaded.map(|x| x.len()).sum(),
+        }
+    }
+
+    /// Keep the cached bytes of an asset and of its dependencies alive until the returned handles are dropped.
+    pub fn retain(&self, asset: &str) -> AssetRef {
+        let path = PathBuf::from(asset);
+        let mut retained = self.retained.lock();
+        let weak = retained.entry(path.clone()).or_default();
+
+        let counter = weak.upgrade().unwrap_or_else(|| {
+            let counter = Arc::new(());
+            *weak = Arc::downgrade(&counter);
+            counter
+        });
+
+        AssetRef {
+            path: Arc::from(path),
+            _counter: counter,
+        }
+    }
+
+    /// Free the cached bytes of the assets whose handles were all dropped, along with their unused dependencies.
+    /// This is called automatically at the start of every frame.
+    pub fn collect_unused(&self) {
+        let mut retained = self.retained.lock();
+        if retained.values().all(|weak| weak.strong_count() > 0) {
+            return;
+        }
+
+        // Assets that are still used by the alive roots
+        let graph = self.graph.read();
+        let alive = retained
+            .iter()
+            .filter(|(_, weak)| weak.strong_count() > 0)
+            .flat_map(|(root, _)| {
+                let mut assets = graph.transitive_dependencies(root);
+                assets.push(root.clone());
+                assets
+            })
+            .collect::<AHashSet<_>>();
+
+        // Remove the roots that don't have any handles anymore
+        let mut unused = Vec::new();
+        retained.retain(|root, weak| {
+            let dead = weak.strong_count() == 0;
+            if dead {
+                unused.push(root.clone());
+                unused.extend(graph.transitive_dependencies(root));
+            }
+            !dead
+        });
+
+        let imported = self.imported.read();
+        let mut bytes = self.bytes.write();
+        for asset in unused {
+            if !alive.contains(&asset)
+                && !imported.contains(&asset)
+                && bytes.remove(&asset).is_some()
+            {
+                log::debug!("Freed the cached bytes of unused asset {:?}", asset);
+            }
+        }
+    }
+
+    /// Get the paths of the assets whose files were modified since the last frame.
+    pub fn changed(&self) -> Vec<PathBuf> {
+        self.changed.lock().clone()
+    }
+
+    /// Check if the file of an asset was modified since the last frame.
+    pub fn is_changed(&self, path: &str) -> bool {
+        let path = Path::new(path);
+        self.changed.lock().iter().any(|changed| changed == path)
+    }
+
+    /// Reload an asset if its file was modified since the last frame.
+    /// Returns None if the asset did not change, so consumers can rebuild their assets in place.
+    pub fn reloaded<'str, 'ctx, 'stg, A: Asset>(
+        &self,
+        input: impl AssetInput<'str, 'ctx, 'stg, A>,
+    ) -> Option<Result<A, AssetLoadError>> {
+        if !self.is_changed(input.path()) {
+            return None;
+        }
+
+        log::debug!("Hot-reloading asset {:?}...", input.path());
+        Some(self.load(input))
+    }
+
     /// Checks if the asset loader will load in assets at runtime or if they will be packed.
     pub fn packed(&self) -> bool {
         cfg_if::cfg_if! {
@@ -126,9 +534,15 @@ impl Assets {
     /// Get the global path of the file that is used by an asset.
     pub fn path(&self, asset: &str) -> Option<PathBuf> {
         let owned = PathBuf::from_str(asset).ok()?;
-        let read = self.hijack.read();
-        let owned = read.get(&owned).unwrap_or(&owned);
-        owned.is_absolute().then(|| owned.clone())
+
+        if owned.is_absolute() {
+            return Some(owned);
+        }
+
+        self.mounts
+            .read()
+            .iter()
+            .find_map(|point| point.mount.global(&owned))
     }
 }
 
@@ -136,17 +550,61 @@ impl Assets {
 impl Assets {
     // Check if the extension of a file is valid
     fn validate<A: Asset>(path: &Path) -> Result<(), AssetLoadError> {
+        Self::validate_extensions::<A>(path, A::extensions())
+    }
+
+    // Check if the extension of a file is one of the given extensions
+    // The type is only used to name the asset within the returned error
+    fn validate_extensions<T>(path: &Path, extensions: &[&str]) -> Result<(), AssetLoadError> {
         let (_, extension) = path
             .file_name()
             .and_then(OsStr::to_str)
-            .ok_or(AssetLoadError::InvalidOsStr)?
+            .ok_or_else(|| AssetLoadError::InvalidOsStr(path.to_owned()))?
             .split_once('.')
-            .ok_or(AssetLoadError::MissingExtension)?;
+            .ok_or_else(|| AssetLoadError::MissingExtension(path.to_owned()))?;
 
         // If the asset has no extensions, we shall not check
-        ((A::extensions().contains(&extension)) || A::extensions().is_empty())
+        (extensions.contains(&extension) || extensions.is_empty())
             .then_some(())
-            .ok_or_else(|| AssetLoadError::InvalidExtension(extension.to_owned()))
+            .ok_or_else(|| AssetLoadError::InvalidExtension {
+                path: path.to_owned(),
+                asset: utils::pretty_type_name::<T>(),
+                extension: extension.to_owned(),
+                supported: extensions.iter().map(|x| x.to_string()).collect(),
+            })
+    }
+
+    // Get the location that the bytes of an asset were resolved from
+    fn location(sources: &ByteSources, path: &Path) -> AssetLocation {
+        if path.is_absolute() {
+            return AssetLocation::Absolute(path.to_owned());
+        }
+
+        let Some(name) = sources.resolved.read().get(path).cloned() else {
+            return AssetLocation::Imported;
+        };
+
+        let global = sources
+            .mounts
+            .read()
+            .iter()
+            .find(|point| point.name == name)
+            .and_then(|point| point.mount.global(path));
+        AssetLocation::Mount(name, global)
+    }
+
+    // Wrap the error returned by an asset implementation with the context of the asset
+    fn deserialization_error<A>(
+        sources: &ByteSources,
+        path: &Path,
+        err: impl std::error::Error + Send + Sync + 'static,
+    ) -> AssetLoadError {
+        AssetLoadError::BoxedDeserialization {
+            path: path.to_owned(),
+            asset: utils::pretty_type_name::<A>(),
+            location: Self::location(sources, path),
+            source: Box::new(err),
+        }
     }
 
     // Convert a path to it's raw name and extension
@@ -160,66 +618,132 @@ impl Assets {
         (name, extension)
     }
 
-    // Load bytes either dynamically or load cached bytes
-    fn load_bytes(
-        bytes: &AsyncLoadedBytes,
-        hijack: AsyncHijackPaths,
-        owned: PathBuf,
-    ) -> Result<Arc<[u8]>, AssetLoadError> {
-        // Load the bytes from cached bytes first
-        let mut loaded = Self::load_cached_bytes(bytes, &owned);
+    // Clone the sources that must be sent to the loading threads
+    fn sources(&self) -> ByteSources {
+        ByteSources {
+            bytes: self.bytes.clone(),
+            mounts: self.mounts.clone(),
+            resolved: self.resolved.clone(),
+            watcher: self.watcher.clone(),
+            graph: self.graph.clone(),
+            imports: self.imports.clone(),
+        }
+    }
+
+    // Read the settings of an asset from its sidecar meta file (if it exists and if the asset supports it)
+    // The meta file is recorded as a dependency of the asset so modifying it hot-reloads the asset
+    fn meta_settings<S>(
+        &self,
+        path: &Path,
+        parse: impl FnOnce(&Meta) -> Option<Result<S, MetaError>>,
+    ) -> Result<Option<S>, AssetLoadError> {
+        let owned = crate::meta_path(path);
+        let sources = self.sources();
+        let exists = sources.bytes.read().contains_key(&owned)
+            || (owned.is_absolute() && owned.is_file())
+            || sources
+                .mounts
+                .read()
+                .iter()
+                .any(|point| point.mount.contains(&owned));
 
-        // If that fails, try loading from user defined asset path
-        if let Err(AssetLoadError::CachedNotFound(_)) = &loaded {
-            loaded = Self::load_bytes_dynamically(bytes, hijack, owned);
+        if !exists {
+            return Ok(None);
         }
 
-        // Return the (hopefully loaded) asset
-        loaded
+        let bytes = Self::load_bytes(&sources, owned.clone())?;
+        let meta = Meta {
+            path: &owned,
+            bytes: &bytes,
+        };
+
+        let Some(settings) = parse(&meta) else {
+            return Ok(None);
+        };
+
+        log::debug!("Read the settings of asset {path:?} from meta file {owned:?}");
+        self.graph.write().add(path, &owned);
+        settings
+            .map(Some)
+            .map_err(|source| AssetLoadError::InvalidMeta {
+                path: owned,
+                source,
+            })
     }
 
-    // Load the already cached bytes
-    fn load_cached_bytes(
-        bytes: &AsyncLoadedBytes,
+    // Load bytes either dynamically or load cached bytes
+    fn load_bytes(sources: &ByteSources, owned: PathBuf) -> Result<Arc<[u8]>, AssetLoadError> {
+        // Load the bytes from cached bytes first, and if that fails, try loading from the mounts
+        match Self::load_cached_bytes(&sources.bytes, &owned) {
+            Some(bytes) => Ok(bytes),
+            None => Self::load_bytes_dynamically(sources, owned),
+        }
+    }
+
+    // Load the bytes of a file that another asset references (recorded as a dependency of said asset)
+    pub(crate) fn load_referenced_bytes(
+        sources: &ByteSources,
+        parent: &Path,
         path: &Path,
     ) -> Result<Arc<[u8]>, AssetLoadError> {
-        if let Some(bytes) = bytes.read().get(path).cloned() {
-            log::debug!("Loaded asset from path {:?} from cached bytes", path);
-            Ok(bytes.clone())
-        } else {
-            let path = path.as_os_str().to_str().unwrap().to_owned();
-            Err(AssetLoadError::CachedNotFound(path))
-        }
+        sources.graph.write().add(parent, path);
+        Self::load_bytes(sources, path.to_path_buf())
+    }
+
+    // Load the already cached bytes
+    fn load_cached_bytes(bytes: &AsyncLoadedBytes, path: &Path) -> Option<Arc<[u8]>> {
+        let bytes = bytes.read().get(path).cloned()?;
+        log::debug!("Loaded asset from path {:?} from cached bytes", path);
+        Some(bytes)
     }
 
     // Load the bytes for an asset dynamically and store them within self
     fn load_bytes_dynamically(
-        bytes: &AsyncLoadedBytes,
-        hijack: AsyncHijackPaths,
+        sources: &ByteSources,
         owned: PathBuf,
     ) -> Result<Arc<[u8]>, AssetLoadError> {
-        let og = owned.clone();
         log::warn!("Loading asset bytes from path {:?} dynamically...", &owned);
-        let mut write = bytes.write();
+        let mut write = sources.bytes.write();
 
-        // Translate the path if it's defined
-        let read = hijack.read();
-        let owned = read.get(&owned).unwrap_or(&owned);
-
-        // Get the path of the asset
-        let path = if owned.is_absolute() {
-            owned.clone()
+        // Absolute paths are read directly, otherwise we fall back through the mounts by priority
+        let (bytes, global) = if owned.is_absolute() {
+            let bytes =
+                super::raw::read(&owned).map_err(|source| AssetLoadError::DynamicNotFound {
+                    path: owned.clone(),
+                    location: AssetLocation::Absolute(owned.clone()),
+                    source,
+                })?;
+            (bytes, Some(owned.clone()))
         } else {
-            let path = owned.as_os_str().to_str().unwrap().to_owned();
-            return Err(AssetLoadError::CachedNotFound(path));
+            let mounts = sources.mounts.read();
+            let (point, bytes) = mounts
+                .iter()
+                .find_map(|point| Some((point, point.read(&owned)?)))
+                .ok_or_else(|| AssetLoadError::CachedNotFound {
+                    path: owned.clone(),
+                    mounts: mounts.iter().map(|point| point.name.clone()).collect(),
+                })?;
+
+            // Only remember the mount once we actually managed to read the bytes from it
+            let bytes = bytes?;
+            log::debug!("Resolved asset {:?} from mount '{}'", &owned, point.name);
+            sources
+                .resolved
+                .write()
+                .insert(owned.clone(), point.name.clone());
+            (bytes, point.mount.global(&owned))
         };
 
-        // Load the asset dynamically
-        let bytes = super::raw::read(&path)?;
+        // Watch the file so we can hot-reload the asset (archived files are never watched)
+        if let Some(global) = &global {
+            if let Some(watching) = sources.watcher.lock().as_mut() {
+                watching.watch(global, &owned);
+            }
+        }
 
         // Add the asset bytes into the cache
         let arc: Arc<[u8]> = Arc::from(bytes);
-        write.insert(og, arc.clone());
+        write.insert(owned.clone(), arc.clone());
         log::debug!(
             "Successfully loaded dynamic asset bytes from path {:?}",
             &owned
@@ -230,20 +754,27 @@ impl Assets {
     // Load an asset asynchronously and automatically add it to the loaded assets
     fn async_load_inner<A: AsyncAsset>(
         owned: PathBuf,
-        bytes: AsyncLoadedBytes,
-        hijack: AsyncHijackPaths,
+        sources: ByteSources,
         context: <A as Asset>::Context<'_>,
-        settings: <A as Asset>::Settings<'_>,
+        settings: Result<<A as Asset>::Settings<'_>, AssetLoadError>,
         sender: Sender<AsyncChannelResult>,
         index: usize,
+        state: Arc<AsyncLoadState>,
     ) {
         // Smaller scope so we can use ? internally
-        let result = move || {
+        let result = || {
+            // Skip the load completely if the handle was dropped before we started
+            if state.cancelled.load(Ordering::Relaxed) {
+                return Err(AssetLoadError::Cancelled(owned.clone()));
+            }
+
             // Validate the path and extensions
             Self::validate::<A>(&owned)?;
+            let settings = settings?;
 
             // Load the bytes dynamically or from cache
-            let bytes = Self::load_bytes(&bytes, hijack, owned.clone())?;
+            let bytes = Self::load_bytes(&sources, owned.clone())?;
+            state.bytes.store(bytes.len(), Ordering::Relaxed);
 
             // Split the path into it's name and extension
             let (name, extension) = Self::decompose_path(&owned);
@@ -256,11 +787,13 @@ impl Assets {
                     bytes,
                     path: owned.as_path(),
                     loader: None,
+                    sources: Some(sources.clone()),
+                    imports: Some(sources.imports.clone()),
                 },
                 context,
                 settings,
             )
-            .map_err(|err| AssetLoadError::BoxedDeserialization(Box::new(err)))?;
+            .map_err(|err| Self::deserialization_error::<A>(&sources, &owned, err))?;
 
             // Box the asset
             let boxed: Box<dyn Any + Send + Sync + 'static> = Box::new(asset);
@@ -268,7 +801,8 @@ impl Assets {
         };
 
         // Send the result to the main thread
-        sender.send((result(), index)).unwrap();
+        let result = result();
+        sender.send((result, index, state)).unwrap();
     }
 }
 
@@ -280,7 +814,8 @@ impl Assets {
         input: impl AssetInput<'str, 'ctx, 'stg, A>,
     ) -> Result<A, AssetLoadError> {
         // Check if the extension is valid
-        let (path, settings, context) = input.split();
+        let has_settings = input.has_settings();
+        let (path, mut settings, context) = input.split();
         let path = Path::new(OsStr::new(path));
         let owned = path.to_owned();
         Self::validate::<A>(path)?;
@@ -289,11 +824,24 @@ impl Assets {
         // All this does is that it ensures that the bytes are valid before we actually deserialize the asset
         let (name, extension) = Self::decompose_path(path);
 
+        // Record the dependency if we are being loaded recursively by another asset
+        if let Some(parent) = LoadingGuard::current() {
+            self.graph.write().add(&parent, path);
+        }
+
+        // Settings provided in code take precedence over the settings from the meta file
+        if !has_settings {
+            if let Some(meta) = self.meta_settings(path, A::from_meta)? {
+                settings = meta;
+            }
+        }
+
         // Load the asset bytes (either dynamically or fetch cached bytes)
-        let hijack = self.hijack.clone();
-        let bytes = Self::load_bytes(&self.bytes, hijack, owned)?;
+        let sources = self.sources();
+        let bytes = Self::load_bytes(&sources, owned.clone())?;
 
-        // Deserialize the asset file
+        // Deserialize the asset file (the assets that it loads will be recorded as dependencies)
+        let _guard = LoadingGuard::push(owned);
         A::deserialize(
             crate::Data {
                 name,
@@ -301,11 +849,13 @@ impl Assets {
                 bytes,
                 path,
                 loader: Some(self),
+                sources: Some(sources.clone()),
+                imports: Some(self.imports.clone()),
             },
             context,
             settings,
         )
-        .map_err(|err| AssetLoadError::BoxedDeserialization(Box::new(err)))
+        .map_err(|err| Self::deserialization_error::<A>(&sources, path, err))
     }
 
     /// Load multiple assets using a common implementation of an [Asset Input](crate::AssetInput)
@@ -318,42 +868,95 @@ impl Assets {
             .map(|input| self.load(input))
             .collect::<Vec<Result<A, AssetLoadError>>>()
     }
+
+    /// Load multiple assets, and return all of the errors that occured if any of them failed to load.
+    pub fn load_all_from_iter<'str, 'ctx, 'stg, A: Asset>(
+        &self,
+        inputs: impl IntoIterator<Item = impl AssetInput<'str, 'ctx, 'stg, A>>,
+    ) -> Result<Vec<A>, AssetLoadErrors> {
+        collect_all(self.load_from_iter(inputs))
+    }
+}
+
+// Split the results of a batch of loads into the loaded assets, or all of the errors
+fn collect_all<A>(results: Vec<Result<A, AssetLoadError>>) -> Result<Vec<A>, AssetLoadErrors> {
+    let mut assets = Vec::with_capacity(results.len());
+    let mut errors = Vec::new();
+
+    for result in results {
+        match result {
+            Ok(asset) => assets.push(asset),
+            Err(error) => errors.push(error),
+        }
+    }
+
+    if errors.is_empty() {
+        Ok(assets)
+    } else {
+        Err(AssetLoadErrors(errors))
+    }
 }
 
 // Asynchronous loading
 impl Assets {
     /// Load an asset using some an implementation of an [Asset Input](crate::AssetInput) in another thread.
-    /// Thread management is handled by [rayon].
+    /// Thread management is handled by [rayon]. Uses the [default load priority](crate::DEFAULT_LOAD_PRIORITY).
     pub fn async_load<'str, A: AsyncAsset>(
         &self,
         input: impl AssetInput<'str, 'static, 'static, A>,
     ) -> AsyncHandle<A>
+    where
+        A::Settings<'static>: Send + Sync,
+        A::Context<'static>: Send + Sync,
+    {
+        self.async_load_with_priority(input, DEFAULT_LOAD_PRIORITY)
+    }
+
+    /// Load an asset in another thread using a specific priority.
+    /// Loads with a higher priority are started before the ones with a lower priority.
+    pub fn async_load_with_priority<'str, A: AsyncAsset>(
+        &self,
+        input: impl AssetInput<'str, 'static, 'static, A>,
+        priority: i32,
+    ) -> AsyncHandle<A>
     where
         A::Settings<'static>: Send + Sync,
         A::Context<'static>: Send + Sync,
     {
         // Get the path and arguments
+        let has_settings = input.has_settings();
         let (path, settings, context) = input.split();
         let path = Path::new(OsStr::new(path));
         let owned = path.to_owned();
-        log::debug!("Asynchronously loading asset {path:?}...",);
+        log::debug!("Asynchronously loading asset {path:?} with priority {priority}...",);
+
+        // Meta files are read on the main thread since they might add dependencies
+        let settings = self.settings_or_meta::<A>(path, settings, has_settings);
 
         // Clone the things that must be sent to the thread
-        let bytes = self.bytes.clone();
+        let sources = self.sources();
         let sender = self.sender.clone();
-        let hijack = self.hijack.clone();
+        let state = AsyncLoadState::new(owned.clone());
 
         // Create the handle's key
-        let index = self.loaded.lock().len();
+        let index = self.next.fetch_add(1, Ordering::Relaxed);
+        self.loaded.lock().insert(
+            index,
+            AsyncSlot {
+                state: Some(state.clone()),
+                result: None,
+            },
+        );
+
         let handle = AsyncHandle::<A> {
             _phantom: PhantomData,
             index,
+            state: state.clone(),
         };
-        self.loaded.lock().push(None);
 
-        // Create a new task that will load this asset
-        rayon::spawn(move || {
-            Self::async_load_inner::<A>(owned, bytes, hijack, context, settings, sender, index);
+        // Queue a new task that will load this asset
+        self.queue.push(priority, move || {
+            Self::async_load_inner::<A>(owned, sources, context, settings, sender, index, state);
         });
         handle
     }
@@ -368,47 +971,109 @@ impl Assets {
         A::Settings<'static>: Send + Sync,
         A::Context<'static>: Send + Sync,
     {
-        // Create a temporary threadpool scope for these assets only
-        let mut outer = Vec::<AsyncHandle<A>>::new();
-        let reference = &mut outer;
-        let mut loaded = self.loaded.lock();
+        self.async_load_from_iter_with_priority(inputs, DEFAULT_LOAD_PRIORITY)
+    }
 
-        for input in inputs.into_iter() {
-            // Check the extension on a per file basis
-            let (path, settings, context) = input.split();
-            let path = Path::new(OsStr::new(path));
-            log::debug!("Asynchronously loading asset {path:?} in batch...",);
-            let owned = path.to_owned();
-
-            // Clone the things that must be sent to the thread
-            let bytes = self.bytes.clone();
-            let sender = self.sender.clone();
-            let hijack = self.hijack.clone();
-
-            // Create the handle's key and insert it
-            let index = loaded.len();
-            reference.push(AsyncHandle::<A> {
-                _phantom: PhantomData,
-                index,
-            });
-            loaded.push(None);
+    /// Load multiple assets in another thread using the same priority for all of them.
+    pub fn async_load_from_iter_with_priority<'s, A: AsyncAsset>(
+        &self,
+        inputs: impl IntoIterator<Item = impl AssetInput<'s, 'static, 'static, A> + Send>,
+        priority: i32,
+    ) -> Vec<AsyncHandle<A>>
+    where
+        A::Settings<'static>: Send + Sync,
+        A::Context<'static>: Send + Sync,
+    {
+        log::debug!("Asynchronously loading assets in batch...");
+        inputs
+            .into_iter()
+            .map(|input| self.async_load_with_priority(input, priority))
+            .collect()
+    }
 
-            // Start telling worker threads to begin loading the assets
-            rayon::spawn(move || {
-                Self::async_load_inner::<A>(owned, bytes, hijack, context, settings, sender, index);
-            });
+    // Replace the settings with the ones from the meta file if they were not provided in code
+    fn settings_or_meta<A: AsyncAsset>(
+        &self,
+        path: &Path,
+        settings: A::Settings<'static>,
+        has_settings: bool,
+    ) -> Result<A::Settings<'static>, AssetLoadError> {
+        if has_settings {
+            return Ok(settings);
         }
-        outer
+
+        Ok(self.meta_settings(path, A::from_meta)?.unwrap_or(settings))
     }
 
     /// Fetches the loaded assets from the receiver and caches them locally.
+    /// This also frees the slots of the loads whose handle was dropped.
     pub fn refresh(&self) {
         let mut loaded = self.loaded.lock();
-        for (result, index) in self.receiver.try_iter() {
-            let len = loaded.len().max(index + 1);
-            loaded.resize_with(len, || None);
-            loaded[index] = Some(result);
+        let mut completed = self.completed.lock();
+        for (result, index, state) in self.receiver.try_iter() {
+            // Nobody is waiting for the results of cancelled loads
+            if state.cancelled.load(Ordering::Relaxed) {
+                continue;
+            }
+
+            completed.push(LoadCompleted {
+                path: state.path.clone(),
+                id: index,
+                succeeded: result.is_ok(),
+            });
+
+            if let Some(slot) = loaded.get_mut(&index) {
+                slot.result = Some(result);
+            }
         }
+
+        loaded.retain(|_, slot| {
+            !slot
+                .state
+                .as_ref()
+                .is_some_and(|state| state.cancelled.load(Ordering::Relaxed))
+        });
+    }
+
+    /// Fetch the loaded assets and take the [LoadCompleted] events of the asynchronous loads that completed since the last call.
+    /// This is called automatically at the start of every frame, and the events are published through [PerFrameEvents](utils::PerFrameEvents).
+    pub fn take_completed(&self) -> Vec<LoadCompleted> {
+        self.refresh();
+        std::mem::take(&mut *self.completed.lock())
+    }
+
+    /// Get the combined progress of a batch of asynchronous loads, including the number of bytes that were read.
+    pub fn async_progress<'h, A: AsyncAsset>(
+        &self,
+        handles: impl IntoIterator<Item = &'h AsyncHandle<A>>,
+    ) -> AssetProgress {
+        self.refresh();
+        let loaded = self.loaded.lock();
+        let mut progress = AssetProgress::default();
+
+        for handle in handles {
+            progress.total += 1;
+            progress.bytes += handle.state.bytes.load(Ordering::Relaxed);
+            if loaded
+                .get(&handle.index)
+                .is_some_and(|slot| slot.result.is_some())
+            {
+                progress.loaded += 1;
+            }
+        }
+
+        progress
+    }
+
+    /// Get the number of asynchronous loads that were queued but not started yet.
+    pub fn queued(&self) -> usize {
+        self.queue.len()
+    }
+
+    /// Get the number of asynchronous and deferred loads whose results were not taken out of the loader yet.
+    pub fn outstanding(&self) -> usize {
+        self.refresh();
+        self.loaded.lock().len()
     }
 
     /// This will check if the asset loader finished loading a specific asset using it's handle.
@@ -416,9 +1081,8 @@ impl Assets {
         self.refresh();
         self.loaded
             .lock()
-            .get(handle.index)
-            .map(|x| x.is_some())
-            .unwrap_or_default()
+            .get(&handle.index)
+            .is_some_and(|slot| slot.result.is_some())
     }
 
     /// This will wait until the asset referenced by this handle has finished loading.
@@ -428,10 +1092,9 @@ impl Assets {
             std::hint::spin_loop();
         }
 
-        // Replace the slot with None
-        let mut loaded = self.loaded.lock();
-        let old = loaded[handle.index].take().unwrap();
-        old.map(|b| *b.downcast::<A>().unwrap())
+        // Free the slot and take its result
+        let slot = self.loaded.lock().remove(&handle.index).unwrap();
+        slot.result.unwrap().map(|b| *b.downcast::<A>().unwrap())
     }
 
     /// This will wait until all the assets reference by these handles have finished loading.
@@ -446,4 +1109,178 @@ impl Assets {
             .map(|handle| self.wait(handle))
             .collect::<Vec<_>>()
     }
+
+    /// This will wait until all the assets reference by these handles have finished loading,
+    /// and return all of the errors that occured if any of them failed to load.
+    pub fn wait_all_from_iter<A: AsyncAsset>(
+        &self,
+        handles: impl IntoIterator<Item = AsyncHandle<A>>,
+    ) -> Result<Vec<A>, AssetLoadErrors> {
+        collect_all(self.wait_from_iter(handles))
+    }
+}
+
+// Deferred (two-phase) loading
+impl Assets {
+    /// Load a [deferred asset](crate::DeferredAsset) using the settings from its meta file, or its default settings.
+    /// The asset is decoded in another thread and finalized on the main thread during the assets update stage.
+    pub fn deferred_load<A: DeferredAsset>(&self, path: &str) -> DeferredHandle<A>
+    where
+        A::Settings: Default,
+    {
+        let settings = self
+            .meta_settings(Path::new(path), A::from_meta)
+            .map(Option::unwrap_or_default);
+        self.deferred_load_inner(path, settings)
+    }
+
+    /// Load a [deferred asset](crate::DeferredAsset) using some custom settings.
+    /// The asset is decoded in another thread and finalized on the main thread during the assets update stage.
+    pub fn deferred_load_with<A: DeferredAsset>(
+        &self,
+        path: &str,
+        settings: A::Settings,
+    ) -> DeferredHandle<A> {
+        self.deferred_load_inner(path, Ok(settings))
+    }
+
+    // Decode a deferred asset in another thread and send it back to the main thread for finalization
+    fn deferred_load_inner<A: DeferredAsset>(
+        &self,
+        path: &str,
+        settings: Result<A::Settings, AssetLoadError>,
+    ) -> DeferredHandle<A> {
+        let owned = PathBuf::from(path);
+        log::debug!("Deferred loading asset {owned:?}...");
+
+        // Clone the things that must be sent to the thread
+        let sources = self.sources();
+        let sender = self.decoded_sender.clone();
+
+        // Create the handle's key
+        let index = self.next.fetch_add(1, Ordering::Relaxed);
+        self.loaded.lock().insert(
+            index,
+            AsyncSlot {
+                state: None,
+                result: None,
+            },
+        );
+
+        // Decode the asset in another thread
+        self.queue.push(DEFAULT_LOAD_PRIORITY, move || {
+            let result = (|| {
+                Self::validate_extensions::<A>(&owned, A::extensions())?;
+                let settings = settings?;
+                let bytes = Self::load_bytes(&sources, owned.clone())?;
+                let (name, extension) = Self::decompose_path(&owned);
+
+                let data = crate::Data {
+                    name,
+                    extension,
+                    bytes,
+                    path: owned.as_path(),
+                    loader: None,
+                    sources: Some(sources.clone()),
+                    imports: Some(sources.imports.clone()),
+                };
+
+                let decoded = A::decode(data, settings)
+                    .map_err(|err| Self::deserialization_error::<A>(&sources, &owned, err))?;
+                let boxed: DecodedBoxed = Box::new(decoded);
+                Ok(boxed)
+            })();
+
+            sender
+                .send((result, Self::finalize_erased::<A>, index, owned))
+                .unwrap();
+        });
+
+        DeferredHandle {
+            _phantom: PhantomData,
+            index,
+        }
+    }
+
+    // Fetch the context of a deferred asset and finalize it
+    fn finalize_erased<A: DeferredAsset>(
+        decoded: DecodedBoxed,
+        world: &World,
+        path: &Path,
+        sources: &ByteSources,
+    ) -> Result<AsyncBoxedResult, DecodedBoxed> {
+        let Some(context) = A::fetch(world) else {
+            return Err(decoded);
+        };
+
+        let decoded = *decoded.downcast::<A::Decoded>().unwrap();
+        Ok(A::finalize(decoded, context)
+            .map(|asset| Box::new(asset) as Box<dyn Any + Send + Sync>)
+            .map_err(|err| Self::deserialization_error::<A>(sources, path, err)))
+    }
+
+    /// Finalize the deferred assets that were decoded since the last call.
+    /// This is called automatically by the assets system during the update stage.
+    /// Assets whose context could not be fetched are kept around until the next call.
+    pub fn finalize_deferred(&self, world: &World) {
+        let mut pending = self.pending.lock();
+        let sources = self.sources();
+
+        for (result, finalize, index, path) in self.decoded_receiver.try_iter() {
+            match result {
+                Ok(decoded) => pending.push((decoded, finalize, index, path)),
+                Err(error) => self.set_result(index, Err(error)),
+            }
+        }
+
+        let count = pending.len();
+        *pending = std::mem::take(&mut *pending)
+            .into_iter()
+            .filter_map(|(decoded, finalize, index, path)| {
+                match finalize(decoded, world, &path, &sources) {
+                    Ok(result) => {
+                        self.set_result(index, result);
+                        None
+                    }
+                    Err(decoded) => Some((decoded, finalize, index, path)),
+                }
+            })
+            .collect();
+
+        if count > pending.len() {
+            log::debug!("Finalized {} deferred assets", count - pending.len());
+        }
+    }
+
+    // Store the result of a deferred load within its slot
+    fn set_result(&self, index: usize, result: AsyncBoxedResult) {
+        if let Some(slot) = self.loaded.lock().get_mut(&index) {
+            slot.result = Some(result);
+        }
+    }
+
+    /// Get the number of decoded deferred assets that are still waiting for their context.
+    pub fn pending_finalization(&self) -> usize {
+        self.pending.lock().len()
+    }
+
+    /// Check if a deferred asset was finalized (or if it failed to load).
+    pub fn is_finalized<A: DeferredAsset>(&self, handle: &DeferredHandle<A>) -> bool {
+        self.loaded
+            .lock()
+            .get(&handle.index)
+            .is_some_and(|slot| slot.result.is_some())
+    }
+
+    /// Take a deferred asset out of the loader once it was finalized.
+    /// Returns None if the asset is still loading, or if it was already taken.
+    pub fn take_finalized<A: DeferredAsset>(
+        &self,
+        handle: &DeferredHandle<A>,
+    ) -> Option<Result<A, AssetLoadError>> {
+        let mut loaded = self.loaded.lock();
+        loaded.get(&handle.index)?.result.as_ref()?;
+        let old = loaded.remove(&handle.index)?.result?;
+        Some(old.map(|b| *b.downcast::<A>().unwrap()))
+    }
 }
@@ -1,5 +1,6 @@
 /// Define an asset path for an asset that will be loaded in.
 /// Assets will either be packed into the binary or loaded in at runtime based on the ``pack-assets`` feature
+/// When loaded at runtime, the assets folder is mounted with the [engine priority](crate::ENGINE_PRIORITY)
 /// # Arguments
 ///
 /// ``assets`` - Immutable asset loader reference
@@ -25,8 +26,13 @@ macro_rules! asset {
                     });
                 }
             } else {
-                let path = concat!(env!("CARGO_MANIFEST_DIR"), $prefix, $file);
-                $assets.hijack($file, path);
+                // Mount the whole assets folder (only once) so it can be overriden by higher priority mounts
+                let _ = $file;
+                let root = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join($prefix.trim_start_matches('/'));
+                let name = root.to_string_lossy();
+                if !$assets.mounts().iter().any(|(mounted, _)| *mounted == name) {
+                    $assets.mount_directory(&name, &root, $crate::ENGINE_PRIORITY);
+                }
             }
         }
     };
@@ -0,0 +1,39 @@
+use crate::MetaError;
+use serde::de::DeserializeOwned;
+use std::path::{Path, PathBuf};
+
+/// Extension of the sidecar files that contain the import settings of assets.
+pub const META_EXTENSION: &str = "meta.ron";
+
+/// Contents of the optional ``<asset>.meta.ron`` sidecar file next to an asset file.
+/// Used to read the default import settings of an asset using [Asset::from_meta](crate::Asset::from_meta).
+pub struct Meta<'a> {
+    pub(crate) path: &'a Path,
+    pub(crate) bytes: &'a [u8],
+}
+
+impl<'a> Meta<'a> {
+    /// Get the asset path of the meta file.
+    pub fn path(&self) -> &'a Path {
+        self.path
+    }
+
+    /// Get the raw bytes of the meta file.
+    pub fn bytes(&self) -> &'a [u8] {
+        self.bytes
+    }
+
+    /// Deserialize the contents of the meta file using RON.
+    pub fn parse<T: DeserializeOwned>(&self) -> Result<T, MetaError> {
+        let source = std::str::from_utf8(self.bytes)?;
+        Ok(ron::from_str(source)?)
+    }
+}
+
+// Get the asset path of the sidecar meta file of an asset ("textures/grass.png.meta.ron")
+pub(crate) fn meta_path(asset: &Path) -> PathBuf {
+    let mut path = asset.as_os_str().to_owned();
+    path.push(".");
+    path.push(META_EXTENSION);
+    PathBuf::from(path)
+}
@@ -0,0 +1,161 @@
+use crate::{archive::normalize, Archive, AssetLoadError, AssetLocation};
+use parking_lot::RwLock;
+use std::{
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+// Mounts sorted by priority that are shared with the async loading threads
+pub(crate) type AsyncMounts = Arc<RwLock<Vec<MountPoint>>>;
+
+/// Priority of the mount that contains the default engine assets.
+pub const ENGINE_PRIORITY: i32 = 0;
+
+/// Priority of the mounts that contain the assets of the game itself.
+pub const GAME_PRIORITY: i32 = 100;
+
+/// Priority of user mod folders, so they can override both game and engine assets.
+pub const MOD_PRIORITY: i32 = 200;
+
+/// A source of asset files that can be mounted by the asset loader.
+pub enum Mount {
+    /// Loose files within a directory on disk. Files from these mounts are watched for hot-reloading.
+    Directory(PathBuf),
+
+    /// Files packed within an [Archive].
+    Archive(Archive),
+}
+
+impl Mount {
+    /// Check if this mount contains the file of an asset.
+    pub fn contains(&self, asset: &Path) -> bool {
+        match self {
+            Mount::Directory(_) => self.global(asset).is_some(),
+            Mount::Archive(archive) => archive.contains(asset),
+        }
+    }
+
+    // Get the global path of the file of an asset if this is a loose directory mount
+    pub(crate) fn global(&self, asset: &Path) -> Option<PathBuf> {
+        match self {
+            Mount::Directory(root) => {
+                let path = root.join(normalize(asset)?);
+                path.is_file().then_some(path)
+            }
+            Mount::Archive(_) => None,
+        }
+    }
+
+    // Get the asset paths of all the files within this mount that start with the given directory
+    pub(crate) fn paths(&self, directory: &str) -> Vec<String> {
+        match self {
+            Mount::Directory(root) => {
+                let mut paths = Vec::new();
+                let mut pending = vec![root.join(directory)];
+
+                while let Some(directory) = pending.pop() {
+                    let Ok(entries) = std::fs::read_dir(&directory) else {
+                        continue;
+                    };
+
+                    for path in entries
+                        .filter_map(|entry| entry.ok())
+                        .map(|entry| entry.path())
+                    {
+                        if path.is_dir() {
+                            pending.push(path);
+                        } else if let Some(local) = path.strip_prefix(root).ok().and_then(normalize)
+                        {
+                            paths.push(local);
+                        }
+                    }
+                }
+
+                paths
+            }
+            Mount::Archive(archive) => archive
+                .paths()
+                .filter(|path| path.starts_with(directory))
+                .map(str::to_owned)
+                .collect(),
+        }
+    }
+}
+
+// A mount with a unique name and a priority
+pub(crate) struct MountPoint {
+    pub(crate) name: String,
+    pub(crate) priority: i32,
+    pub(crate) mount: Mount,
+}
+
+impl MountPoint {
+    // Read the bytes of an asset from this mount. Returns None if the mount does not contain it
+    pub(crate) fn read(&self, asset: &Path) -> Option<Result<Vec<u8>, AssetLoadError>> {
+        match &self.mount {
+            Mount::Directory(_) => {
+                let global = self.mount.global(asset)?;
+                Some(
+                    super::raw::read(&global).map_err(|source| AssetLoadError::DynamicNotFound {
+                        path: asset.to_owned(),
+                        location: AssetLocation::Mount(self.name.clone(), Some(global)),
+                        source,
+                    }),
+                )
+            }
+            Mount::Archive(archive) => archive.read(asset).map(|result| {
+                result.map_err(|source| AssetLoadError::Archive {
+                    path: asset.to_owned(),
+                    mount: self.name.clone(),
+                    source,
+                })
+            }),
+        }
+    }
+}
+
+// Get the directory prefix of a glob pattern that does not contain any wildcards
+pub(crate) fn literal_prefix(pattern: &str) -> String {
+    let segments = pattern.split('/').collect::<Vec<_>>();
+    let literal = segments[..segments.len() - 1]
+        .iter()
+        .take_while(|segment| !segment.contains(['*', '?']))
+        .copied()
+        .collect::<Vec<_>>();
+    literal.join("/")
+}
+
+// Check if an asset path matches a glob pattern
+// "*" and "?" match within a single segment, "**" matches any number of segments
+pub(crate) fn matches(pattern: &str, path: &str) -> bool {
+    fn segments(pattern: &[&str], path: &[&str]) -> bool {
+        match (pattern.first(), path.first()) {
+            (None, None) => true,
+            (Some(&"**"), _) => {
+                segments(&pattern[1..], path) || (!path.is_empty() && segments(pattern, &path[1..]))
+            }
+            (Some(first), Some(segment)) => {
+                segment_matches(first.as_bytes(), segment.as_bytes())
+                    && segments(&pattern[1..], &path[1..])
+            }
+            _ => false,
+        }
+    }
+
+    fn segment_matches(pattern: &[u8], name: &[u8]) -> bool {
+        match (pattern.first(), name.first()) {
+            (None, None) => true,
+            (Some(b'*'), _) => {
+                segment_matches(&pattern[1..], name)
+                    || (!name.is_empty() && segment_matches(pattern, &name[1..]))
+            }
+            (Some(b'?'), Some(_)) => segment_matches(&pattern[1..], &name[1..]),
+            (Some(a), Some(b)) => a == b && segment_matches(&pattern[1..], &name[1..]),
+            _ => false,
+        }
+    }
+
+    let pattern = pattern.split('/').collect::<Vec<_>>();
+    let path = path.split('/').collect::<Vec<_>>();
+    segments(&pattern, &path)
+}
@@ -0,0 +1,121 @@
+use crate::{Asset, AsyncHandle};
+use parking_lot::Mutex;
+use std::{
+    cmp::Ordering,
+    collections::BinaryHeap,
+    path::PathBuf,
+    sync::{
+        atomic::{self, AtomicBool, AtomicU64, AtomicUsize},
+        Arc,
+    },
+};
+
+/// Priority of background streaming loads that should only start once everything else was loaded.
+pub const STREAMING_LOAD_PRIORITY: i32 = -100;
+
+/// Priority of the asynchronous loads that don't specify any priority.
+pub const DEFAULT_LOAD_PRIORITY: i32 = 0;
+
+/// Priority of the loads that are required for the currently visible level.
+pub const VISIBLE_LOAD_PRIORITY: i32 = 100;
+
+// A load that is waiting for a worker thread
+struct QueuedLoad {
+    priority: i32,
+    sequence: u64,
+    job: Box<dyn FnOnce() + Send>,
+}
+
+// Loads with the highest priority come first, and loads with the same priority are started in order
+impl Ord for QueuedLoad {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.priority
+            .cmp(&other.priority)
+            .then_with(|| other.sequence.cmp(&self.sequence))
+    }
+}
+
+impl PartialOrd for QueuedLoad {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl PartialEq for QueuedLoad {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for QueuedLoad {}
+
+// Priority queue of the loads that were not started yet
+// Every rayon task pops the most important load when it starts instead of running a fixed one
+#[derive(Default)]
+pub(crate) struct LoadQueue {
+    pending: Mutex<BinaryHeap<QueuedLoad>>,
+    sequence: AtomicU64,
+}
+
+impl LoadQueue {
+    // Add a load to the queue and wake up a worker thread for it
+    pub(crate) fn push(self: &Arc<Self>, priority: i32, job: impl FnOnce() + Send + 'static) {
+        let sequence = self.sequence.fetch_add(1, atomic::Ordering::Relaxed);
+        self.pending.lock().push(QueuedLoad {
+            priority,
+            sequence,
+            job: Box::new(job),
+        });
+
+        let queue = self.clone();
+        rayon::spawn(move || {
+            let next = queue.pending.lock().pop();
+            if let Some(next) = next {
+                (next.job)();
+            }
+        });
+    }
+
+    // Get the number of loads that were not started yet
+    pub(crate) fn len(&self) -> usize {
+        self.pending.lock().len()
+    }
+}
+
+// State of a single asynchronous load that is shared between its handle and its job
+pub(crate) struct AsyncLoadState {
+    pub(crate) path: PathBuf,
+    pub(crate) cancelled: AtomicBool,
+    pub(crate) bytes: AtomicUsize,
+}
+
+impl AsyncLoadState {
+    pub(crate) fn new(path: PathBuf) -> Arc<Self> {
+        Arc::new(Self {
+            path,
+            cancelled: AtomicBool::new(false),
+            bytes: AtomicUsize::new(0),
+        })
+    }
+}
+
+/// Event that is published once an asynchronous asset load completes.
+/// Published through [PerFrameEvents](utils::PerFrameEvents) during the frame after the load completed.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LoadCompleted {
+    /// Path of the asset that was loaded.
+    pub path: PathBuf,
+
+    /// Unique ID of the load, same as [AsyncHandle::id](crate::AsyncHandle::id).
+    pub id: usize,
+
+    /// Whether the asset was loaded successfully.
+    pub succeeded: bool,
+}
+
+impl LoadCompleted {
+    /// Check if this event was published for the load of the given handle.
+    pub fn is<A: Asset>(&self, handle: &AsyncHandle<A>) -> bool {
+        self.id == handle.id()
+    }
+}
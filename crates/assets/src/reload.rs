@@ -0,0 +1,76 @@
+use crate::{Asset, AssetInput, AssetLoadError, Assets};
+use std::path::PathBuf;
+use utils::{Handle, Storage};
+
+/// Event that is published through [PerFrameEvents](utils::PerFrameEvents) whenever the file of an asset (or the file of one of its dependencies) gets modified.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AssetChanged {
+    /// Asset path of the asset that changed.
+    pub path: PathBuf,
+}
+
+/// Assets that can be rebuilt in place when their files get modified.
+///
+/// Rebuilding in place keeps the handles and references to the asset valid, so consumers don't need to fetch it again.
+pub trait Reload: Asset {
+    /// Replace the contents of the asset using a freshly loaded version of it.
+    fn reload(&mut self, reloaded: Self) {
+        *self = reloaded;
+    }
+}
+
+impl Reload for String {}
+impl Reload for Vec<u8> {}
+
+impl Assets {
+    /// Rebuild an asset in place if its file (or the file of one of its dependencies) was modified since the last frame.
+    /// Returns None if the asset did not change. The old asset is kept if the new version fails to load.
+    pub fn reload<'str, 'ctx, 'stg, A: Reload>(
+        &self,
+        asset: &mut A,
+        input: impl AssetInput<'str, 'ctx, 'stg, A>,
+    ) -> Option<Result<(), AssetLoadError>> {
+        let reloaded = self.reloaded(input)?;
+        Some(reloaded.map(|reloaded| asset.reload(reloaded)))
+    }
+
+    /// Rebuild in place every asset of a storage whose name is the path of an asset that was modified since the last frame.
+    /// Assets must be inserted using [Storage::insert_named] with their asset path as name to be reloaded.
+    /// Returns the handles of the assets that were rebuilt. Assets that fail to load again are kept as is.
+    pub fn reload_storage<'ctx, 'stg, A: Reload>(
+        &self,
+        storage: &mut Storage<A>,
+        mut input: impl FnMut() -> (A::Settings<'stg>, A::Context<'ctx>),
+    ) -> Vec<Handle<A>> {
+        let changed = self.changed();
+        if changed.is_empty() {
+            return Vec::new();
+        }
+
+        let handles = storage
+            .handles()
+            .filter(|handle| {
+                storage
+                    .name(handle)
+                    .is_some_and(|name| changed.iter().any(|path| path.as_os_str() == name))
+            })
+            .collect::<Vec<_>>();
+
+        handles
+            .into_iter()
+            .filter(|handle| {
+                let path = storage.name(handle).unwrap().to_owned();
+                let (settings, context) = input();
+                let asset = storage.get_mut(handle);
+                match self.reload(asset, (path.as_str(), settings, context)) {
+                    Some(Ok(())) => true,
+                    Some(Err(err)) => {
+                        log::error!("Could not hot-reload asset {path:?}: {err}");
+                        false
+                    }
+                    None => false,
+                }
+            })
+            .collect()
+    }
+}
@@ -1,11 +1,18 @@
-use crate::{asset, Assets};
+use crate::{asset, AssetChanged, Assets, LoadCompleted};
 
+use utils::{FileManager, FileType, PerFrameEvents};
 use world::{user, System, World};
 
 // Initialize a load and add it to the world
 fn init(world: &mut World) {
     let loader = Assets::new();
 
+    // Store the processed outputs of imported assets in the cache directory
+    if let Ok(manager) = world.get::<FileManager>() {
+        let directory = manager.local_path_to_global("imports/", FileType::Cache);
+        loader.set_import_cache(Some(directory));
+    }
+
     macro_rules! internal {
         ($assets:expr, $file:expr) => {
             asset!($assets, $file, "/src/assets/");
@@ -84,13 +91,46 @@ fn init(world: &mut World) {
     internal!(loader, "engine/meshes/plane.obj");
     internal!(loader, "engine/meshes/froggo.glb");
 
-    // Insert the loader
+    // Insert the loader and the hot-reload events
     world.insert(loader);
+    world.insert(PerFrameEvents::<AssetChanged>::new());
+    world.insert(PerFrameEvents::<LoadCompleted>::new());
+}
+
+// Fetch the modified asset files at the start of every frame for hot-reloading (and publish them as events),
+// publish the completed asynchronous loads, and free the cached bytes of the assets that are not used anymore
+fn update(world: &mut World) {
+    let assets = world.get::<Assets>().unwrap();
+    assets.refresh_changes();
+    let completed = assets.take_completed();
+    assets.collect_unused();
+
+    // Publish the changed assets so their consumers can rebuild them
+    let mut events = world.get_mut::<PerFrameEvents<AssetChanged>>().unwrap();
+    events.send(
+        assets
+            .changed()
+            .into_iter()
+            .map(|path| AssetChanged { path }),
+    );
+    drop(events);
+
+    // Publish the asynchronous loads that completed since the last frame
+    let mut events = world.get_mut::<PerFrameEvents<LoadCompleted>>().unwrap();
+    events.send(completed.into_iter());
+}
+
+// Finalize the deferred assets that were decoded in other threads
+fn finalize(world: &mut World) {
+    let assets = world.get::<Assets>().unwrap();
+    assets.finalize_deferred(world);
 }
 
 // This system will add the asset loader resource into the world and automatically pre-load the default assets as well
 // This system will also insert the GlobalPaths resource into the world
 
 pub fn system(system: &mut System) {
-    system.insert_init(init).before(user);
+    system.insert_init(init).before(user).after(utils::io);
+    system.insert_update(update).before(user);
+    system.insert_update(finalize).before(user);
 }
@@ -1,6 +1,11 @@
 #[cfg(test)]
 mod tests {
-    use crate::{asset, AssetLoadError, Assets};
+    use crate::{
+        asset, Archive, ArchiveBuilder, ArchiveError, AssetLoadError, AssetLocation, Assets,
+        Compression, ImportKey, Mount, ENGINE_PRIORITY, MOD_PRIORITY, STREAMING_LOAD_PRIORITY,
+        VISIBLE_LOAD_PRIORITY,
+    };
+    use utils::Storage;
 
     #[test]
     fn read() {
@@ -41,7 +46,7 @@ mod tests {
         let string = strings.pop().unwrap();
         assert!(matches!(
             string.unwrap_err(),
-            AssetLoadError::CachedNotFound(_)
+            AssetLoadError::CachedNotFound { .. }
         ));
     }
 
@@ -61,7 +66,7 @@ mod tests {
         let string = loader.wait(handle);
         assert!(matches!(
             string.unwrap_err(),
-            AssetLoadError::CachedNotFound(_)
+            AssetLoadError::CachedNotFound { .. }
         ));
     }
 
@@ -86,7 +91,7 @@ mod tests {
         let string = vec.pop().unwrap();
         assert!(matches!(
             string.unwrap_err(),
-            AssetLoadError::CachedNotFound(_)
+            AssetLoadError::CachedNotFound { .. }
         ));
     }
 
@@ -119,4 +124,633 @@ mod tests {
         let string = loader.load::<Contextual>(("test/text.txt", &context));
         assert_eq!(string.unwrap().0, "this is a test file\n1234567890");
     }
+
+    #[test]
+    fn not_changed() {
+        let loader = Assets::new();
+        asset!(loader, "test/text.txt", "src/assets/");
+        loader.refresh_changes();
+        assert!(loader.changed().is_empty());
+        assert!(!loader.is_changed("test/text.txt"));
+        assert!(loader.reloaded::<String>("test/text.txt").is_none());
+    }
+
+    #[test]
+    fn reload_in_place() {
+        let loader = Assets::new();
+        asset!(loader, "test/text.txt", "src/assets/");
+        loader.mark_changed(vec!["test/text.txt".into()]);
+        assert!(loader.is_changed("test/text.txt"));
+
+        let mut string = String::from("old");
+        loader
+            .reload(&mut string, "test/text.txt")
+            .unwrap()
+            .unwrap();
+        assert_eq!(string, "this is a test file\n1234567890");
+
+        let mut storage = Storage::<String>::default();
+        let handle = storage.insert_named("test/text.txt", String::from("old"));
+        let other = storage.insert_named("test/other.txt", String::from("other"));
+        let reloaded = loader.reload_storage(&mut storage, || ((), ()));
+        assert_eq!(reloaded.len(), 1);
+        assert_eq!(storage.get(&handle), "this is a test file\n1234567890");
+        assert_eq!(storage.get(&other), "other");
+
+        // Nothing changed anymore, so nothing gets reloaded
+        loader.refresh_changes();
+        assert!(loader.reload(&mut string, "test/text.txt").is_none());
+        assert!(loader.reload_storage(&mut storage, || ((), ())).is_empty());
+    }
+
+    #[test]
+    fn archive() {
+        let mut builder = ArchiveBuilder::new();
+        let text = "this is a test file\n1234567890".repeat(16);
+        builder
+            .add(
+                "test/text.txt",
+                text.clone().into_bytes(),
+                Compression::Deflate,
+            )
+            .unwrap();
+        builder
+            .add("test/raw.txt", b"raw".to_vec(), Compression::None)
+            .unwrap();
+
+        let mut bytes = Vec::new();
+        builder.write(&mut bytes).unwrap();
+        let archive = Archive::from_bytes(bytes.clone()).unwrap();
+        assert_eq!(archive.len(), 2);
+        assert!(archive.contains("test/text.txt"));
+        assert_eq!(
+            archive.read("test/text.txt").unwrap().unwrap(),
+            text.as_bytes()
+        );
+        assert_eq!(archive.read("test/raw.txt").unwrap().unwrap(), b"raw");
+        assert!(archive.read("test/missing.txt").is_none());
+
+        // Flip the first byte of the first entry (right after the header) to break it
+        bytes[20] ^= 0xFF;
+        let archive = Archive::from_bytes(bytes).unwrap();
+        assert!(archive.read("test/text.txt").unwrap().is_err());
+        assert!(archive.read("test/raw.txt").unwrap().is_ok());
+    }
+
+    #[test]
+    fn archive_invalid() {
+        let result = Archive::from_bytes(b"not an archive".to_vec());
+        assert!(matches!(result, Err(ArchiveError::InvalidMagic)));
+    }
+
+    #[test]
+    fn archive_corrupted() {
+        let mut builder = ArchiveBuilder::new();
+        builder
+            .add("test/a.txt", b"abc".to_vec(), Compression::None)
+            .unwrap();
+        let mut bytes = Vec::new();
+        builder.write(&mut bytes).unwrap();
+        assert!(Archive::from_bytes(bytes.clone()).is_ok());
+
+        // Header (20), data (3), path length (2), path (10), compression (1), checksum (4)
+        let offset = 20 + 3 + 2 + 10 + 1 + 4;
+        let corrupt = |range: std::ops::Range<usize>, value: &[u8]| {
+            let mut bytes = bytes.clone();
+            bytes[range].copy_from_slice(value);
+            Archive::from_bytes(bytes)
+        };
+
+        // Entry count, index offset, and path length that don't fit within the archive
+        let result = corrupt(8..12, &u32::MAX.to_le_bytes());
+        assert!(matches!(result, Err(ArchiveError::Corrupted)));
+        let result = corrupt(12..20, &u64::MAX.to_le_bytes());
+        assert!(matches!(result, Err(ArchiveError::Corrupted)));
+        let result = corrupt(23..25, &u16::MAX.to_le_bytes());
+        assert!(matches!(result, Err(ArchiveError::Corrupted)));
+
+        // Entry offset, stored size, and decompressed size that don't fit within the archive
+        let result = corrupt(offset..offset + 8, &u64::MAX.to_le_bytes());
+        assert!(matches!(result, Err(ArchiveError::Corrupted)));
+        let result = corrupt(offset + 8..offset + 16, &u64::MAX.to_le_bytes());
+        assert!(matches!(result, Err(ArchiveError::Corrupted)));
+        let result = corrupt(offset + 16..offset + 24, &u64::MAX.to_le_bytes());
+        assert!(matches!(result, Err(ArchiveError::Corrupted)));
+    }
+
+    #[test]
+    fn mounts() {
+        let mut archive = ArchiveBuilder::new();
+        archive
+            .add(
+                "test/text.txt",
+                b"from archive".to_vec(),
+                Compression::Deflate,
+            )
+            .unwrap();
+        let mut bytes = Vec::new();
+        archive.write(&mut bytes).unwrap();
+
+        let loader = Assets::new();
+        let directory = concat!(env!("CARGO_MANIFEST_DIR"), "/src/assets/");
+        loader.mount_directory("engine", directory, ENGINE_PRIORITY);
+        assert_eq!(loader.resolve("test/text.txt").unwrap(), "engine");
+        assert!(loader.load::<String>("test/text.txt").is_ok());
+        assert_eq!(loader.resolved("test/text.txt").unwrap(), "engine");
+
+        // The mod has a higher priority, so it overrides the engine asset
+        let archive = Archive::from_bytes(bytes).unwrap();
+        loader.mount("mod", Mount::Archive(archive), MOD_PRIORITY);
+        assert_eq!(loader.mounts()[0].0, "mod");
+        let string = loader.load::<String>("test/text.txt").unwrap();
+        assert_eq!(string, "from archive");
+        assert_eq!(loader.resolved("test/text.txt").unwrap(), "mod");
+
+        // Falls back to the engine mount
+        let string = loader.load::<String>("test/invalid.txt");
+        assert!(!matches!(
+            string,
+            Err(AssetLoadError::CachedNotFound { .. })
+        ));
+        assert_eq!(loader.resolved("test/invalid.txt").unwrap(), "engine");
+        assert!(loader.path("test/invalid.txt").is_some());
+
+        assert!(loader.unmount("mod").is_some());
+        assert!(loader.unmount("mod").is_none());
+        let string = loader.load::<String>("test/text.txt").unwrap();
+        assert_eq!(string, "this is a test file\n1234567890");
+    }
+
+    #[test]
+    fn mount_failed_read() {
+        let mut archive = ArchiveBuilder::new();
+        archive
+            .add("test/broken.txt", b"broken".to_vec(), Compression::None)
+            .unwrap();
+        let mut bytes = Vec::new();
+        archive.write(&mut bytes).unwrap();
+
+        // Corrupt the stored bytes so the checksum doesn't match anymore
+        bytes[20] ^= 0xFF;
+        let loader = Assets::new();
+        let archive = Archive::from_bytes(bytes).unwrap();
+        loader.mount("mod", Mount::Archive(archive), MOD_PRIORITY);
+
+        // Failed reads must not be remembered as resolved
+        assert!(loader.load::<String>("test/broken.txt").is_err());
+        assert!(loader.resolved("test/broken.txt").is_none());
+    }
+
+    #[test]
+    fn list() {
+        let loader = Assets::new();
+        asset!(loader, "test/text.txt", "src/assets/");
+        loader.import("imported/file.txt", Vec::new());
+
+        let listed = loader.list("test/*.txt");
+        assert_eq!(listed.len(), 2);
+        assert!(listed.contains(&"test/text.txt".into()));
+        assert!(listed.contains(&"test/invalid.txt".into()));
+        assert_eq!(loader.list("**/file.txt").len(), 1);
+        assert_eq!(loader.list("test/te?t.txt").len(), 1);
+        assert!(loader.list("test/*.glsl").is_empty());
+    }
+
+    #[test]
+    fn deferred() {
+        struct Numbered(String);
+
+        impl crate::DeferredAsset for Numbered {
+            type Decoded = String;
+            type Context<'w> = world::Read<'w, u32>;
+            type Settings = ();
+            type Err = std::string::FromUtf8Error;
+
+            fn extensions() -> &'static [&'static str] {
+                &["txt"]
+            }
+
+            fn decode(data: crate::Data, _: Self::Settings) -> Result<Self::Decoded, Self::Err> {
+                String::from_utf8(data.bytes().to_vec())
+            }
+
+            fn fetch(world: &world::World) -> Option<Self::Context<'_>> {
+                world.get::<u32>().ok()
+            }
+
+            fn finalize(
+                decoded: Self::Decoded,
+                context: Self::Context<'_>,
+            ) -> Result<Self, Self::Err> {
+                Ok(Numbered(format!("{decoded}{}", *context)))
+            }
+        }
+
+        let loader = Assets::new();
+        asset!(loader, "test/text.txt", "src/assets/");
+        let handle = loader.deferred_load::<Numbered>("test/text.txt");
+        let mut world = world::World::empty();
+
+        // The context is not available yet, so the decoded asset must stay pending
+        let start = std::time::Instant::now();
+        while loader.pending_finalization() == 0 {
+            assert!(
+                !loader.is_finalized(&handle),
+                "Deferred asset failed to decode"
+            );
+            assert!(
+                start.elapsed() < std::time::Duration::from_secs(10),
+                "Deferred asset was never decoded"
+            );
+            loader.finalize_deferred(&world);
+            std::thread::yield_now();
+        }
+        assert!(!loader.is_finalized(&handle));
+
+        world.insert(69u32);
+        loader.finalize_deferred(&world);
+        assert!(loader.is_finalized(&handle));
+        let numbered = loader.take_finalized(&handle).unwrap().unwrap();
+        assert_eq!(numbered.0, "this is a test file\n123456789069");
+        assert!(loader.take_finalized(&handle).is_none());
+    }
+
+    #[test]
+    fn dependencies() {
+        struct Parent;
+
+        impl crate::Asset for Parent {
+            type Context<'ctx> = ();
+            type Settings<'stg> = ();
+            type Err = AssetLoadError;
+
+            fn extensions() -> &'static [&'static str] {
+                &["txt"]
+            }
+
+            fn deserialize(
+                data: crate::Data,
+                _: Self::Context<'_>,
+                _: Self::Settings<'_>,
+            ) -> Result<Self, Self::Err> {
+                let loader = data.loader().unwrap();
+                loader.load::<String>("test/invalid.txt").ok();
+                Ok(Parent)
+            }
+        }
+
+        let loader = Assets::new();
+        asset!(loader, "test/text.txt", "src/assets/");
+        let handle = loader.retain("test/text.txt");
+        assert_eq!(loader.progress("test/text.txt").total, 1);
+        assert!(loader.load::<Parent>("test/text.txt").is_ok());
+
+        let dependencies = loader.dependencies("test/text.txt");
+        assert_eq!(
+            dependencies,
+            vec![std::path::PathBuf::from("test/invalid.txt")]
+        );
+        assert_eq!(loader.dependents("test/invalid.txt").len(), 1);
+        assert!(loader.progress("test/text.txt").is_done());
+        assert_eq!(loader.progress("test/text.txt").total, 2);
+
+        // The bytes stay cached while we have a handle
+        loader.collect_unused();
+        assert!(loader.progress("test/text.txt").is_done());
+
+        drop(handle);
+        loader.collect_unused();
+        assert_eq!(loader.progress("test/text.txt").loaded, 0);
+    }
+
+    #[test]
+    fn dependencies_after_panic() {
+        struct Panicking;
+
+        impl crate::Asset for Panicking {
+            type Context<'ctx> = ();
+            type Settings<'stg> = ();
+            type Err = AssetLoadError;
+
+            fn extensions() -> &'static [&'static str] {
+                &["txt"]
+            }
+
+            fn deserialize(
+                _: crate::Data,
+                _: Self::Context<'_>,
+                _: Self::Settings<'_>,
+            ) -> Result<Self, Self::Err> {
+                panic!("failed to deserialize")
+            }
+        }
+
+        let loader = Assets::new();
+        asset!(loader, "test/text.txt", "src/assets/");
+        asset!(loader, "test/invalid.txt", "src/assets/");
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            loader.load::<Panicking>("test/invalid.txt")
+        }));
+        assert!(result.is_err());
+
+        // The panicking asset must not be seen as the parent of the next loads
+        assert!(loader.load::<String>("test/text.txt").is_ok());
+        assert!(loader.dependents("test/text.txt").is_empty());
+    }
+
+    #[test]
+    fn referenced_bytes_async() {
+        struct Linked(Vec<u8>);
+
+        impl crate::Asset for Linked {
+            type Context<'ctx> = ();
+            type Settings<'stg> = ();
+            type Err = AssetLoadError;
+
+            fn extensions() -> &'static [&'static str] {
+                &["txt"]
+            }
+
+            fn deserialize(
+                data: crate::Data,
+                _: Self::Context<'_>,
+                _: Self::Settings<'_>,
+            ) -> Result<Self, Self::Err> {
+                // No recursive loader in worker threads, but we can still read other files
+                assert!(data.loader().is_none());
+                let bytes = data.load_bytes("test/invalid.txt").unwrap()?;
+                Ok(Linked(bytes.to_vec()))
+            }
+        }
+
+        let loader = Assets::new();
+        asset!(loader, "test/text.txt", "src/assets/");
+        asset!(loader, "test/invalid.txt", "src/assets/");
+        let handle = loader.async_load::<Linked>("test/text.txt");
+        let linked = loader.wait(handle).unwrap();
+        assert!(!linked.0.is_empty());
+        assert_eq!(
+            loader.dependencies("test/text.txt"),
+            vec![std::path::PathBuf::from("test/invalid.txt")]
+        );
+    }
+
+    #[test]
+    fn import_cache() {
+        let directory = std::env::temp_dir().join(format!("assets-imports-{}", std::process::id()));
+        let loader = Assets::new();
+        let key = ImportKey::new("test", b"source", &(1u32, "settings"));
+
+        // Nothing gets stored while the import cache is disabled
+        loader.store_import(key, b"processed");
+        assert!(loader.cached_import(key).is_none());
+
+        loader.set_import_cache(Some(directory.clone()));
+        assert!(loader.cached_import(key).is_none());
+        loader.store_import(key, b"processed");
+        assert_eq!(loader.cached_import(key).unwrap(), b"processed");
+
+        // Different source bytes or settings must not reuse the cached output
+        assert_ne!(key, ImportKey::new("test", b"source2", &(1u32, "settings")));
+        assert_ne!(key, ImportKey::new("test", b"source", &(2u32, "settings")));
+        assert_ne!(key, ImportKey::new("other", b"source", &(1u32, "settings")));
+
+        // Corrupted outputs are ignored
+        let path = directory.join(format!("{key}.bin"));
+        let mut bytes = std::fs::read(&path).unwrap();
+        *bytes.last_mut().unwrap() ^= 0xFF;
+        std::fs::write(&path, bytes).unwrap();
+        assert!(loader.cached_import(key).is_none());
+
+        std::fs::remove_dir_all(directory).unwrap();
+    }
+
+    #[test]
+    fn import_cache_async() {
+        struct Imported(bool);
+
+        impl crate::Asset for Imported {
+            type Context<'ctx> = ();
+            type Settings<'stg> = ();
+            type Err = AssetLoadError;
+
+            fn extensions() -> &'static [&'static str] {
+                &["txt"]
+            }
+
+            fn deserialize(
+                data: crate::Data,
+                _: Self::Context<'_>,
+                _: Self::Settings<'_>,
+            ) -> Result<Self, Self::Err> {
+                // No recursive loader in worker threads, but the import cache is still reachable
+                assert!(data.loader().is_none());
+                let key = ImportKey::new("imported", data.bytes(), &());
+                let cached = data.cached_import(key).is_some();
+                data.store_import(key, data.bytes());
+                Ok(Imported(cached))
+            }
+        }
+
+        let directory =
+            std::env::temp_dir().join(format!("assets-imports-async-{}", std::process::id()));
+        let loader = Assets::new();
+        asset!(loader, "test/text.txt", "src/assets/");
+        loader.set_import_cache(Some(directory.clone()));
+
+        let handle = loader.async_load::<Imported>("test/text.txt");
+        assert!(!loader.wait(handle).unwrap().0);
+        let handle = loader.async_load::<Imported>("test/text.txt");
+        assert!(loader.wait(handle).unwrap().0);
+
+        std::fs::remove_dir_all(directory).unwrap();
+    }
+
+    #[test]
+    fn stable_hasher() {
+        use std::hash::Hasher;
+
+        // Reference vectors of SipHash-2-4 using the key 00 01 02 .. 0f
+        let hash = |bytes: &[u8]| {
+            let mut hasher = crate::StableHasher::new(0x0706050403020100, 0x0f0e0d0c0b0a0908);
+            hasher.write(bytes);
+            hasher.finish()
+        };
+        let message = (0..15).collect::<Vec<u8>>();
+        assert_eq!(hash(&[]), 0x726fdb47dd0e0e31);
+        assert_eq!(hash(&message), 0xa129ca6149be45e5);
+
+        // Splitting the input between multiple writes must not change the hash
+        for split in 0..message.len() {
+            let mut hasher = crate::StableHasher::new(0x0706050403020100, 0x0f0e0d0c0b0a0908);
+            hasher.write(&message[..split]);
+            hasher.write_u8(message[split]);
+            hasher.write(&message[split + 1..]);
+            assert_eq!(hasher.finish(), hash(&message));
+        }
+
+        // Integers are always hashed as little endian bytes
+        let mut hasher = crate::stable_hasher();
+        hasher.write_u32(0x04030201);
+        let mut bytes = crate::stable_hasher();
+        bytes.write(&[1, 2, 3, 4]);
+        assert_eq!(hasher.finish(), bytes.finish());
+    }
+
+    #[test]
+    fn meta() {
+        #[derive(Default, serde::Deserialize)]
+        struct Settings {
+            suffix: String,
+        }
+
+        struct Suffixed(String);
+
+        impl crate::Asset for Suffixed {
+            type Context<'ctx> = ();
+            type Settings<'stg> = Settings;
+            type Err = std::string::FromUtf8Error;
+
+            fn extensions() -> &'static [&'static str] {
+                &["txt"]
+            }
+
+            fn deserialize(
+                data: crate::Data,
+                _: Self::Context<'_>,
+                settings: Self::Settings<'_>,
+            ) -> Result<Self, Self::Err> {
+                let string = String::from_utf8(data.bytes().to_vec())?;
+                Ok(Suffixed(string + &settings.suffix))
+            }
+
+            fn from_meta<'s>(
+                meta: &crate::Meta,
+            ) -> Option<Result<Self::Settings<'s>, crate::MetaError>> {
+                Some(meta.parse())
+            }
+        }
+
+        let loader = Assets::new();
+        loader.import("test/meta.txt", b"text".to_vec());
+        loader.import("test/meta.txt.meta.ron", b"(suffix: \"-meta\")".to_vec());
+        loader.import("test/plain.txt", b"text".to_vec());
+        loader.import("test/broken.txt", b"text".to_vec());
+        loader.import("test/broken.txt.meta.ron", b"(suffix: 0)".to_vec());
+
+        // Settings are read from the meta file unless they are provided in code
+        let read = loader.load::<Suffixed>("test/meta.txt").unwrap();
+        assert_eq!(read.0, "text-meta");
+        let settings = Settings {
+            suffix: "-code".to_owned(),
+        };
+        let read = loader
+            .load::<Suffixed>(("test/meta.txt", settings, ()))
+            .unwrap();
+        assert_eq!(read.0, "text-code");
+
+        // Assets without meta files use the default settings
+        let read = loader.load::<Suffixed>("test/plain.txt").unwrap();
+        assert_eq!(read.0, "text");
+
+        // Modifying the meta file must reload the asset
+        assert_eq!(
+            loader.dependencies("test/meta.txt"),
+            vec![std::path::PathBuf::from("test/meta.txt.meta.ron")]
+        );
+
+        let broken = loader.load::<Suffixed>("test/broken.txt");
+        assert!(matches!(broken, Err(AssetLoadError::InvalidMeta { .. })));
+    }
+
+    #[test]
+    fn async_queue() {
+        let loader = Assets::new();
+        loader.import("test/visible.txt", b"visible".to_vec());
+        loader.import("test/streamed.txt", b"streamed".to_vec());
+        loader.import("test/dropped.txt", b"dropped".to_vec());
+
+        let visible =
+            loader.async_load_with_priority::<String>("test/visible.txt", VISIBLE_LOAD_PRIORITY);
+        let streamed = loader.async_load_from_iter_with_priority::<String>(
+            ["test/streamed.txt"],
+            STREAMING_LOAD_PRIORITY,
+        );
+        drop(loader.async_load::<String>("test/dropped.txt"));
+
+        // Wait for the batch without taking the loaded assets out of the loader
+        let handles = std::iter::once(&visible).chain(&streamed);
+        while !loader.async_progress(handles.clone()).is_done() {
+            std::thread::yield_now();
+        }
+
+        let progress = loader.async_progress(handles);
+        assert_eq!(progress.total, 2);
+        assert_eq!(progress.bytes, 15);
+        assert_eq!(progress.fraction(), 1.0);
+
+        // Completion events are taken once, and never published for dropped handles
+        let completed = loader.take_completed();
+        assert!(completed.iter().any(|event| event.is(&visible)));
+        assert!(completed.iter().any(|event| event.is(&streamed[0])));
+        assert!(completed.iter().all(|event| event.succeeded));
+        assert!(completed
+            .iter()
+            .all(|event| event.path != std::path::Path::new("test/dropped.txt")));
+        assert!(loader.take_completed().is_empty());
+
+        // The slot of the dropped load was freed, and the others are freed once taken
+        assert_eq!(loader.outstanding(), 2);
+        assert_eq!(loader.wait(visible).unwrap(), "visible");
+        drop(streamed);
+        assert_eq!(loader.outstanding(), 0);
+    }
+
+    #[test]
+    fn error_context() {
+        let loader = Assets::new();
+        let directory = concat!(env!("CARGO_MANIFEST_DIR"), "/src/assets/");
+        loader.mount_directory("engine", directory, ENGINE_PRIORITY);
+
+        // Wrong extensions list the extensions that the asset supports
+        let Err(AssetLoadError::InvalidExtension {
+            asset,
+            extension,
+            supported,
+            ..
+        }) = loader.load::<String>("test/text.png")
+        else {
+            panic!()
+        };
+        assert_eq!(asset, "String");
+        assert_eq!(extension, "png");
+        assert_eq!(supported, vec!["txt".to_owned()]);
+
+        // Missing assets list the mounts that were searched
+        let Err(AssetLoadError::CachedNotFound { mounts, .. }) =
+            loader.load::<String>("test/missing.txt")
+        else {
+            panic!()
+        };
+        assert_eq!(mounts, vec!["engine".to_owned()]);
+
+        // Deserialization errors point to the resolved file and keep the source error
+        let error = loader.load::<String>("test/invalid.txt").unwrap_err();
+        let AssetLoadError::BoxedDeserialization { location, .. } = &error else {
+            panic!()
+        };
+        assert!(matches!(location, AssetLocation::Mount(name, Some(_)) if name == "engine"));
+        assert!(std::error::Error::source(&error).is_some());
+        assert!(error.report().contains("caused by"));
+
+        // Batches report every failure instead of stopping at the first one
+        let errors = loader
+            .load_all_from_iter::<String>(["test/missing.txt", "test/text.txt", "test/text.png"])
+            .unwrap_err();
+        assert_eq!(errors.0.len(), 2);
+        let handles = loader.async_load_from_iter::<String>(["test/text.txt", "test/invalid.txt"]);
+        assert_eq!(loader.wait_all_from_iter(handles).unwrap_err().0.len(), 1);
+        let handles = loader.async_load_from_iter::<String>(["test/text.txt"]);
+        assert!(loader.wait_all_from_iter(handles).is_ok());
+    }
 }
@@ -0,0 +1,89 @@
+use ahash::{AHashMap, AHashSet};
+use notify::{RecursiveMode, Watcher};
+use parking_lot::Mutex;
+use std::{
+    path::{Path, PathBuf},
+    sync::{mpsc::Sender, Arc},
+};
+
+// Shared file watcher that is used by the asset loader and the async loading threads
+pub(crate) type AsyncWatcher = Arc<Mutex<Option<Watching>>>;
+
+// Watches the files of the dynamically loaded assets so we can hot-reload them
+pub(crate) struct Watching {
+    watcher: notify::RecommendedWatcher,
+
+    // Directories that contain the watched files (each one is only watched once)
+    directories: AHashSet<PathBuf>,
+
+    // Maps the global paths of the watched files to their asset paths
+    watched: AHashMap<PathBuf, PathBuf>,
+}
+
+impl Watching {
+    // Create a new file watcher that will send the global paths of the modified files through the sender
+    // Removed and renamed files count as modified, since editors can save by renaming a temporary file over the original
+    pub(crate) fn new(sender: Sender<PathBuf>) -> Option<Self> {
+        let watcher =
+            notify::recommended_watcher(move |event: Result<notify::Event, notify::Error>| {
+                let Ok(event) = event else {
+                    return;
+                };
+
+                if let notify::EventKind::Modify(_)
+                | notify::EventKind::Create(_)
+                | notify::EventKind::Remove(_) = event.kind
+                {
+                    for path in event.paths {
+                        let _ = sender.send(path);
+                    }
+                }
+            });
+
+        match watcher {
+            Ok(watcher) => Some(Self {
+                watcher,
+                directories: Default::default(),
+                watched: Default::default(),
+            }),
+            Err(error) => {
+                log::warn!("Could not create asset file watcher, hot-reloading is disabled: {error}");
+                None
+            }
+        }
+    }
+
+    // Start watching the file of a dynamically loaded asset (does nothing if we already watch it)
+    // Replacing a file drops the watches on the file itself, so we watch its parent directory instead
+    pub(crate) fn watch(&mut self, global: &Path, asset: &Path) {
+        if self.watched.contains_key(global) {
+            return;
+        }
+
+        let Some(directory) = global.parent() else {
+            return;
+        };
+
+        if !self.directories.contains(directory) {
+            if let Err(error) = self.watcher.watch(directory, RecursiveMode::NonRecursive) {
+                log::warn!(
+                    "Could not watch asset {:?} for hot-reloading: {error}",
+                    asset
+                );
+                return;
+            }
+
+            self.directories.insert(directory.to_path_buf());
+        }
+
+        log::debug!("Watching asset {:?} for hot-reloading", asset);
+        self.watched
+            .insert(global.to_path_buf(), asset.to_path_buf());
+    }
+
+    // Convert the global path of a modified file back to the path of its asset
+    // Events of the other files within the watched directories are ignored
+    pub(crate) fn asset(&self, global: &Path) -> Option<&Path> {
+        self.watched.get(global).map(PathBuf::as_path)
+    }
+}
@@ -1,5 +1,5 @@
 use crate::AudioClipDeserializationError;
-use assets::Asset;
+use assets::{Asset, Reload};
 
 use std::{
     io::{BufReader, Cursor},
@@ -164,6 +164,9 @@ impl Asset for AudioClip {
     }
 }
 
+// Reloaded clips only affect the sources that get played afterwards
+impl Reload for AudioClip {}
+
 // Calculate the clip duration knowing the number of frames, channels and sample rate
 // https://chunminchang.github.io/blog/post/estimation-of-mp3-duration
 fn calculate_clip_duration_secs_from_frames(
@@ -1,6 +1,6 @@
 use crate::{AudioEmitter, AudioListener};
 use coords::{Position, Rotation};
-use ecs::Scene;
+use ecs::{Entity, Scene, Scenes};
 use world::{post_user, System, World};
 
 // Main audio update event that will play the audio clips
@@ -26,6 +26,16 @@ fn update(world: &mut World) {
     *listener.ear_positions[0].write() = left + **listener_position;
     *listener.ear_positions[1].write() = right + **listener_position;
 
+    // Update the emitters of the main scene and the audible resident scenes
+    let mut scenes = world.get_mut::<Scenes>().unwrap();
+    update_emitters(&mut scene, listener);
+    for (_, scene) in scenes.targeted_mut(|targets| targets.audio) {
+        update_emitters(scene, listener);
+    }
+}
+
+// Start playing the new audio emitters of a scene and update their positions
+fn update_emitters(scene: &mut Scene, listener: &AudioListener) {
     // Iterate through all the audio sources that have been changed or added
     let filter = ecs::added::<&AudioEmitter>() | ecs::modified::<&AudioEmitter>();
     for emitter in scene.query_mut_with::<&mut AudioEmitter>(filter) {
@@ -38,6 +48,19 @@ fn update(world: &mut World) {
         }
     }
 
+    // Pause the streams of disabled emitters and resume them once they get enabled again
+    // Toggling an entity marks all of its components as modified
+    let filter = ecs::modified::<&AudioEmitter>() & ecs::include_disabled();
+    for (entity, emitter) in scene.query_with::<(&Entity, &AudioEmitter)>(filter) {
+        if let Some(stream) = emitter.stream.as_ref() {
+            if scene.is_enabled(*entity).unwrap() && emitter.playing {
+                cpal::traits::StreamTrait::play(stream).unwrap();
+            } else {
+                cpal::traits::StreamTrait::pause(stream).unwrap();
+            }
+        }
+    }
+
     // Update the positions of the positional audio emitters
     let filter = ecs::modified::<&Position>();
     for (emitter, position) in scene.query_mut_with::<(&mut AudioEmitter, &Position)>(filter) {
@@ -43,6 +43,15 @@ impl Archetype {
         }
     }
 
+    // Create an empty archetype that uses the given table of columns
+    pub(crate) fn from_table(mask: Mask, table: Table) -> Self {
+        Self {
+            mask,
+            table,
+            entities: Default::default(),
+        }
+    }
+
     pub(crate) fn instantiate_prefab(
         &mut self,
         entities: &mut EntitySet,
@@ -53,6 +62,7 @@ impl Archetype {
         let linkings = EntityLinkings {
             mask: self.mask,
             index,
+            enabled: true,
         };
         let entity = entities.insert(linkings);
         self.entities.push(entity);
@@ -91,6 +101,7 @@ impl Archetype {
             let linkings = EntityLinkings {
                 mask: self.mask,
                 index: old_len + i,
+                enabled: true,
             };
             let entity = entities.insert(linkings);
             self.entities.push(entity);
@@ -199,6 +210,11 @@ impl Archetype {
         &self.entities
     }
 
+    // Get the entity vector mutably
+    pub(crate) fn entities_mut(&mut self) -> &mut Vec<Entity> {
+        &mut self.entities
+    }
+
     /// Get the unique archetype mask.
     pub fn mask(&self) -> Mask {
         self.mask
@@ -24,6 +24,7 @@ impl Entity {
 pub struct EntityLinkings {
     pub(crate) mask: Mask,
     pub(crate) index: usize,
+    pub(crate) enabled: bool,
 }
 
 impl EntityLinkings {
@@ -36,4 +37,9 @@ impl EntityLinkings {
     pub fn index(&self) -> usize {
         self.index
     }
+
+    /// Check if the entity is enabled (disabled entities are skipped by queries by default).
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
 }
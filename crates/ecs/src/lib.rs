@@ -10,6 +10,8 @@ mod mask;
 mod query;
 mod registry;
 mod scene;
+mod scenes;
+mod snapshot;
 mod vec;
 pub use archetype::*;
 pub use components::*;
@@ -19,5 +21,7 @@ pub use mask::*;
 pub use query::*;
 pub use registry::*;
 pub use scene::*;
+pub use scenes::*;
+pub use snapshot::*;
 pub use vec::*;
 mod tests;
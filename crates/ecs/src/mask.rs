@@ -34,7 +34,7 @@ impl Mask {
     }
 
     /// Create a mask that has it's bitfield set to zero.
-    pub fn zero() -> Mask {
+    pub const fn zero() -> Mask {
         Mask(0b0)
     }
 
@@ -1,7 +1,9 @@
 pub(crate) mod filters;
 mod query_mut;
 mod query_ref;
+mod state;
 
 pub use filters::*;
 pub use query_mut::*;
 pub use query_ref::*;
+pub use state::*;
@@ -1,5 +1,6 @@
 use crate::{
-    Archetype, ArchetypeSet, LayoutAccess, Mask, QueryLayoutMut, QueryLayoutRef, StateColumn,
+    Archetype, ArchetypeSet, EntitySet, LayoutAccess, Mask, QueryLayoutMut, QueryLayoutRef,
+    StateColumn,
 };
 use std::marker::PhantomData;
 use utils::BitSet;
@@ -50,6 +51,13 @@ pub trait QueryFilter {
     /// Cached columns that we fetch from an archetypes.
     type Columns<'a>: 'a;
 
+    /// Should the query also return the entities that were disabled.
+    const INCLUDE_DISABLED: bool = false;
+
+    /// Filters that don't discard any entry by themselves (like [IncludeDisabled]) are ignored by the modifiers.
+    /// This makes them neutral when combined using either the "&" or "|" operators.
+    const NEUTRAL: bool = false;
+
     /// Create the permanent cached data.
     fn prepare() -> Self::Cached;
 
@@ -106,6 +114,41 @@ pub(super) fn archetypes<L: QueryLayoutRef, F: QueryFilter>(
     (mask, archetypes, cached)
 }
 
+// Hide the disabled entities of each archetype by unsetting their bits within the bitsets
+// This does nothing if there are no disabled entities within the scene
+pub(super) fn apply_enabled_bitsets<'a>(
+    entities: &EntitySet,
+    disabled: usize,
+    archetypes: impl Iterator<Item = &'a Archetype>,
+    bitsets: &mut Option<Vec<BitSet<usize>>>,
+) {
+    if disabled == 0 {
+        return;
+    }
+
+    let enabled = archetypes.map(|archetype| {
+        let mut bitset = BitSet::<usize>::new();
+        bitset.reserve(archetype.len());
+        for (i, entity) in archetype.entities().iter().enumerate() {
+            if entities[*entity].enabled {
+                bitset.set(i);
+            }
+        }
+        bitset
+    });
+
+    match bitsets {
+        Some(bitsets) => {
+            for (bitset, enabled) in bitsets.iter_mut().zip(enabled) {
+                for (out, chunk) in bitset.chunks_mut().iter_mut().zip(enabled.chunks()) {
+                    *out &= *chunk;
+                }
+            }
+        }
+        None => *bitsets = Some(enabled.collect()),
+    }
+}
+
 // Create a vector of bitsets in case we are using query filtering
 pub(super) fn generate_bitset_chunks<'a, F: QueryFilter>(
     archetypes: impl Iterator<Item = &'a Archetype>,
@@ -151,6 +194,10 @@ pub struct Modified<T: QueryLayoutRef>(PhantomData<T>);
 /// All the components within the [QueryLayoutRef] must be within the archetype for this filter to pass the coarse test
 pub struct Contains<T: QueryLayoutRef>(PhantomData<T>);
 
+/// Filter source that makes the query also return the disabled entities
+/// This is neutral when combined with other filters, so it never changes which entries pass them
+pub struct IncludeDisabled;
+
 // Note: ONLY USED INTERNALLY. THIS IS LITERALLY USELESS
 pub(crate) struct Always;
 
@@ -166,6 +213,19 @@ pub struct Xor<A: QueryFilter, B: QueryFilter>(PhantomData<A>, PhantomData<B>);
 /// Passes if the filters fail the coarse / fine tests
 pub struct Not<A: QueryFilter>(PhantomData<A>);
 
+// Combine the results of two filters using a binary operator, ignoring the neutral filters
+fn combine<A: QueryFilter, B: QueryFilter, T>(
+    a: impl FnOnce() -> T,
+    b: impl FnOnce() -> T,
+    op: impl FnOnce(T, T) -> T,
+) -> T {
+    match (A::NEUTRAL, B::NEUTRAL) {
+        (true, _) => b(),
+        (false, true) => a(),
+        (false, false) => op(a(), b()),
+    }
+}
+
 pub(crate) fn get_either_states(col: &crate::UntypedColumn, ticked: bool) -> &StateColumn {
     match ticked {
         true => col.delta_tick_states(),
@@ -295,6 +355,30 @@ impl<L: QueryLayoutRef> QueryFilter for Contains<L> {
     }
 }
 
+impl QueryFilter for IncludeDisabled {
+    type Cached = ();
+    type Columns<'a> = ();
+    const INCLUDE_DISABLED: bool = true;
+    const NEUTRAL: bool = true;
+
+    fn prepare() -> Self::Cached {}
+
+    fn evaluate_archetype(_cached: Self::Cached, _archetype: &Archetype) -> bool {
+        true
+    }
+
+    fn cache_columns(
+        _cached: Self::Cached,
+        _archetype: &Archetype,
+        _ticked: bool,
+    ) -> Self::Columns<'_> {
+    }
+
+    fn evaluate_chunk(_columns: &Self::Columns<'_>, _index: usize) -> ChunkEval {
+        ChunkEval::Evaluated(usize::MAX)
+    }
+}
+
 impl QueryFilter for Always {
     type Cached = ();
     type Columns<'a> = ();
@@ -321,13 +405,19 @@ impl QueryFilter for Always {
 impl<A: QueryFilter, B: QueryFilter> QueryFilter for And<A, B> {
     type Cached = (A::Cached, B::Cached);
     type Columns<'a> = (A::Columns<'a>, B::Columns<'a>);
+    const INCLUDE_DISABLED: bool = A::INCLUDE_DISABLED || B::INCLUDE_DISABLED;
+    const NEUTRAL: bool = A::NEUTRAL && B::NEUTRAL;
 
     fn prepare() -> Self::Cached {
         (A::prepare(), B::prepare())
     }
 
     fn evaluate_archetype(cached: Self::Cached, archetype: &Archetype) -> bool {
-        A::evaluate_archetype(cached.0, archetype) && B::evaluate_archetype(cached.1, archetype)
+        combine::<A, B, _>(
+            || A::evaluate_archetype(cached.0, archetype),
+            || B::evaluate_archetype(cached.1, archetype),
+            |a, b| a && b,
+        )
     }
 
     fn cache_columns(
@@ -342,22 +432,30 @@ impl<A: QueryFilter, B: QueryFilter> QueryFilter for And<A, B> {
     }
 
     fn evaluate_chunk(columns: &Self::Columns<'_>, index: usize) -> ChunkEval {
-        let a = A::evaluate_chunk(&columns.0, index);
-        let b = B::evaluate_chunk(&columns.1, index);
-        a.zip_with(b, |(a, b)| a & b)
+        combine::<A, B, _>(
+            || A::evaluate_chunk(&columns.0, index),
+            || B::evaluate_chunk(&columns.1, index),
+            |a, b| a.zip_with(b, |(a, b)| a & b),
+        )
     }
 }
 
 impl<A: QueryFilter, B: QueryFilter> QueryFilter for Or<A, B> {
     type Cached = (A::Cached, B::Cached);
     type Columns<'a> = (A::Columns<'a>, B::Columns<'a>);
+    const INCLUDE_DISABLED: bool = A::INCLUDE_DISABLED || B::INCLUDE_DISABLED;
+    const NEUTRAL: bool = A::NEUTRAL && B::NEUTRAL;
 
     fn prepare() -> Self::Cached {
         (A::prepare(), B::prepare())
     }
 
     fn evaluate_archetype(cached: Self::Cached, archetype: &Archetype) -> bool {
-        A::evaluate_archetype(cached.0, archetype) || B::evaluate_archetype(cached.1, archetype)
+        combine::<A, B, _>(
+            || A::evaluate_archetype(cached.0, archetype),
+            || B::evaluate_archetype(cached.1, archetype),
+            |a, b| a || b,
+        )
     }
 
     fn cache_columns(
@@ -372,22 +470,30 @@ impl<A: QueryFilter, B: QueryFilter> QueryFilter for Or<A, B> {
     }
 
     fn evaluate_chunk(columns: &Self::Columns<'_>, index: usize) -> ChunkEval {
-        let a = A::evaluate_chunk(&columns.0, index);
-        let b = B::evaluate_chunk(&columns.1, index);
-        a.zip_with(b, |(a, b)| a | b)
+        combine::<A, B, _>(
+            || A::evaluate_chunk(&columns.0, index),
+            || B::evaluate_chunk(&columns.1, index),
+            |a, b| a.zip_with(b, |(a, b)| a | b),
+        )
     }
 }
 
 impl<A: QueryFilter, B: QueryFilter> QueryFilter for Xor<A, B> {
     type Cached = (A::Cached, B::Cached);
     type Columns<'a> = (A::Columns<'a>, B::Columns<'a>);
+    const INCLUDE_DISABLED: bool = A::INCLUDE_DISABLED || B::INCLUDE_DISABLED;
+    const NEUTRAL: bool = A::NEUTRAL && B::NEUTRAL;
 
     fn prepare() -> Self::Cached {
         (A::prepare(), B::prepare())
     }
 
     fn evaluate_archetype(cached: Self::Cached, archetype: &Archetype) -> bool {
-        A::evaluate_archetype(cached.0, archetype) ^ B::evaluate_archetype(cached.1, archetype)
+        combine::<A, B, _>(
+            || A::evaluate_archetype(cached.0, archetype),
+            || B::evaluate_archetype(cached.1, archetype),
+            |a, b| a ^ b,
+        )
     }
 
     fn cache_columns(
@@ -402,22 +508,26 @@ impl<A: QueryFilter, B: QueryFilter> QueryFilter for Xor<A, B> {
     }
 
     fn evaluate_chunk(columns: &Self::Columns<'_>, index: usize) -> ChunkEval {
-        let a = A::evaluate_chunk(&columns.0, index);
-        let b = B::evaluate_chunk(&columns.1, index);
-        a.zip_with(b, |(a, b)| a ^ b)
+        combine::<A, B, _>(
+            || A::evaluate_chunk(&columns.0, index),
+            || B::evaluate_chunk(&columns.1, index),
+            |a, b| a.zip_with(b, |(a, b)| a ^ b),
+        )
     }
 }
 
 impl<A: QueryFilter> QueryFilter for Not<A> {
     type Cached = A::Cached;
     type Columns<'a> = A::Columns<'a>;
+    const INCLUDE_DISABLED: bool = A::INCLUDE_DISABLED;
+    const NEUTRAL: bool = A::NEUTRAL;
 
     fn prepare() -> Self::Cached {
         A::prepare()
     }
 
     fn evaluate_archetype(cached: Self::Cached, archetype: &Archetype) -> bool {
-        !A::evaluate_archetype(cached, archetype)
+        A::NEUTRAL || !A::evaluate_archetype(cached, archetype)
     }
 
     fn cache_columns(
@@ -429,7 +539,12 @@ impl<A: QueryFilter> QueryFilter for Not<A> {
     }
 
     fn evaluate_chunk(columns: &Self::Columns<'_>, index: usize) -> ChunkEval {
-        A::evaluate_chunk(columns, index).map(|x| !x)
+        let eval = A::evaluate_chunk(columns, index);
+        if A::NEUTRAL {
+            eval
+        } else {
+            eval.map(|x| !x)
+        }
     }
 }
 
@@ -443,6 +558,11 @@ pub fn added<L: QueryLayoutRef>() -> Wrap<Added<L>> {
     Wrap::<Added<L>>(PhantomData)
 }
 
+/// Source that makes the query also return the disabled entities.
+pub fn include_disabled() -> Wrap<IncludeDisabled> {
+    Wrap::<IncludeDisabled>(PhantomData)
+}
+
 /// Source to check if we contain a specific component within the archetype.
 pub fn contains<L: QueryLayoutRef>() -> Wrap<Contains<L>> {
     Wrap::<Contains<L>>(PhantomData)
@@ -16,12 +16,19 @@ pub struct QueryMut<'a: 'b, 'b, L: QueryLayoutMut> {
 impl<'a: 'b, 'b, L: QueryLayoutMut> QueryMut<'a, 'b, L> {
     // Create a new mut query from the scene
     pub(crate) fn new(scene: &'a mut Scene) -> Self {
-        let (access, archetypes, _) = super::archetypes_mut::<L, Always>(scene.archetypes_mut());
+        let (access, archetypes, _) = super::archetypes_mut::<L, Always>(&mut scene.archetypes);
+        let mut bitsets = None;
+        super::apply_enabled_bitsets(
+            &scene.entities,
+            scene.disabled,
+            archetypes.iter().map(|a| &**a),
+            &mut bitsets,
+        );
 
         Self {
             archetypes,
             access,
-            bitsets: None,
+            bitsets,
             _phantom1: PhantomData,
             _phantom3: PhantomData,
         }
@@ -34,14 +41,40 @@ impl<'a: 'b, 'b, L: QueryLayoutMut> QueryMut<'a, 'b, L> {
         ticked: bool,
     ) -> Self {
         // Filter out the archetypes then create the bitsets
-        let (access, archetypes, cached) = super::archetypes_mut::<L, F>(scene.archetypes_mut());
-        let bitsets =
-            super::generate_bitset_chunks::<F>(archetypes.iter().map(|a| &**a), cached, ticked);
+        let (access, archetypes, cached) = super::archetypes_mut::<L, F>(&mut scene.archetypes);
+        let mut bitsets = Some(super::generate_bitset_chunks::<F>(
+            archetypes.iter().map(|a| &**a),
+            cached,
+            ticked,
+        ));
+
+        if !F::INCLUDE_DISABLED {
+            super::apply_enabled_bitsets(
+                &scene.entities,
+                scene.disabled,
+                archetypes.iter().map(|a| &**a),
+                &mut bitsets,
+            );
+        }
 
         Self {
             archetypes,
             access,
-            bitsets: Some(bitsets),
+            bitsets,
+            _phantom1: PhantomData,
+            _phantom3: PhantomData,
+        }
+    }
+
+    // Create a new mut query from archetypes that were already filtered by a query state
+    pub(crate) fn from_parts(
+        archetypes: Vec<&'a mut Archetype>,
+        bitsets: Option<Vec<BitSet<usize>>>,
+    ) -> Self {
+        Self {
+            archetypes,
+            access: L::reduce(|a, b| a | b),
+            bitsets,
             _phantom1: PhantomData,
             _phantom3: PhantomData,
         }
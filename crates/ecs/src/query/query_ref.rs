@@ -18,9 +18,17 @@ impl<'a: 'b, 'b, 's, L: QueryLayoutRef> QueryRef<'a, 'b, 's, L> {
     // Create a new mut query from the scene for active entities
     pub(crate) fn new(scene: &'a Scene) -> Self {
         let (mask, archetypes, _) = super::archetypes::<L, Always>(scene.archetypes());
+        let mut bitsets = None;
+        super::apply_enabled_bitsets(
+            &scene.entities,
+            scene.disabled,
+            archetypes.iter().map(|a| &**a),
+            &mut bitsets,
+        );
+
         Self {
             archetypes,
-            bitsets: None,
+            bitsets,
             _phantom3: PhantomData,
             access: mask,
             _phantom1: PhantomData,
@@ -36,13 +44,40 @@ impl<'a: 'b, 'b, 's, L: QueryLayoutRef> QueryRef<'a, 'b, 's, L> {
     ) -> Self {
         // Filter out the archetypes then create the bitsets
         let (access, archetypes, cached) = super::archetypes::<L, F>(scene.archetypes());
-        let bitsets =
-            super::generate_bitset_chunks::<F>(archetypes.iter().map(|a| &**a), cached, ticked);
+        let mut bitsets = Some(super::generate_bitset_chunks::<F>(
+            archetypes.iter().map(|a| &**a),
+            cached,
+            ticked,
+        ));
+
+        if !F::INCLUDE_DISABLED {
+            super::apply_enabled_bitsets(
+                &scene.entities,
+                scene.disabled,
+                archetypes.iter().map(|a| &**a),
+                &mut bitsets,
+            );
+        }
 
         Self {
             archetypes,
             access,
-            bitsets: Some(bitsets),
+            bitsets,
+            _phantom3: PhantomData,
+            _phantom1: PhantomData,
+            _phantom2: PhantomData,
+        }
+    }
+
+    // Create a new query from archetypes that were already filtered by a query state
+    pub(crate) fn from_parts(
+        archetypes: Vec<&'a Archetype>,
+        bitsets: Option<Vec<BitSet<usize>>>,
+    ) -> Self {
+        Self {
+            archetypes,
+            access: L::reduce(|a, b| a | b),
+            bitsets,
             _phantom3: PhantomData,
             _phantom1: PhantomData,
             _phantom2: PhantomData,
@@ -0,0 +1,194 @@
+use crate::{
+    Always, Archetype, ArchetypeSet, Mask, MaskHashSet, QueryFilter, QueryLayoutMut,
+    QueryLayoutRef, QueryMut, QueryMutIter, QueryRef, QueryRefIter, Scene, Wrap,
+};
+use std::marker::PhantomData;
+
+/// A query state caches the masks of the archetypes that match a specific query layout.
+/// Instead of scanning the whole archetype set every time we create a query, the state will only check the archetypes that were created since its last use.
+/// Systems should keep the state around (in a resource for example) and use it with the same scene every time.
+/// Using the state with another scene is still valid, but it will make the state discard its cache.
+pub struct QueryState<L> {
+    scene: Option<u64>,
+    checked: MaskHashSet,
+    matched: Vec<Mask>,
+    _phantom: PhantomData<L>,
+}
+
+impl<L> Default for QueryState<L> {
+    fn default() -> Self {
+        Self {
+            scene: None,
+            checked: MaskHashSet::default(),
+            matched: Vec::new(),
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<L> QueryState<L> {
+    /// Create a new query state with an empty cache.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Get the masks of all the archetypes that matched the query layout the last time we used the state.
+    pub fn matched(&self) -> &[Mask] {
+        &self.matched
+    }
+
+    // Check the archetypes that were created since the last update (or all of them if the scene changed)
+    fn update(&mut self, scene: &Scene, search: Mask) {
+        if self.scene != Some(scene.id) {
+            self.scene = Some(scene.id);
+            self.checked.clear();
+            self.matched.clear();
+        }
+
+        // Archetypes never get removed from a scene (the archetype set can't be modified outside of the crate), so we can use the count to detect new ones
+        let archetypes = &scene.archetypes;
+        if archetypes.len() == self.checked.len() {
+            return;
+        }
+
+        for &mask in archetypes.keys() {
+            if self.checked.insert(mask) && mask.contains(search) {
+                self.matched.push(mask);
+            }
+        }
+    }
+
+    // Fetch the cached archetypes that pass the coarse archetype filter
+    fn archetypes<'a, F: QueryFilter>(
+        &self,
+        archetypes: &'a ArchetypeSet,
+        cached: F::Cached,
+    ) -> Vec<&'a Archetype> {
+        self.matched
+            .iter()
+            .filter_map(|mask| archetypes.get(mask))
+            .filter(|archetype| !archetype.is_empty() && F::evaluate_archetype(cached, archetype))
+            .collect()
+    }
+
+    // Fetch the cached archetypes that pass the coarse archetype filter mutably
+    fn archetypes_mut<'a, F: QueryFilter>(
+        &self,
+        archetypes: &'a mut ArchetypeSet,
+        cached: F::Cached,
+    ) -> Vec<&'a mut Archetype> {
+        self.matched
+            .iter()
+            .filter_map(|mask| {
+                let archetype = archetypes.get_mut(mask)? as *mut Archetype;
+
+                // The cached masks are unique, so the archetypes will never alias
+                Some(unsafe { &mut *archetype })
+            })
+            .filter(|archetype| !archetype.is_empty() && F::evaluate_archetype(cached, archetype))
+            .collect()
+    }
+}
+
+impl<L: QueryLayoutRef> QueryState<L> {
+    /// Create a new immutable [query](QueryRef) from the scene using the cached archetypes (with no filter).
+    pub fn query<'a>(&mut self, scene: &'a Scene) -> QueryRef<'a, 'a, 'a, L> {
+        self.update(scene, L::reduce(|a, b| a | b).search());
+        let archetypes = self.archetypes::<Always>(&scene.archetypes, ());
+        let mut bitsets = None;
+        super::apply_enabled_bitsets(
+            &scene.entities,
+            scene.disabled,
+            archetypes.iter().map(|a| &**a),
+            &mut bitsets,
+        );
+        QueryRef::from_parts(archetypes, bitsets)
+    }
+
+    /// Create a new immutable [query](QueryRef) from the scene using the cached archetypes and a [filter](QueryFilter).
+    pub fn query_with<'a, F: QueryFilter>(
+        &mut self,
+        scene: &'a Scene,
+        _: Wrap<F>,
+    ) -> QueryRef<'a, 'a, 'a, L> {
+        self.update(scene, L::reduce(|a, b| a | b).search());
+        let cached = F::prepare();
+        let archetypes = self.archetypes::<F>(&scene.archetypes, cached);
+        let mut bitsets = Some(super::generate_bitset_chunks::<F>(
+            archetypes.iter().map(|a| &**a),
+            cached,
+            scene.ticked,
+        ));
+
+        if !F::INCLUDE_DISABLED {
+            super::apply_enabled_bitsets(
+                &scene.entities,
+                scene.disabled,
+                archetypes.iter().map(|a| &**a),
+                &mut bitsets,
+            );
+        }
+        QueryRef::from_parts(archetypes, bitsets)
+    }
+
+    /// Iterate immutably over the entries of the scene using the cached archetypes.
+    pub fn iter<'a>(&mut self, scene: &'a Scene) -> QueryRefIter<'a, L> {
+        self.query(scene).into_iter()
+    }
+}
+
+impl<L: QueryLayoutMut> QueryState<L> {
+    /// Create a new mutable [query](QueryMut) from the scene using the cached archetypes (with no filter).
+    pub fn query_mut<'a>(&mut self, scene: &'a mut Scene) -> QueryMut<'a, 'a, L> {
+        assert!(
+            L::is_valid(),
+            "Query layout is not valid, check the layout for component collisions"
+        );
+        self.update(scene, L::reduce(|a, b| a | b).search());
+        let archetypes = self.archetypes_mut::<Always>(&mut scene.archetypes, ());
+        let mut bitsets = None;
+        super::apply_enabled_bitsets(
+            &scene.entities,
+            scene.disabled,
+            archetypes.iter().map(|a| &**a),
+            &mut bitsets,
+        );
+        QueryMut::from_parts(archetypes, bitsets)
+    }
+
+    /// Create a new mutable [query](QueryMut) from the scene using the cached archetypes and a [filter](QueryFilter).
+    pub fn query_mut_with<'a, F: QueryFilter>(
+        &mut self,
+        scene: &'a mut Scene,
+        _: Wrap<F>,
+    ) -> QueryMut<'a, 'a, L> {
+        assert!(
+            L::is_valid(),
+            "Query layout is not valid, check the layout for component collisions"
+        );
+        self.update(scene, L::reduce(|a, b| a | b).search());
+        let cached = F::prepare();
+        let ticked = scene.ticked;
+        let archetypes = self.archetypes_mut::<F>(&mut scene.archetypes, cached);
+        let mut bitsets = Some(super::generate_bitset_chunks::<F>(
+            archetypes.iter().map(|a| &**a),
+            cached,
+            ticked,
+        ));
+
+        if !F::INCLUDE_DISABLED {
+            super::apply_enabled_bitsets(
+                &scene.entities,
+                scene.disabled,
+                archetypes.iter().map(|a| &**a),
+                &mut bitsets,
+            );
+        }
+        QueryMut::from_parts(archetypes, bitsets)
+    }
+
+    /// Iterate mutably over the entries of the scene using the cached archetypes.
+    pub fn iter_mut<'a>(&mut self, scene: &'a mut Scene) -> QueryMutIter<'a, L> {
+        self.query_mut(scene).into_iter()
+    }
+}
@@ -2,13 +2,16 @@ use ahash::AHashMap;
 use itertools::Itertools;
 use rayon::prelude::{IntoParallelRefIterator, ParallelIterator};
 use slotmap::SlotMap;
-use std::iter::once;
+use std::{
+    iter::once,
+    sync::atomic::{AtomicU64, Ordering},
+};
 use world::{post_user, user, System, World};
 
 use crate::{
     entity::Entity, mask, Archetype, Bundle, Component, EntityLinkings, EntryMut, EntryRef, Mask,
     MaskHashMap, PrefabBundle, QueryFilter, QueryLayoutMut, QueryLayoutRef, QueryMut, QueryRef,
-    UntypedVec, Wrap, Named, Tagged,
+    Scenes, StateFlags, UntypedVec, Wrap, Named, Tagged,
 };
 
 // Convenience type aliases
@@ -19,6 +22,9 @@ pub(crate) type RemovedComponents = MaskHashMap<Box<dyn UntypedVec>>;
 /// Identifier for prefabs
 pub type PrefabId = &'static str;
 
+// Used to give each scene a unique ID
+static NEXT_SCENE_ID: AtomicU64 = AtomicU64::new(0);
+
 /// The scene is what will contain the multiple ECS entities and archetypes
 pub struct Scene {
     // Entities are just objects that contain an ID and some component masks
@@ -39,6 +45,12 @@ pub struct Scene {
     pub(crate) prefabs: AHashMap<PrefabId, (Box<dyn PrefabBundle>, Mask)>,
 
     pub(crate) ticked: bool,
+
+    // Number of entities that are currently disabled (used to skip the enabled checks in queries)
+    pub(crate) disabled: usize,
+
+    // Unique ID of this scene, used by query states to detect when they get used with another scene
+    pub(crate) id: u64,
 }
 
 impl Default for Scene {
@@ -51,6 +63,8 @@ impl Default for Scene {
             removed: Default::default(),
             prefabs: AHashMap::default(),
             ticked: false,
+            disabled: 0,
+            id: NEXT_SCENE_ID.fetch_add(1, Ordering::Relaxed),
         }
     }
 }
@@ -87,6 +101,10 @@ impl Scene {
     /// Panics if the entity ID is invalid.
     pub fn remove(&mut self, entity: Entity) {
         let linkings = *self.entities.get(entity).unwrap();
+        if !linkings.enabled {
+            self.disabled -= 1;
+        }
+
         let archetype = self.archetypes.get_mut(&linkings.mask).unwrap();
         archetype.remove_from_iter(
             &mut self.entities,
@@ -104,6 +122,7 @@ impl Scene {
             .map(|e| (e, *self.entities.get(e).unwrap()))
             .collect::<Vec<_>>();
         entities.sort_unstable_by_key(|(_, l)| l.mask);
+        self.disabled -= entities.iter().filter(|(_, l)| !l.enabled).count();
 
         // Group the entities based on their archetype
         let grouped = entities.iter().group_by(|(_, l)| l.mask);
@@ -224,13 +243,47 @@ impl Scene {
         EntryMut::new(self, entity)
     }
 
+    /// Enable or disable an entity without moving it to another archetype.
+    /// Disabled entities keep their components but they are skipped by queries (unless we use the [include_disabled](crate::include_disabled) filter).
+    /// All the components of the entity are marked as modified so systems can react to the change.
+    /// Returns None if the entity does not exist.
+    pub fn set_enabled(&mut self, entity: Entity, enabled: bool) -> Option<()> {
+        let linkings = self.entities.get_mut(entity)?;
+        if linkings.enabled == enabled {
+            return Some(());
+        }
+
+        linkings.enabled = enabled;
+        let index = linkings.index;
+        let archetype = self.archetypes.get_mut(&linkings.mask).unwrap();
+        for (_, column) in archetype.table_mut().iter_mut() {
+            let modified = |flags: &mut StateFlags| flags.modified = true;
+            column.delta_frame_states_mut().update(index, modified);
+            column.delta_tick_states_mut().update(index, modified);
+        }
+
+        if enabled {
+            self.disabled -= 1;
+        } else {
+            self.disabled += 1;
+        }
+
+        Some(())
+    }
+
+    /// Check if an entity is enabled. Returns None if the entity does not exist.
+    pub fn is_enabled(&self, entity: Entity) -> Option<bool> {
+        self.entities.get(entity).map(|linkings| linkings.enabled)
+    }
+
     /// Get a immutable reference to the active [archetype set](ArchetypeSet).
     pub fn archetypes(&self) -> &ArchetypeSet {
         &self.archetypes
     }
 
     /// Get a mutable reference to the active [archetype set](ArchetypeSet).
-    pub fn archetypes_mut(&mut self) -> &mut ArchetypeSet {
+    /// Only available within the crate, since query states rely on archetypes never getting removed.
+    pub(crate) fn archetypes_mut(&mut self) -> &mut ArchetypeSet {
         &mut self.archetypes
     }
 
@@ -291,45 +344,56 @@ impl Scene {
     }
 }
 
-// Init event that will insert the ECS resource
+// Init event that will insert the ECS resources
 fn init(world: &mut World) {
     world.insert(Scene::default());
+    world.insert(Scenes::default());
+}
+
+// Apply a function on the main scene and on all the resident scenes
+fn for_each_scene(world: &mut World, mut function: impl FnMut(&mut Scene)) {
+    let mut scene = world.get_mut::<Scene>().unwrap();
+    let mut scenes = world.get_mut::<Scenes>().unwrap();
+    function(&mut scene);
+    for (_, scene) in scenes.iter_mut() {
+        function(scene);
+    }
 }
 
 // At the end of each frame reset the delta states
 fn reset_delta_frame_states_end(world: &mut World) {
-    let mut scene = world.get_mut::<Scene>().unwrap();
-    for (_, archetype) in scene.archetypes_mut() {
-        for (_, column) in archetype.table_mut().iter_mut() {
-            column.delta_frame_states_mut().reset();
+    for_each_scene(world, |scene| {
+        for archetype in scene.archetypes_mut().values_mut() {
+            for (_, column) in archetype.table_mut().iter_mut() {
+                column.delta_frame_states_mut().reset();
+            }
         }
-    }
 
-    for (_, vec) in scene.removed.iter_mut() {
-        vec.clear();
-    }
+        for (_, vec) in scene.removed.iter_mut() {
+            vec.clear();
+        }
+    });
 }
 
 // At the end of each tick reset the tick states
 fn reset_delta_tick_states_end(world: &mut World) {
-    let mut scene = world.get_mut::<Scene>().unwrap();
-    for (_, archetype) in scene.archetypes_mut() {
-        for (_, column) in archetype.table_mut().iter_mut() {
-            column.delta_tick_states_mut().reset();
+    for_each_scene(world, |scene| {
+        for archetype in scene.archetypes_mut().values_mut() {
+            for (_, column) in archetype.table_mut().iter_mut() {
+                column.delta_tick_states_mut().reset();
+            }
         }
-    }
+    });
 }
 
 // Called at the start of every frame to set ticked to false
 fn set_ticked_true(world: &mut World) {
-    let mut scene = world.get_mut::<Scene>().unwrap();
-    scene.ticked = true;
+    for_each_scene(world, |scene| scene.ticked = true);
 }
 
 // Called at the start of every tick to set ticked to true
 fn set_ticked_false(world: &mut World) {
-    let mut scene = world.get_mut::<Scene>().unwrap();
-    scene.ticked = false;
+    for_each_scene(world, |scene| scene.ticked = false);
 }
 
 /// Only used for init
@@ -0,0 +1,243 @@
+use crate::{Archetype, Entity, EntityLinkings, Scene, StateFlags, Table};
+use slotmap::{new_key_type, SlotMap};
+
+new_key_type! {
+    /// Handle to a scene that is resident within the [Scenes] resource.
+    pub struct SceneId;
+}
+
+/// Tells the engine systems if they should process the entities of a resident scene.
+/// The main [Scene] resource is always processed by every system.
+#[derive(Default, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SceneTargets {
+    /// Should the entities of this scene be rendered.
+    pub rendering: bool,
+
+    /// Should the entities of this scene be simulated by the physics system.
+    pub physics: bool,
+
+    /// Should the audio emitters of this scene be played.
+    pub audio: bool,
+}
+
+impl SceneTargets {
+    /// Targets that will make the scene be processed by all the engine systems.
+    pub fn all() -> Self {
+        Self {
+            rendering: true,
+            physics: true,
+            audio: true,
+        }
+    }
+}
+
+// A scene that lives alongside the main scene resource
+struct ResidentScene {
+    name: String,
+    scene: Scene,
+    targets: SceneTargets,
+    swaps: u64,
+}
+
+/// Resource that contains multiple scenes that live alongside the main [Scene] resource.
+/// These can be used to populate a level in the background, or to keep a separate UI scene.
+#[derive(Default)]
+pub struct Scenes {
+    scenes: SlotMap<SceneId, ResidentScene>,
+}
+
+impl Scenes {
+    /// Insert a new resident scene with a unique name and the given targets.
+    pub fn insert(&mut self, name: &str, scene: Scene, targets: SceneTargets) -> SceneId {
+        if self.find(name).is_some() {
+            log::warn!("Inserted resident scene '{name}', but the name is already in use");
+        }
+
+        let id = self.scenes.insert(ResidentScene {
+            name: name.to_string(),
+            scene,
+            targets,
+            swaps: 0,
+        });
+        log::debug!("Inserted resident scene '{name}'");
+        id
+    }
+
+    /// Remove a resident scene and return it.
+    pub fn remove(&mut self, id: SceneId) -> Option<Scene> {
+        self.scenes.remove(id).map(|resident| resident.scene)
+    }
+
+    /// Find the ID of a resident scene using its name.
+    pub fn find(&self, name: &str) -> Option<SceneId> {
+        self.scenes
+            .iter()
+            .find_map(|(id, resident)| (resident.name == name).then_some(id))
+    }
+
+    /// Get the name of a resident scene.
+    pub fn name(&self, id: SceneId) -> Option<&str> {
+        self.scenes.get(id).map(|resident| resident.name.as_str())
+    }
+
+    /// Get an immutable reference to a resident scene.
+    pub fn get(&self, id: SceneId) -> Option<&Scene> {
+        self.scenes.get(id).map(|resident| &resident.scene)
+    }
+
+    /// Get a mutable reference to a resident scene.
+    pub fn get_mut(&mut self, id: SceneId) -> Option<&mut Scene> {
+        self.scenes.get_mut(id).map(|resident| &mut resident.scene)
+    }
+
+    /// Get the targets of a resident scene.
+    pub fn targets(&self, id: SceneId) -> Option<SceneTargets> {
+        self.scenes.get(id).map(|resident| resident.targets)
+    }
+
+    /// Get the targets of a resident scene mutably.
+    pub fn targets_mut(&mut self, id: SceneId) -> Option<&mut SceneTargets> {
+        self.scenes
+            .get_mut(id)
+            .map(|resident| &mut resident.targets)
+    }
+
+    /// Check if a resident scene exists.
+    pub fn contains(&self, id: SceneId) -> bool {
+        self.scenes.contains_key(id)
+    }
+
+    /// Get the number of resident scenes.
+    pub fn len(&self) -> usize {
+        self.scenes.len()
+    }
+
+    /// Check if there are no resident scenes.
+    pub fn is_empty(&self) -> bool {
+        self.scenes.is_empty()
+    }
+
+    /// Iterate over all the resident scenes immutably.
+    pub fn iter(&self) -> impl Iterator<Item = (SceneId, &Scene)> {
+        self.scenes
+            .iter()
+            .map(|(id, resident)| (id, &resident.scene))
+    }
+
+    /// Iterate over all the resident scenes mutably.
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = (SceneId, &mut Scene)> {
+        self.scenes
+            .iter_mut()
+            .map(|(id, resident)| (id, &mut resident.scene))
+    }
+
+    /// Iterate over the resident scenes whose targets pass the given filter.
+    pub fn targeted(
+        &self,
+        filter: impl Fn(&SceneTargets) -> bool,
+    ) -> impl Iterator<Item = (SceneId, &Scene)> {
+        self.scenes
+            .iter()
+            .filter(move |(_, resident)| filter(&resident.targets))
+            .map(|(id, resident)| (id, &resident.scene))
+    }
+
+    /// Iterate mutably over the resident scenes whose targets pass the given filter.
+    pub fn targeted_mut(
+        &mut self,
+        filter: impl Fn(&SceneTargets) -> bool,
+    ) -> impl Iterator<Item = (SceneId, &mut Scene)> {
+        self.scenes
+            .iter_mut()
+            .filter(move |(_, resident)| filter(&resident.targets))
+            .map(|(id, resident)| (id, &mut resident.scene))
+    }
+
+    /// Get the number of times a resident scene was swapped with the main scene.
+    /// Systems that keep per scene state (like physics worlds) use this to make their state follow the swapped scenes.
+    pub fn swaps(&self, id: SceneId) -> Option<u64> {
+        self.scenes.get(id).map(|resident| resident.swaps)
+    }
+
+    /// Swap the contents of the main scene with a resident scene.
+    /// This is useful to promote a level that was populated in the background.
+    pub fn swap(&mut self, id: SceneId, main: &mut Scene) -> Option<()> {
+        let resident = self.scenes.get_mut(id)?;
+        std::mem::swap(&mut resident.scene, main);
+        resident.swaps += 1;
+        log::debug!("Swapped main scene with resident scene '{}'", resident.name);
+        Some(())
+    }
+}
+
+impl Scene {
+    /// Move an entity and all of its components into another scene.
+    /// Returns the new entity handle within the target scene, or None if the entity does not exist.
+    /// The moved components are marked as added within the target scene.
+    /// Entity handles stored inside components (hierarchy links for example) are NOT remapped.
+    pub fn transfer(&mut self, entity: Entity, target: &mut Scene) -> Option<Entity> {
+        let linkings = *self.entities.get(entity)?;
+        let index = linkings.index;
+        let current = self.archetypes.get_mut(&linkings.mask).unwrap();
+
+        // Create the target archetype if needed
+        let output = target.archetypes.entry(linkings.mask).or_insert_with(|| {
+            let columns = current
+                .table()
+                .iter()
+                .map(|(mask, column)| (*mask, column.clone_default()));
+            Archetype::from_table(linkings.mask, Table::from_iter(columns))
+        });
+
+        // Move the components from one scene to the other
+        let flags = StateFlags {
+            added: true,
+            modified: true,
+        };
+        for (mask, input) in current.table_mut().iter_mut() {
+            let column = output.table_mut().get_mut(mask).unwrap();
+            input
+                .components_mut()
+                .swap_remove_move(index, column.components_mut());
+            input.delta_frame_states_mut().swap_remove(index);
+            input.delta_tick_states_mut().swap_remove(index);
+            column.delta_frame_states_mut().extend_with_flags(1, flags);
+            column.delta_tick_states_mut().extend_with_flags(1, flags);
+        }
+
+        // Handle swap-remove logic in the current archetype
+        let entities = current.entities_mut();
+        entities.swap_remove(index);
+        if let Some(swapped) = entities.get(index).cloned() {
+            self.entities[swapped].index = index;
+        }
+        self.entities.remove(entity);
+
+        // Disabled entities stay disabled within the target scene
+        if !linkings.enabled {
+            self.disabled -= 1;
+            target.disabled += 1;
+        }
+
+        // Insert the entity in the target scene
+        let moved = target.entities.insert(EntityLinkings {
+            mask: linkings.mask,
+            index: output.entities().len(),
+            enabled: linkings.enabled,
+        });
+        output.entities_mut().push(moved);
+        Some(moved)
+    }
+
+    /// Move a batch of entities into another scene and return their new entity handles.
+    /// Panics if ANY entity ID is invalid.
+    pub fn transfer_from_iter(
+        &mut self,
+        iter: impl IntoIterator<Item = Entity>,
+        target: &mut Scene,
+    ) -> Vec<Entity> {
+        iter.into_iter()
+            .map(|entity| self.transfer(entity, target).unwrap())
+            .collect()
+    }
+}
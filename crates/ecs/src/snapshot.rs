@@ -0,0 +1,337 @@
+use crate::{
+    mask, Archetype, Component, Entity, EntityLinkings, EntitySet, Mask, MaskHashMap, Scene,
+    StateFlags, Table, UntypedColumn, UntypedVec,
+};
+use ahash::AHashMap;
+use lazy_static::lazy_static;
+use parking_lot::RwLock;
+
+// Function that clones all the elements of the first untyped vec and pushes them into the second one
+// This assumes that both untyped vecs contain the same component type
+type ExtendClonedFn = fn(&dyn UntypedVec, &mut dyn UntypedVec);
+
+// Registered snapshottable components
+lazy_static! {
+    static ref CLONERS: RwLock<MaskHashMap<ExtendClonedFn>> = RwLock::new(MaskHashMap::default());
+}
+
+/// Register a component so it can be cloned whenever we take a [snapshot](SceneSnapshot) of a scene.
+/// Components that are not registered will be stripped from the entities stored in the snapshot.
+pub fn register_snapshottable<T: Component + Clone>() {
+    fn extend_cloned<T: Component + Clone>(input: &dyn UntypedVec, output: &mut dyn UntypedVec) {
+        let input = input.as_any().downcast_ref::<Vec<T>>().unwrap();
+        let output = output.as_any_mut().downcast_mut::<Vec<T>>().unwrap();
+        output.extend_from_slice(input);
+    }
+
+    let mask = mask::<T>();
+    CLONERS.write().insert(mask, extend_cloned::<T>);
+    log::debug!(
+        "Registered component '{}' as snapshottable",
+        utils::pretty_type_name::<T>()
+    );
+}
+
+/// Check if the given component mask only contains snapshottable components.
+pub fn is_snapshottable(mask: Mask) -> bool {
+    let cloners = CLONERS.read();
+    mask.units().all(|unit| cloners.contains_key(&unit))
+}
+
+// A single archetype stored within the snapshot
+struct SnapshotArchetype {
+    entities: Vec<Entity>,
+    columns: MaskHashMap<Box<dyn UntypedVec>>,
+}
+
+/// A snapshot contains a cloned copy of all the entities and snapshottable components of a [Scene].
+/// Snapshots can be restored later on using [Scene::restore], and entity handles stay the same after a restore.
+///
+/// Components that were not registered using [register_snapshottable] are stripped from the snapshot,
+/// meaning that the entities will be restored without them (the entities themselves are still kept).
+pub struct SceneSnapshot {
+    entities: EntitySet,
+    archetypes: MaskHashMap<SnapshotArchetype>,
+    skipped: Mask,
+}
+
+impl SceneSnapshot {
+    /// Get the number of entities stored within the snapshot.
+    pub fn len(&self) -> usize {
+        self.entities.len()
+    }
+
+    /// Check if the snapshot contains no entities.
+    pub fn is_empty(&self) -> bool {
+        self.entities.is_empty()
+    }
+
+    /// Check if an entity is stored within the snapshot.
+    pub fn contains(&self, entity: Entity) -> bool {
+        self.entities.contains_key(entity)
+    }
+
+    /// Get the mask of the entity at the time of the snapshot (without the stripped components).
+    pub fn mask(&self, entity: Entity) -> Option<Mask> {
+        self.entities.get(entity).map(|linkings| linkings.mask)
+    }
+
+    /// Get the combined mask of all the components that were stripped because they were not snapshottable.
+    pub fn skipped(&self) -> Mask {
+        self.skipped
+    }
+
+    /// Try to get an immutable reference to a component of an entity stored within the snapshot.
+    pub fn get<T: Component>(&self, entity: Entity) -> Option<&T> {
+        let linkings = self.entities.get(entity)?;
+        let archetype = self.archetypes.get(&linkings.mask)?;
+        let column = archetype.columns.get(&mask::<T>())?;
+        let vec = column.as_any().downcast_ref::<Vec<T>>()?;
+        vec.get(linkings.index)
+    }
+
+    /// Compare this snapshot with a newer one and return the entities that were spawned, despawned, or that changed layout.
+    pub fn diff(&self, newer: &SceneSnapshot) -> SnapshotDiff {
+        let spawned = newer
+            .entities
+            .keys()
+            .filter(|entity| !self.entities.contains_key(*entity))
+            .collect();
+
+        let despawned = self
+            .entities
+            .keys()
+            .filter(|entity| !newer.entities.contains_key(*entity))
+            .collect();
+
+        let changed = self
+            .entities
+            .iter()
+            .filter_map(|(entity, old)| {
+                let new = newer.entities.get(entity)?;
+                (old.mask != new.mask).then_some((entity, old.mask, new.mask))
+            })
+            .collect();
+
+        SnapshotDiff {
+            spawned,
+            despawned,
+            changed,
+        }
+    }
+}
+
+/// The difference between two [snapshots](SceneSnapshot) at the entity level.
+#[derive(Default, Debug, Clone)]
+pub struct SnapshotDiff {
+    /// Entities that only exist within the newer snapshot.
+    pub spawned: Vec<Entity>,
+
+    /// Entities that only exist within the older snapshot.
+    pub despawned: Vec<Entity>,
+
+    /// Entities that exist in both snapshots but whose layout changed (old mask, new mask).
+    pub changed: Vec<(Entity, Mask, Mask)>,
+}
+
+impl SnapshotDiff {
+    /// Check if the two snapshots contain the same entities with the same layouts.
+    pub fn is_empty(&self) -> bool {
+        self.spawned.is_empty() && self.despawned.is_empty() && self.changed.is_empty()
+    }
+}
+
+impl Scene {
+    /// Take a snapshot of all the entities and their snapshottable components.
+    /// Components that were not registered using [register_snapshottable] will be stripped from the snapshot.
+    pub fn snapshot(&self) -> SceneSnapshot {
+        let cloners = CLONERS.read();
+        let mut entities = self.entities.clone();
+        let mut archetypes = MaskHashMap::<SnapshotArchetype>::default();
+        let mut skipped = Mask::zero();
+
+        for (&mask, archetype) in self.archetypes.iter() {
+            // Strip the components that we cannot clone
+            let stripped = mask
+                .units()
+                .filter(|unit| !cloners.contains_key(unit))
+                .fold(Mask::zero(), |a, b| a | b);
+            skipped = skipped | stripped;
+            let target = mask & !stripped;
+
+            if archetype.is_empty() {
+                continue;
+            }
+
+            // Multiple archetypes might get merged into the same stripped one
+            let output = archetypes.entry(target).or_insert_with(|| SnapshotArchetype {
+                entities: Vec::new(),
+                columns: archetype
+                    .table()
+                    .iter()
+                    .filter(|(unit, _)| target.contains(**unit))
+                    .map(|(unit, column)| (*unit, column.components().clone_default()))
+                    .collect(),
+            });
+
+            // Update the linkings of the cloned entities to point to the stripped archetype
+            let offset = output.entities.len();
+            for (i, entity) in archetype.entities().iter().enumerate() {
+                let linkings = &mut entities[*entity];
+                linkings.mask = target;
+                linkings.index = offset + i;
+            }
+            output.entities.extend_from_slice(archetype.entities());
+
+            // Clone the component data into the snapshot
+            for (unit, column) in output.columns.iter_mut() {
+                let input = archetype.table()[unit].components();
+                cloners[unit](input, &mut **column);
+            }
+        }
+
+        if !skipped.is_zero() {
+            let names = skipped.units().filter_map(crate::name).collect::<Vec<_>>();
+            log::warn!("Stripped non-snapshottable components {names:?} from scene snapshot");
+        }
+
+        SceneSnapshot {
+            entities,
+            archetypes,
+            skipped,
+        }
+    }
+
+    /// Restore the scene to the state of a previously taken snapshot.
+    /// Entities that were spawned after the snapshot was taken are despawned, and entity handles from the snapshot stay valid.
+    /// All the restored components are marked as modified so change detection filters pick them up.
+    /// Components that did not exist right before the restore (like the ones of despawned entities) are marked as added as well.
+    /// Entity handles that were spawned after the snapshot was taken stay invalid, even once their slots get reused.
+    pub fn restore(&mut self, snapshot: &SceneSnapshot) {
+        let cloners = CLONERS.read();
+
+        // Clear all the current entities (we keep the archetypes around since they might be reused)
+        for (_, archetype) in self.archetypes.iter_mut() {
+            archetype.clear();
+        }
+
+        // This will also restore the generations of the entity handles
+        let previous = std::mem::replace(&mut self.entities, snapshot.entities.clone());
+        bump_versions(&mut self.entities, &previous);
+        self.disabled = self.entities.values().filter(|l| !l.enabled).count();
+
+        for (&mask, input) in snapshot.archetypes.iter() {
+            let archetype = self.archetypes.entry(mask).or_insert_with(|| {
+                let columns = input
+                    .columns
+                    .iter()
+                    .map(|(unit, vec)| (*unit, UntypedColumn::new(vec.clone_default())));
+                Archetype::from_table(mask, Table::from_iter(columns))
+            });
+
+            let flags = StateFlags {
+                added: false,
+                modified: true,
+            };
+
+            let count = input.entities.len();
+            for (unit, column) in archetype.table_mut().iter_mut() {
+                cloners[unit](&*input.columns[unit], column.components_mut());
+                column.delta_frame_states_mut().extend_with_flags(count, flags);
+                column.delta_tick_states_mut().extend_with_flags(count, flags);
+
+                // Components that the entities did not have before the restore are re-created
+                for (i, entity) in input.entities.iter().enumerate() {
+                    let existed = previous
+                        .get(*entity)
+                        .is_some_and(|linkings| linkings.mask.contains(*unit));
+
+                    if !existed {
+                        let added = |flags: &mut StateFlags| flags.added = true;
+                        column.delta_frame_states_mut().update(i, added);
+                        column.delta_tick_states_mut().update(i, added);
+                    }
+                }
+            }
+
+            archetype.entities_mut().extend_from_slice(&input.entities);
+        }
+
+        // Make sure the unit archetype always exists
+        self.archetypes
+            .entry(Mask::zero())
+            .or_insert_with(|| Archetype::from_table(Mask::zero(), Table::default()));
+
+        log::debug!("Restored scene snapshot with {} entities", snapshot.len());
+    }
+}
+
+// Split a raw entity handle into its slot index and version
+fn slot(entity: Entity) -> (u32, u32) {
+    let raw = entity.to_raw();
+    (raw as u32, (raw >> 32) as u32)
+}
+
+// Placeholder linkings used to occupy free slots
+const DUMMY: EntityLinkings = EntityLinkings {
+    mask: Mask::zero(),
+    index: 0,
+    enabled: true,
+};
+
+// Occupy every free slot of the entity set (and create new slots up to the given index)
+// New slots start at version 1, so the first one of them tells us that the free slots were all used up
+fn occupy(entities: &mut EntitySet, last: u32) -> Vec<Entity> {
+    let mut dummies = Vec::new();
+    loop {
+        let entity = entities.insert(DUMMY);
+        dummies.push(entity);
+        let (index, version) = slot(entity);
+
+        if version == 1 && index >= last {
+            return dummies;
+        }
+    }
+}
+
+// Make sure that the slots that are free within the restored entities have a newer version than
+// any handle that used them before the restore. Otherwise stale handles would alias new entities
+fn bump_versions(entities: &mut EntitySet, previous: &EntitySet) {
+    // Get the latest version of every slot, including the free ones (inserting into a free slot bumps its version by one)
+    let mut probe = previous.clone();
+    let mut versions = previous.keys().map(slot).collect::<AHashMap<u32, u32>>();
+    versions.extend(
+        occupy(&mut probe, 0)
+            .into_iter()
+            .map(slot)
+            .filter(|(_, version)| *version > 1)
+            .map(|(index, version)| (index, version - 1)),
+    );
+
+    // Slots that were occupied within the snapshot must keep their versions
+    for entity in entities.keys() {
+        versions.remove(&slot(entity).0);
+    }
+
+    let Some(last) = versions.keys().copied().max() else {
+        return;
+    };
+
+    // Removing a slot puts it at the head of the free list, so the next insertion reuses it with a newer version
+    let mut dummies = occupy(entities, last);
+    for entity in dummies.iter_mut() {
+        let (index, _) = slot(*entity);
+        let Some(&version) = versions.get(&index) else {
+            continue;
+        };
+
+        while slot(*entity).1 < version {
+            entities.remove(*entity);
+            *entity = entities.insert(DUMMY);
+        }
+    }
+
+    for entity in dummies {
+        entities.remove(entity);
+    }
+}
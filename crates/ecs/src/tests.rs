@@ -455,4 +455,203 @@ mod tests {
         assert_eq!(query.into_iter().count(), 0);
     }
     */
+
+    #[test]
+    fn snapshot_restore() {
+        register_snapshottable::<Name>();
+        register_snapshottable::<Health>();
+
+        let mut scene = Scene::default();
+        let e1 = scene.insert((Name("Player"), Health(100)));
+        let e2 = scene.insert(Health(50));
+        let snapshot = scene.snapshot();
+        assert_eq!(snapshot.len(), 2);
+
+        scene.entry_mut(e1).unwrap().get_mut::<Health>().unwrap().0 = 0;
+        scene.remove(e2);
+        let e3 = scene.insert(Name("Enemy"));
+        assert!(!scene.contains(e2));
+
+        scene.restore(&snapshot);
+        assert!(scene.contains(e1));
+        assert!(scene.contains(e2));
+        assert!(!scene.contains(e3));
+        assert_eq!(scene.entry(e1).unwrap().get::<Health>(), Some(&Health(100)));
+        assert_eq!(
+            scene.entry(e1).unwrap().get::<Name>(),
+            Some(&Name("Player"))
+        );
+        assert_eq!(scene.entry(e2).unwrap().get::<Health>(), Some(&Health(50)));
+        assert_eq!(scene.query::<&Health>().into_iter().count(), 2);
+
+        let diff = snapshot.diff(&scene.snapshot());
+        assert!(diff.is_empty());
+    }
+
+    #[test]
+    fn snapshot_stale_handles() {
+        register_snapshottable::<Health>();
+
+        let mut scene = Scene::default();
+        let e1 = scene.insert(Health(100));
+        let snapshot = scene.snapshot();
+
+        // One entity that is still alive and one that was already despawned before the restore
+        let e2 = scene.insert(Health(50));
+        let e3 = scene.insert(Health(25));
+        scene.remove(e3);
+
+        scene.restore(&snapshot);
+        assert!(scene.contains(e1));
+        assert!(!scene.contains(e2));
+        assert!(!scene.contains(e3));
+
+        // New entities reuse the slots, but the old handles must stay invalid
+        let spawned = (0..8).map(|i| scene.insert(Health(i))).collect::<Vec<_>>();
+        assert!(!spawned.contains(&e2));
+        assert!(!spawned.contains(&e3));
+        assert!(!scene.contains(e2));
+        assert!(!scene.contains(e3));
+        assert_eq!(scene.query::<&Health>().into_iter().count(), 9);
+    }
+
+    #[test]
+    fn snapshot_restore_added() {
+        register_snapshottable::<Name>();
+        register_snapshottable::<Health>();
+
+        let mut scene = Scene::default();
+        let e1 = scene.insert((Name("Player"), Health(100)));
+        let e2 = scene.insert(Health(50));
+        let snapshot = scene.snapshot();
+
+        scene.remove(e2);
+        cleanup(&mut scene);
+        scene.restore(&snapshot);
+
+        // Only the entity that had to be re-created is added, but both are modified
+        let added = scene.query_with::<&Entity>(added::<&Health>());
+        assert_eq!(added.into_iter().copied().collect::<Vec<_>>(), vec![e2]);
+        let modified = scene.query_with::<&Entity>(modified::<&Health>());
+        assert_eq!(modified.len(), 2);
+        assert!(scene.contains(e1));
+    }
+
+    #[test]
+    fn snapshot_strips_components() {
+        register_snapshottable::<Health>();
+
+        let mut scene = Scene::default();
+        let e1 = scene.insert((Health(100), Placeholder()));
+        let e2 = scene.insert(Health(50));
+        let snapshot = scene.snapshot();
+        assert_eq!(snapshot.skipped(), crate::mask::<Placeholder>());
+        assert_eq!(snapshot.mask(e1), Some(crate::mask::<Health>()));
+        assert_eq!(snapshot.get::<Health>(e1), Some(&Health(100)));
+
+        scene.restore(&snapshot);
+        let entry = scene.entry(e1).unwrap();
+        assert!(entry.get::<Placeholder>().is_none());
+        assert_eq!(entry.get::<Health>(), Some(&Health(100)));
+        assert_eq!(scene.entry(e2).unwrap().get::<Health>(), Some(&Health(50)));
+
+        let e3 = scene.insert(Health(10));
+        let diff = snapshot.diff(&scene.snapshot());
+        assert_eq!(diff.spawned, vec![e3]);
+        assert!(diff.despawned.is_empty());
+    }
+
+    #[test]
+    fn transfer() {
+        let mut main = Scene::default();
+        let mut scenes = Scenes::default();
+        let id = scenes.insert("loading", Scene::default(), SceneTargets::default());
+
+        let e1 = main.insert((Name("Moved"), Health(10)));
+        let e2 = main.insert((Name("Stays"), Health(20)));
+
+        let loading = scenes.get_mut(id).unwrap();
+        let moved = main.transfer(e1, loading).unwrap();
+        assert!(!main.contains(e1));
+        assert_eq!(main.entry(e2).unwrap().get::<Health>(), Some(&Health(20)));
+
+        let entry = loading.entry(moved).unwrap();
+        assert_eq!(entry.get::<Name>(), Some(&Name("Moved")));
+        assert_eq!(entry.get::<Health>(), Some(&Health(10)));
+        assert_eq!(loading.query::<&Health>().into_iter().count(), 1);
+
+        assert_eq!(scenes.swaps(id), Some(0));
+        scenes.swap(id, &mut main).unwrap();
+        assert!(main.contains(moved));
+        assert_eq!(scenes.find("loading"), Some(id));
+        assert_eq!(scenes.swaps(id), Some(1));
+    }
+
+    #[test]
+    fn query_state() {
+        let mut scene = Scene::default();
+        let mut state = QueryState::<&Health>::new();
+        scene.insert((Name("A"), Health(1)));
+        assert_eq!(state.iter(&scene).count(), 1);
+        assert_eq!(state.matched().len(), 1);
+
+        // New archetypes must be picked up by the cached state
+        scene.insert((Health(2), Ammo(5)));
+        scene.insert(Ammo(3));
+        assert_eq!(state.iter(&scene).count(), 2);
+        assert_eq!(state.matched().len(), 2);
+
+        let mut state = QueryState::<&mut Health>::new();
+        for health in state.iter_mut(&mut scene) {
+            health.0 += 10;
+        }
+        let filter = contains::<&Ammo>();
+        let mut state = QueryState::<&Health>::new();
+        let query = state.query_with(&scene, filter);
+        assert_eq!(query.into_iter().collect::<Vec<_>>(), vec![&Health(12)]);
+
+        // Using the state with another scene must discard the cache
+        let other = Scene::default();
+        assert_eq!(state.iter(&other).count(), 0);
+        assert!(state.matched().is_empty());
+    }
+
+    #[test]
+    fn enable_disable() {
+        let mut scene = Scene::default();
+        let entities = scene
+            .extend_from_iter((0..100).map(|i| (Health(i), Ammo(0))))
+            .to_vec();
+        scene.set_enabled(entities[3], false).unwrap();
+        scene.set_enabled(entities[70], false).unwrap();
+        assert_eq!(scene.is_enabled(entities[3]), Some(false));
+        assert_eq!(scene.query::<&Health>().into_iter().count(), 98);
+        assert!(scene
+            .query::<&Health>()
+            .into_iter()
+            .all(|h| h.0 != 3 && h.0 != 70));
+
+        for ammo in scene.query_mut::<&mut Ammo>() {
+            ammo.0 += 1;
+        }
+        let entry = scene.entry(entities[3]).unwrap();
+        assert_eq!(entry.get::<Ammo>(), Some(&Ammo(0)));
+
+        let filter = include_disabled();
+        assert_eq!(scene.query_with::<&Health>(filter).into_iter().count(), 100);
+        let filter = contains::<&Ammo>();
+        assert_eq!(scene.query_with::<&Health>(filter).into_iter().count(), 98);
+
+        // Including the disabled entities must not change what the other filters match
+        let filter = include_disabled() | contains::<&Name>();
+        assert_eq!(scene.query_with::<&Health>(filter).into_iter().count(), 0);
+        let filter = contains::<&Ammo>() & include_disabled();
+        assert_eq!(scene.query_with::<&Health>(filter).into_iter().count(), 100);
+        let filter = !include_disabled();
+        assert_eq!(scene.query_with::<&Health>(filter).into_iter().count(), 100);
+
+        scene.remove(entities[70]);
+        scene.set_enabled(entities[3], true).unwrap();
+        assert_eq!(scene.query::<&Health>().into_iter().count(), 99);
+    }
 }
@@ -1,4 +1,4 @@
-use crate::{ColorTexel, Normalized, Texel, R, RG, RGBA, SRGBA, UBC1, UBC2, UBC3, UBC7};
+use crate::{ColorTexel, Normalized, Texel, R, RG, RGBA, SRGBA, UBC1, UBC3};
 
 // Image texels are texels that can be loaded from a file, like when loading a Texture2D<RGBA<Normalized<u8>>
 pub trait ImageTexel: Texel + ColorTexel {
@@ -37,25 +37,47 @@ macro_rules! impl_image_texel {
     };
 }
 
-/*
-macro_rules! impl_compressed_image_texels_rgba_variants {
-    ($t:ty) => {
-        internal_impl_single_image_texel!(
-            RGBA,
-            $t,
-            into_rgba8,
-            vek::Vec4::from_slice
-        );
+// Internally used for implementing the block compressed image texels
+// Texels are compressed using tbc, and every 4 bytes of a compressed block are stored within a single texel
+macro_rules! internal_impl_compressed_image_texel {
+    ($t:ident, $base:ty, $encode:path) => {
+        impl ImageTexel for $t<$base> {
+            fn dyn_image_to_texels(image: image::DynamicImage) -> Option<Vec<Self::Storage>> {
+                let width = image.width() as usize;
+                let height = image.height() as usize;
+
+                // Block compression requires the image to only contain whole 4x4 blocks
+                if width % 4 != 0 || height % 4 != 0 {
+                    return None;
+                }
+
+                let image = image.into_rgba8();
+                let pixels = image
+                    .chunks(4)
+                    .map(|rgba| tbc::color::Rgba8 {
+                        r: rgba[0],
+                        g: rgba[1],
+                        b: rgba[2],
+                        a: rgba[3],
+                    })
+                    .collect::<Vec<_>>();
+                let blocks = $encode(&pixels, width, height);
+                Some(blocks.chunks(4).map(vek::Vec4::from_slice).collect())
+            }
+
+            fn hdr_image_to_texels(_: hdrldr::Image) -> Option<Vec<Self::Storage>> {
+                None
+            }
+        }
+    };
+}
 
-        internal_impl_single_image_texel!(
-            SRGBA,
-            $t,
-            into_rgba8,
-            vek::Vec4::from_slice
-        );
+macro_rules! impl_compressed_image_texels_rgba_variants {
+    ($t:ty, $encode:path) => {
+        internal_impl_compressed_image_texel!(RGBA, $t, $encode);
+        internal_impl_compressed_image_texel!(SRGBA, $t, $encode);
     };
 }
-*/
 
 impl_image_texel!(R, |val| val[0]);
 impl_image_texel!(RG, vek::Vec2::from_slice);
@@ -63,9 +85,6 @@ impl_image_texel!(RGBA, vek::Vec4::from_slice);
 
 internal_impl_single_image_texel!(SRGBA, Normalized<u8>, into_rgba8, vek::Vec4::from_slice);
 
-/*
-impl_compressed_image_texels_rgba_variants!(Normalized<UBC1>);
-impl_compressed_image_texels_rgba_variants!(Normalized<UBC2>);
-impl_compressed_image_texels_rgba_variants!(Normalized<UBC3>);
-impl_compressed_image_texels_rgba_variants!(Normalized<UBC7>);
-*/
+// tbc does not implement BC2 and BC7 encoders
+impl_compressed_image_texels_rgba_variants!(Normalized<UBC1>, tbc::encode_image_bc1_conv_u8);
+impl_compressed_image_texels_rgba_variants!(Normalized<UBC3>, tbc::encode_image_bc3_conv_u8);
@@ -5,7 +5,7 @@ use crate::{
     TexelInfo, Texture, TextureViewDimension, VertexModule,
 };
 use ahash::{AHashMap, AHashSet};
-use assets::Assets;
+use assets::{Assets, ImportKey};
 use itertools::Itertools;
 use parking_lot::Mutex;
 use snailquote::unescape;
@@ -266,28 +266,30 @@ fn compile(
 
     // Compile SPIRV if it was not in cache already
     let key = (source.clone(), defines.clone(), snippets.clone());
-    let cached = graphics.0.cached.spirvs.get(&key);
-    let spirv = if cached.is_none() {
-        Some(compile_spirv(
-            path, source, defines, snippets, assets, graphics, kind, optimize,
-        )?)
-    } else {
-        None
-    };
+    let cached = graphics.0.cached.spirvs.get(&key).map(|x| x.clone());
+
+    // Try to reuse the SPIRV that was compiled in a previous run
+    let import = ImportKey::new(
+        "spirv",
+        source.as_bytes(),
+        &(defines, snippets, kind, optimize),
+    );
+    let cached = cached.or_else(|| load_cached_spirv(assets, import, path));
 
     // Fetch cached SPIRV binary if it was already compiled
-    let mut spirv = spirv
-        .as_ref()
-        .map(|x| x.as_binary())
-        .unwrap_or_else(|| &cached.as_ref().unwrap())
-        .to_vec();
+    let mut spirv = match cached {
+        Some(spirv) => spirv,
+        None => {
+            let artifact = compile_spirv(
+                path, source, defines, snippets, assets, graphics, kind, optimize,
+            )?;
+            let spirv = artifact.as_binary().to_vec();
+            store_cached_spirv(assets, import, path, &spirv);
+            spirv
+        }
+    };
     let before = spirv.clone();
 
-    // Cache the SPIRV into the shader cache if needed
-    if cached.is_none() {
-        //graphics.0.cached.spirvs.insert(key, spirv.clone());
-    }
-
     // Parse the spirv manually to be able to handle specialization constants
     specialize_spec_constants(&mut spirv, &constants);
 
@@ -327,6 +329,111 @@ fn compile(
     Ok((raw, reflected))
 }
 
+// Hash the source code of an included function module to detect modifications
+fn hash_source(source: &str) -> u64 {
+    let mut hasher = assets::stable_hasher();
+    source.hash(&mut hasher);
+    hasher.finish()
+}
+
+// Store compiled SPIRV in the import cache, alongside all the files that it included (recursively)
+// Layout: include count, (parent, include, source hash) for every include, then the SPIRV words
+fn store_cached_spirv(assets: &Assets, key: ImportKey, path: &Path, spirv: &[u32]) {
+    let mut edges = Vec::<(PathBuf, PathBuf, u64)>::new();
+    let mut visited = AHashSet::<PathBuf>::new();
+    let mut pending = vec![path.to_path_buf()];
+
+    while let Some(parent) = pending.pop() {
+        let Some(str) = parent.to_str() else {
+            log::warn!("Could not cache the SPIRV of shader {path:?}, include {parent:?} is not a valid path");
+            return;
+        };
+
+        for child in assets.dependencies(str) {
+            let Some(module) = child
+                .to_str()
+                .and_then(|child| assets.load::<FunctionModule>(child).ok())
+            else {
+                log::warn!("Could not cache the SPIRV of shader {path:?}, failed to load include {child:?}");
+                return;
+            };
+
+            edges.push((parent.clone(), child.clone(), hash_source(&module.source)));
+            if visited.insert(child.clone()) {
+                pending.push(child);
+            }
+        }
+    }
+
+    let mut bytes = Vec::<u8>::new();
+    bytes.extend_from_slice(&(edges.len() as u32).to_le_bytes());
+    for (parent, child, hash) in edges {
+        for path in [parent, child] {
+            let path = path.to_string_lossy();
+            bytes.extend_from_slice(&(path.len() as u32).to_le_bytes());
+            bytes.extend_from_slice(path.as_bytes());
+        }
+        bytes.extend_from_slice(&hash.to_le_bytes());
+    }
+    bytes.extend_from_slice(bytemuck::cast_slice(spirv));
+    assets.store_import(key, &bytes);
+}
+
+// Load SPIRV that was compiled in a previous run from the import cache
+// The SPIRV is only reused if none of its included files were modified since then
+fn load_cached_spirv(assets: &Assets, key: ImportKey, path: &Path) -> Option<Vec<u32>> {
+    fn take<'a>(bytes: &mut &'a [u8], count: usize) -> Option<&'a [u8]> {
+        let taken = bytes.get(..count)?;
+        *bytes = &bytes[count..];
+        Some(taken)
+    }
+
+    fn take_u32(bytes: &mut &[u8]) -> Option<u32> {
+        Some(u32::from_le_bytes(take(bytes, 4)?.try_into().unwrap()))
+    }
+
+    fn take_path(bytes: &mut &[u8]) -> Option<String> {
+        let length = take_u32(bytes)? as usize;
+        String::from_utf8(take(bytes, length)?.to_vec()).ok()
+    }
+
+    let cached = assets.cached_import(key)?;
+    let bytes = &mut cached.as_slice();
+    let count = take_u32(bytes)?;
+    let mut edges = Vec::with_capacity(count as usize);
+
+    for _ in 0..count {
+        let parent = take_path(bytes)?;
+        let child = take_path(bytes)?;
+        let hash = u64::from_le_bytes(take(bytes, 8)?.try_into().unwrap());
+
+        let module = assets.load::<FunctionModule>(child.as_str()).ok()?;
+        if hash_source(&module.source) != hash {
+            log::debug!("Included file {child} was modified, recompiling {path:?}");
+            return None;
+        }
+
+        edges.push((parent, child));
+    }
+
+    if bytes.len() % 4 != 0 {
+        return None;
+    }
+
+    // Includes are not resolved when we skip compilation, so record the dependencies manually
+    for (parent, child) in edges {
+        assets.add_dependency(parent, child);
+    }
+
+    log::debug!("Found compiled SPIRV in the import cache for {path:?}");
+    Some(
+        bytes
+            .chunks_exact(4)
+            .map(|word| u32::from_le_bytes(word.try_into().unwrap()))
+            .collect(),
+    )
+}
+
 // Force the compilation of SPIRV code
 // Only gets executed if the SPIRV was not cached in the shader cache
 fn compile_spirv(
@@ -371,6 +478,13 @@ fn compile_spirv(
 
     let included = Included::default();
     options.set_include_callback(move |target, _type, current, depth| {
+        // Record the included function modules as dependencies so they can be hot-reloaded
+        // Includes from the root module use its file name instead of its asset path
+        if matches!(_type, shaderc::IncludeType::Standard) {
+            let parent = if current == file { path } else { Path::new(current) };
+            assets.add_dependency(parent, target);
+        }
+
         include(current, _type, target, depth, assets, &snippets, &included)
     });
 
@@ -1,6 +1,6 @@
 use std::path::{Path, PathBuf};
 
-use assets::Asset;
+use assets::{Asset, Reload};
 
 // The type of shader module that the shader source represent
 #[derive(Clone, Copy, Hash, PartialEq, Eq, Debug)]
@@ -156,6 +156,8 @@ macro_rules! impl_asset_for_module {
                 Ok(Self { source, path })
             }
         }
+
+        impl Reload for $t {}
     };
 }
 
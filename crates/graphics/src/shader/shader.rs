@@ -2,6 +2,7 @@ use crate::{
     Compiled, Compiler, ComputeModule, FragmentModule, Graphics, ReflectedShader,
     ShaderCompilationError, ShaderError, VertexModule,
 };
+use assets::Assets;
 use std::sync::Arc;
 
 // A rendering shader that contains a vertex module and fragment module
@@ -50,6 +51,39 @@ impl Shader {
         })
     }
 
+    // Check if the file of any of the modules changed since the last frame
+    // Modified include files are propagated to the modules that include them
+    pub fn is_changed(&self, assets: &Assets) -> bool {
+        [self.vertex.path(), self.fragment.path()]
+            .into_iter()
+            .filter_map(|path| path.to_str())
+            .any(|path| assets.is_changed(path))
+    }
+
+    // Recompile the shader in place if the file of any of its modules changed since the last frame
+    // The compiler must contain the same snippets, defines, and resources that were used to create the shader
+    // Returns None if nothing changed. The old shader is kept if recompilation fails
+    pub fn reload(&mut self, compiler: &Compiler) -> Option<Result<(), ShaderError>> {
+        if !self.is_changed(compiler.assets) {
+            return None;
+        }
+
+        let vertex = self.vertex.path().to_str()?.to_owned();
+        let fragment = self.fragment.path().to_str()?.to_owned();
+
+        let vertex = compiler.assets.load::<VertexModule>(&vertex);
+        let fragment = compiler.assets.load::<FragmentModule>(&fragment);
+        let (vertex, fragment) = match (vertex, fragment) {
+            (Ok(vertex), Ok(fragment)) => (vertex, fragment),
+            (Err(err), _) | (_, Err(err)) => {
+                log::error!("Could not reload shader modules: {err}");
+                return None;
+            }
+        };
+
+        Some(Self::new(vertex, fragment, compiler).map(|shader| *self = shader))
+    }
+
     // Get the vertex module
     pub fn vertex(&self) -> &Compiled<VertexModule> {
         &self.vertex
@@ -115,6 +149,36 @@ impl ComputeShader {
         })
     }
 
+    // Check if the file of the module changed since the last frame
+    // Modified include files are propagated to the modules that include them
+    pub fn is_changed(&self, assets: &Assets) -> bool {
+        self.compiled
+            .path()
+            .to_str()
+            .is_some_and(|path| assets.is_changed(path))
+    }
+
+    // Recompile the compute shader in place if the file of its module changed since the last frame
+    // The compiler must contain the same snippets, defines, and resources that were used to create the shader
+    // Returns None if nothing changed. The old shader is kept if recompilation fails
+    pub fn reload(&mut self, compiler: &Compiler) -> Option<Result<(), ShaderError>> {
+        if !self.is_changed(compiler.assets) {
+            return None;
+        }
+
+        let path = self.compiled.path().to_str()?.to_owned();
+
+        let module = match compiler.assets.load::<ComputeModule>(&path) {
+            Ok(module) => module,
+            Err(err) => {
+                log::error!("Could not reload compute module {path:?}: {err}");
+                return None;
+            }
+        };
+
+        Some(Self::new(module, compiler).map(|shader| *self = shader))
+    }
+
     // Get the compute module
     pub fn compute(&self) -> &Compiled<ComputeModule> {
         &self.compiled
@@ -374,3 +374,35 @@ mod region {
         assert_eq!(levels(2048), 12);
     }
 }
+
+#[cfg(test)]
+mod import {
+    use crate::{
+        import_mip_maps, Normalized, RawTexels, TextureImportSettings, TextureScale, RGBA, UBC1,
+    };
+    use assets::{Asset, Data};
+    use std::{path::Path, sync::Arc};
+
+    #[test]
+    fn compressed_default_settings() {
+        let image = image::RgbaImage::from_pixel(8, 8, image::Rgba([255, 128, 0, 255]));
+        let mut bytes = std::io::Cursor::new(Vec::new());
+        image
+            .write_to(&mut bytes, image::ImageOutputFormat::Png)
+            .unwrap();
+
+        type Texel = RGBA<Normalized<UBC1>>;
+        let path = Path::new("test.png");
+        let data = Data::new("test", "png", Arc::from(bytes.into_inner()), path, None);
+        let raw = RawTexels::<Texel>::deserialize(data, (), TextureScale::Default).unwrap();
+
+        // Four BC1 blocks of 8 bytes each, stored as 4 bytes per texel
+        assert_eq!(raw.texels().len(), 8);
+        assert_eq!(raw.dimensions(), vek::Extent2::new(8, 8));
+
+        // Block compressed texels can't be downsampled, so no mips must be generated
+        let settings = TextureImportSettings::<Texel>::default();
+        let mips = import_mip_maps::<Texel>(settings.mipmaps, raw.texels(), raw.dimensions());
+        assert!(mips.unwrap().is_none());
+    }
+}
@@ -2,6 +2,7 @@ mod cubemap;
 mod errors;
 mod layered2d;
 mod loader;
+mod meta;
 mod mipmap;
 mod region;
 mod sampler;
@@ -14,6 +15,7 @@ pub use cubemap::*;
 pub use errors::*;
 pub use layered2d::*;
 pub use loader::*;
+pub use meta::*;
 pub use mipmap::*;
 pub use region::*;
 pub use sampler::*;
@@ -1,7 +1,8 @@
-use crate::{Extent, Graphics, ImageTexel, Texel};
-use assets::Asset;
+use crate::{Extent, Graphics, ImageTexel, Texel, TextureMeta};
+use assets::{Asset, ImportKey};
 pub use image::imageops::FilterType;
 use image::ImageFormat;
+use std::hash::{Hash, Hasher};
 use thiserror::Error;
 
 // Texture resolution scale that we can use to downsample or upsample imported textures
@@ -18,6 +19,20 @@ pub enum TextureScale {
     },
 }
 
+// Hashed manually since the scaling factor is a float
+impl Hash for TextureScale {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        match *self {
+            TextureScale::Default => 0u8.hash(state),
+            TextureScale::Scale { scaling, filter } => {
+                1u8.hash(state);
+                scaling.to_bits().hash(state);
+                (filter as u8).hash(state);
+            }
+        }
+    }
+}
+
 // Type of error that might occur when deserializing an image
 #[derive(Debug, Error)]
 pub enum RawTexelsError {
@@ -62,7 +77,30 @@ impl<T: ImageTexel> Asset for RawTexels<T> {
         context: Self::Context<'c>,
         settings: Self::Settings<'s>,
     ) -> Result<Self, Self::Err> {
-        let bytes = data.bytes();
+        // Reuse the texels that were decoded in a previous run if possible
+        let key = ImportKey::new(std::any::type_name::<T>(), data.bytes(), &settings);
+        let cached = data
+            .cached_import(key)
+            .and_then(|bytes| Self::from_cached(&bytes));
+        if let Some(raw) = cached {
+            return Ok(raw);
+        }
+
+        // Decode the texels and store them in the import cache for the next run
+        let raw = Self::decode(data.bytes(), settings)?;
+        data.store_import(key, &raw.to_cached());
+
+        Ok(raw)
+    }
+
+    fn from_meta<'s>(meta: &assets::Meta) -> Option<Result<Self::Settings<'s>, assets::MetaError>> {
+        Some(meta.parse::<TextureMeta>().map(|meta| meta.scale()))
+    }
+}
+
+impl<T: ImageTexel> RawTexels<T> {
+    // Decode the texels from the raw bytes of an image file
+    fn decode(bytes: &[u8], settings: TextureScale) -> Result<Self, RawTexelsError> {
         let guessed = image::guess_format(bytes).unwrap();
 
         // Deserialize, scale down/up, and convert to texels
@@ -106,6 +144,33 @@ impl<T: ImageTexel> Asset for RawTexels<T> {
             _ => panic!("Not supported yet"),
         };
 
-        return Ok(Self(texels, extent));
+        Ok(Self(texels, extent))
+    }
+
+    // Convert the texels to the bytes stored within the import cache (extent followed by the texels)
+    fn to_cached(&self) -> Vec<u8> {
+        let texels = bytemuck::cast_slice::<T::Storage, u8>(&self.0);
+        let mut bytes = Vec::with_capacity(texels.len() + 8);
+        bytes.extend_from_slice(&self.1.w.to_le_bytes());
+        bytes.extend_from_slice(&self.1.h.to_le_bytes());
+        bytes.extend_from_slice(texels);
+        bytes
+    }
+
+    // Convert the bytes stored within the import cache back to texels
+    fn from_cached(bytes: &[u8]) -> Option<Self> {
+        let size = std::mem::size_of::<T::Storage>();
+        let (extent, texels) = (bytes.get(..8)?, &bytes[8..]);
+        if texels.len() % size != 0 {
+            return None;
+        }
+
+        let width = u32::from_le_bytes(extent[..4].try_into().unwrap());
+        let height = u32::from_le_bytes(extent[4..].try_into().unwrap());
+        let texels = texels
+            .chunks_exact(size)
+            .map(bytemuck::pod_read_unaligned)
+            .collect();
+        Some(Self(texels, vek::Extent2::new(width, height)))
     }
 }
@@ -0,0 +1,116 @@
+use crate::{
+    FilterType, ImageTexel, SamplerFilter, SamplerSettings, SamplerWrap, TextureImportSettings,
+    TextureMipMaps, TextureScale,
+};
+use serde::Deserialize;
+
+// Serde mirrors of the external enums that we can specify within meta files
+#[derive(Deserialize)]
+#[serde(remote = "FilterType")]
+enum FilterTypeDef {
+    Nearest,
+    Triangle,
+    CatmullRom,
+    Gaussian,
+    Lanczos3,
+}
+
+#[derive(Deserialize)]
+#[serde(remote = "SamplerFilter")]
+enum SamplerFilterDef {
+    Nearest,
+    Linear,
+}
+
+#[derive(Deserialize)]
+#[serde(remote = "SamplerWrap")]
+enum SamplerWrapDef {
+    ClampToEdge,
+    Repeat,
+    MirrorRepeat,
+    ClampToBorder,
+}
+
+// Texture import settings that artists can write within the "<texture>.meta.ron" sidecar file
+// Missing fields use the same values as the default texture import settings
+#[derive(Deserialize)]
+#[serde(default)]
+pub struct TextureMeta {
+    // Scaling factor applied to the texture when importing it (1.0 keeps the original size)
+    pub scaling: f32,
+    #[serde(with = "FilterTypeDef")]
+    pub filter: FilterType,
+
+    // Sampler filtering and wrapping modes
+    #[serde(with = "SamplerFilterDef")]
+    pub mag_filter: SamplerFilter,
+    #[serde(with = "SamplerFilterDef")]
+    pub min_filter: SamplerFilter,
+    #[serde(with = "SamplerFilterDef")]
+    pub mip_filter: SamplerFilter,
+    #[serde(with = "SamplerWrapDef")]
+    pub wrap_u: SamplerWrap,
+    #[serde(with = "SamplerWrapDef")]
+    pub wrap_v: SamplerWrap,
+    #[serde(with = "SamplerWrapDef")]
+    pub wrap_w: SamplerWrap,
+
+    // Should we generate the mipmaps of the texture
+    pub mipmaps: bool,
+}
+
+impl Default for TextureMeta {
+    fn default() -> Self {
+        let sampling = SamplerSettings::default();
+
+        Self {
+            scaling: 1.0,
+            filter: FilterType::Triangle,
+            mag_filter: sampling.mag_filter,
+            min_filter: sampling.min_filter,
+            mip_filter: sampling.mip_filter,
+            wrap_u: sampling.wrap_u,
+            wrap_v: sampling.wrap_v,
+            wrap_w: sampling.wrap_w,
+            mipmaps: true,
+        }
+    }
+}
+
+impl TextureMeta {
+    // Get the texture scale that must be applied when importing the texels
+    pub fn scale(&self) -> TextureScale {
+        if self.scaling == 1.0 {
+            TextureScale::Default
+        } else {
+            TextureScale::Scale {
+                scaling: self.scaling,
+                filter: self.filter,
+            }
+        }
+    }
+
+    // Convert the meta settings into texture import settings
+    pub fn settings<'s, T: ImageTexel>(&self) -> TextureImportSettings<'s, T> {
+        let defaults = TextureImportSettings::<T>::default();
+
+        TextureImportSettings {
+            sampling: Some(SamplerSettings {
+                mag_filter: self.mag_filter,
+                min_filter: self.min_filter,
+                mip_filter: self.mip_filter,
+                wrap_u: self.wrap_u,
+                wrap_v: self.wrap_v,
+                wrap_w: self.wrap_w,
+                ..SamplerSettings::default()
+            }),
+            scale: self.scale(),
+            mipmaps: if self.mipmaps {
+                defaults.mipmaps
+            } else {
+                TextureMipMaps::Disabled
+            },
+            ..defaults
+        }
+    }
+}
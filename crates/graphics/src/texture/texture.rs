@@ -43,8 +43,19 @@ pub trait Texture: Sized + 'static {
         let format = <Self::T as Texel>::format();
 
         // Make sure the number of texels matches up with the dimensions
+        // Compressed texels store whole blocks, so there are less of them than there are pixels
         if let Some(texels) = texels {
-            if <Self::Region as Region>::volume(extent) as usize != texels.len() {
+            let volume = <Self::Region as Region>::volume(extent) as usize;
+            let count = match <Self::T as Texel>::size() {
+                TexelSize::Uncompressed(_) => volume,
+                TexelSize::Compressed(compression) => {
+                    let block = compression.block_size() as usize;
+                    let bytes = (volume / (block * block)) * compression.bytes_per_block() as usize;
+                    bytes / std::mem::size_of::<<Self::T as Texel>::Storage>()
+                }
+            };
+
+            if count != texels.len() {
                 return Err(TextureInitializationError::TexelDimensionsMismatch {
                     count: texels.len(),
                     w: extent.width(),
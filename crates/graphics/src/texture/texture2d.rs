@@ -1,12 +1,12 @@
 use std::{marker::PhantomData, mem::ManuallyDrop, sync::Arc, time::Instant};
 
-use assets::Asset;
+use assets::{Asset, Reload};
 use smallvec::SmallVec;
 
 use crate::{
-    Extent, Graphics, ImageTexel, RawTexels, Sampler, SamplerSettings, Texel, Texture,
-    TextureAssetLoadError, TextureInitializationError, TextureMipMaps, TextureScale, TextureUsage,
-    TextureViewDimension, TextureViewSettings,
+    Extent, Graphics, ImageTexel, RawTexels, Sampler, SamplerSettings, Texel, TexelSize, Texture,
+    TextureAssetLoadError, TextureInitializationError, TextureMeta, TextureMipMaps, TextureScale,
+    TextureUsage, TextureViewDimension, TextureViewSettings,
 };
 
 // A 2D texture that contains multiple texels that have their own channels
@@ -143,8 +143,15 @@ impl<T: ImageTexel> Asset for Texture2D<T> {
         // Convert raw texels to texture
         texture2d_from_raw(graphics, settings, raw).map_err(TextureAssetLoadError::Initialization)
     }
+
+    fn from_meta<'s>(meta: &assets::Meta) -> Option<Result<Self::Settings<'s>, assets::MetaError>> {
+        Some(meta.parse::<TextureMeta>().map(|meta| meta.settings()))
+    }
 }
 
+// Reloaded textures simply replace the old GPU texture
+impl<T: ImageTexel> Reload for Texture2D<T> {}
+
 // Load in a texture from the raw texels
 pub fn texture2d_from_raw<T: ImageTexel>(
     graphics: Graphics,
@@ -153,22 +160,8 @@ pub fn texture2d_from_raw<T: ImageTexel>(
 ) -> Result<Texture2D<T>, TextureInitializationError> {
     let RawTexels(texels, dimensions) = raw;
 
-    // Check if we must generate mip maps
-    let generate_mip_maps = if let TextureMipMaps::Manual { mips: &[] } = settings.mipmaps {
-        true
-    } else {
-        false
-    };
-
-    // Generate each mip's texel data
-    let mips = if generate_mip_maps {
-        Some(
-            super::generate_mip_map::<T, (vek::Vec2<u32>, vek::Extent2<u32>)>(&texels, dimensions)
-                .ok_or(TextureInitializationError::MipMapGenerationNPOT)?,
-        )
-    } else {
-        None
-    };
+    // Generate each mip's texel data (if needed)
+    let mips = import_mip_maps::<T>(settings.mipmaps, &texels, dimensions)?;
 
     // Convert the vecs to slices
     let mips = mips
@@ -176,12 +169,10 @@ pub fn texture2d_from_raw<T: ImageTexel>(
         .map(|mips| mips.iter().map(|x| x.as_slice()).collect::<Vec<_>>());
 
     // Overwrite the Manual mip map layers if they were empty to begin with
-    let mipmaps = if generate_mip_maps {
-        TextureMipMaps::Manual {
-            mips: &mips.as_ref().unwrap(),
-        }
-    } else {
-        settings.mipmaps
+    let mipmaps = match (settings.mipmaps, mips.as_deref()) {
+        (_, Some(mips)) => TextureMipMaps::Manual { mips },
+        (TextureMipMaps::Manual { mips: &[] }, None) => TextureMipMaps::Disabled,
+        (mipmaps, None) => mipmaps,
     };
 
     // Create the texture
@@ -195,3 +186,20 @@ pub fn texture2d_from_raw<T: ImageTexel>(
         mipmaps,
     )
 }
+
+// Generate the mip maps of imported texels if the manual mip map layers are empty
+// Block compressed texels contain whole 4x4 blocks that can't be downsampled on the CPU, so mip mapping is skipped for them
+pub(crate) fn import_mip_maps<T: ImageTexel>(
+    mipmaps: TextureMipMaps<T>,
+    texels: &[T::Storage],
+    dimensions: vek::Extent2<u32>,
+) -> Result<Option<Vec<Vec<T::Storage>>>, TextureInitializationError> {
+    let generate = matches!(mipmaps, TextureMipMaps::Manual { mips: &[] });
+    if !generate || matches!(T::size(), TexelSize::Compressed(_)) {
+        return Ok(None);
+    }
+
+    super::generate_mip_map::<T, (vek::Vec2<u32>, vek::Extent2<u32>)>(texels, dimensions)
+        .ok_or(TextureInitializationError::MipMapGenerationNPOT)
+        .map(Some)
+}
@@ -59,9 +59,9 @@ fn finish(world: &mut World) {
     // End the Egui frame and fetch the meshes
     let mut output = interface.egui.end_frame();
 
-    // Hide the cursor if we are not taking events
+    // Leave the cursor to the input cursor state if we are not taking events
     if !interface.consumes_window_events {
-        output.platform_output.cursor_icon = egui::CursorIcon::None;
+        output.platform_output.cursor_icon = egui::CursorIcon::Default;
     }
 
     // Handle platform output and tesselate the shapes
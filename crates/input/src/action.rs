@@ -0,0 +1,307 @@
+use crate::{order, Button, ButtonState, Input, InputDevice};
+use ahash::{AHashMap, AHashSet};
+use serde::{Deserialize, Serialize};
+use std::time::{Duration, Instant};
+
+/// Name of the context that is always at the bottom of the context stack.
+pub const DEFAULT_CONTEXT: &str = "default";
+
+/// How the button of an action binding must be used to trigger the action.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum Trigger {
+    /// The action simply follows the state of the button.
+    #[default]
+    Press,
+
+    /// The action only triggers once the button was held for the given number of seconds.
+    Hold(f32),
+
+    /// The action only triggers if the button was pressed twice within the given number of seconds.
+    DoubleTap(f32),
+}
+
+/// A single way to trigger an action, like "Ctrl+S" or "double tap the A button".
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ActionBinding {
+    /// Main button of the binding.
+    pub button: Button,
+
+    /// Buttons that must be held down for the binding to trigger (chords).
+    #[serde(default)]
+    pub modifiers: Vec<Button>,
+
+    /// How the main button must be used.
+    #[serde(default)]
+    pub trigger: Trigger,
+}
+
+impl ActionBinding {
+    /// Create a new binding that simply follows the state of a button.
+    pub fn new(button: impl Into<Button>) -> Self {
+        Self {
+            button: button.into(),
+            modifiers: Vec::new(),
+            trigger: Trigger::Press,
+        }
+    }
+
+    /// Require a modifier to be held down for the binding to trigger.
+    pub fn with_modifier(mut self, modifier: impl Into<Button>) -> Self {
+        self.modifiers.push(modifier.into());
+        self
+    }
+
+    /// Only trigger the binding once the button was held for some amount of seconds.
+    pub fn hold(mut self, seconds: f32) -> Self {
+        self.trigger = Trigger::Hold(seconds);
+        self
+    }
+
+    /// Only trigger the binding if the button was pressed twice within some amount of seconds.
+    pub fn double_tap(mut self, seconds: f32) -> Self {
+        self.trigger = Trigger::DoubleTap(seconds);
+        self
+    }
+}
+
+/// Actions that are only active while their context is within the context stack.
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct ActionMap {
+    // "jump" -> [Key::Space, Gamepad::South]
+    #[serde(serialize_with = "order")]
+    pub(crate) actions: AHashMap<String, Vec<ActionBinding>>,
+
+    // Exclusive contexts hide every button from the contexts below them
+    #[serde(default)]
+    pub(crate) exclusive: bool,
+}
+
+// Time of the last two presses of a button on a specific device
+#[derive(Clone, Copy)]
+pub(crate) struct Press {
+    pub(crate) current: Instant,
+    pub(crate) previous: Option<Instant>,
+}
+
+impl Input {
+    /// Add a binding to an action within a context. Actions can have multiple bindings.
+    pub fn bind_action(
+        &mut self,
+        context: impl Into<String>,
+        name: impl Into<String>,
+        binding: ActionBinding,
+    ) {
+        let (context, name) = (context.into(), name.into());
+        log::debug!("Binding {binding:?} to action '{name}' in context '{context}'");
+        self.bindings
+            .action_maps
+            .entry(context)
+            .or_default()
+            .actions
+            .entry(name)
+            .or_default()
+            .push(binding);
+    }
+
+    /// Add a binding to an action within a context that is only used by a specific player.
+    pub fn bind_action_for(
+        &mut self,
+        player: usize,
+        context: impl Into<String>,
+        name: impl Into<String>,
+        binding: ActionBinding,
+    ) {
+        let (context, name) = (context.into(), name.into());
+        log::debug!(
+            "Binding {binding:?} to action '{name}' in context '{context}' for player {player}"
+        );
+        self.player_bindings
+            .entry(player)
+            .or_default()
+            .action_maps
+            .entry(context)
+            .or_default()
+            .actions
+            .entry(name)
+            .or_default()
+            .push(binding);
+    }
+
+    /// Make a context exclusive, so the contexts below it don't see any input while it is active.
+    /// Non exclusive contexts only hide the buttons that they have bound.
+    pub fn set_exclusive(&mut self, context: impl Into<String>, exclusive: bool) {
+        self.bindings
+            .action_maps
+            .entry(context.into())
+            .or_default()
+            .exclusive = exclusive;
+    }
+
+    /// Push a context on top of the context stack. It consumes the input that it uses.
+    pub fn push_context(&mut self, context: impl Into<String>) {
+        let context = context.into();
+        log::debug!("Pushing input context '{context}'");
+        self.contexts.push(context);
+    }
+
+    /// Pop the context at the top of the context stack. The default context is never popped.
+    pub fn pop_context(&mut self) -> Option<String> {
+        (self.contexts.len() > 1).then(|| self.contexts.pop().unwrap())
+    }
+
+    /// Get the context stack, from the bottom (default context) to the top.
+    pub fn contexts(&self) -> &[String] {
+        &self.contexts
+    }
+
+    /// Get the state of an action using the active contexts and the devices of every player.
+    pub fn get_action(&self, name: &str) -> ButtonState {
+        self.action_state(name, None)
+    }
+
+    /// Get the state of an action using the active contexts, and the devices and bindings of a specific player.
+    pub fn get_action_for(&self, player: usize, name: &str) -> ButtonState {
+        self.action_state(name, Some(player))
+    }
+
+    // Remember when a button was pressed so we can detect holds and double taps
+    // Presses are tracked per device so players that share a button don't mix their timings
+    pub(crate) fn record_press(&mut self, button: Button, device: InputDevice) {
+        let now = self.now;
+        let previous = self
+            .presses
+            .get(&(device, button))
+            .map(|press| press.current);
+        self.presses.insert(
+            (device, button),
+            Press {
+                current: now,
+                previous,
+            },
+        );
+    }
+
+    // Get the latest press of a button that comes from the devices of a player (or all devices)
+    fn last_press(&self, button: Button, player: Option<usize>) -> Option<&Press> {
+        self.presses
+            .iter()
+            .filter(|((device, pressed), _)| *pressed == button && self.used_by(*device, player))
+            .map(|(_, press)| press)
+            .max_by_key(|press| press.current)
+    }
+
+    // Find the action map of a context, looking at the bindings of the player first
+    fn action_map(&self, context: &str, player: Option<usize>) -> impl Iterator<Item = &ActionMap> {
+        let player = player
+            .and_then(|player| self.player_bindings.get(&player))
+            .and_then(|bindings| bindings.action_maps.get(context));
+        player
+            .into_iter()
+            .chain(self.bindings.action_maps.get(context))
+    }
+
+    // Go through the contexts from top to bottom until we find one that contains the action
+    fn action_state(&self, name: &str, player: Option<usize>) -> ButtonState {
+        let mut consumed = AHashSet::<Button>::new();
+
+        for context in self.contexts.iter().rev() {
+            let maps = self.action_map(context, player).collect::<Vec<_>>();
+
+            if let Some(bindings) = maps.iter().find_map(|map| map.actions.get(name)) {
+                return bindings
+                    .iter()
+                    .filter(|binding| !consumed.contains(&binding.button))
+                    .map(|binding| self.binding_state(binding, player))
+                    .fold(ButtonState::None, strongest);
+            }
+
+            // Higher contexts consume the buttons that they use
+            if maps.iter().any(|map| map.exclusive) {
+                return ButtonState::None;
+            }
+
+            // Chords only consume their button while their modifiers are held (Ctrl+S must not hide S)
+            consumed.extend(
+                maps.iter()
+                    .flat_map(|map| map.actions.values().flatten())
+                    .filter(|binding| self.modifiers_down(binding, player))
+                    .map(|binding| binding.button),
+            );
+        }
+
+        ButtonState::None
+    }
+
+    // Check if all the modifiers of an action binding are held down
+    fn modifiers_down(&self, binding: &ActionBinding, player: Option<usize>) -> bool {
+        binding.modifiers.iter().all(|button| {
+            matches!(
+                self.button_state(*button, player),
+                ButtonState::Pressed | ButtonState::Held
+            )
+        })
+    }
+
+    // Get the state of a single action binding
+    fn binding_state(&self, binding: &ActionBinding, player: Option<usize>) -> ButtonState {
+        if !self.modifiers_down(binding, player) {
+            return ButtonState::None;
+        }
+
+        let state = self.button_state(binding.button, player);
+        let Some(press) = self.last_press(binding.button, player) else {
+            return state;
+        };
+
+        match binding.trigger {
+            Trigger::Press => state,
+
+            // Compare how long the button was held at the last two updates
+            Trigger::Hold(seconds) => {
+                let seconds = Duration::from_secs_f32(seconds);
+                let now = self.time.saturating_duration_since(press.current) >= seconds;
+                let before = self.previous.saturating_duration_since(press.current) >= seconds;
+
+                match state {
+                    ButtonState::Pressed | ButtonState::Held if now && !before => {
+                        ButtonState::Pressed
+                    }
+                    ButtonState::Pressed | ButtonState::Held if now => ButtonState::Held,
+                    ButtonState::Released if now => ButtonState::Released,
+                    _ => ButtonState::None,
+                }
+            }
+
+            // Only follow the button if its last press came shortly after the one before
+            Trigger::DoubleTap(seconds) => {
+                let double = press.previous.is_some_and(|previous| {
+                    press.current.duration_since(previous).as_secs_f32() <= seconds
+                });
+
+                if double {
+                    state
+                } else {
+                    ButtonState::None
+                }
+            }
+        }
+    }
+}
+
+// Pick the most "active" state when multiple bindings or devices map to the same button
+pub(crate) fn strongest(a: ButtonState, b: ButtonState) -> ButtonState {
+    fn rank(state: ButtonState) -> u8 {
+        match state {
+            ButtonState::Pressed => 3,
+            ButtonState::Held => 2,
+            ButtonState::Released => 1,
+            ButtonState::None => 0,
+        }
+    }
+
+    if rank(b) > rank(a) {
+        b
+    } else {
+        a
+    }
+}
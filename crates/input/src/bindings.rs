@@ -0,0 +1,172 @@
+use crate::{order, Input, InputUserBindings};
+use ahash::AHashMap;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use utils::{FileManager, FileType, SerdeFormat};
+
+/// Version of the on-disk bindings format written by [Input::save_bindings].
+/// Files without any version are the raw [InputUserBindings] of older builds (format 0).
+pub const BINDINGS_FORMAT: u32 = 1;
+
+/// Default path of the bindings file within the config directory.
+pub const BINDINGS_PATH: &str = "bindings.ron";
+
+/// Bindings file that is written to / read from the config directory.
+#[derive(Default, Clone, Serialize, Deserialize)]
+pub struct InputBindingsFile {
+    /// Version of the file format itself (see [BINDINGS_FORMAT]).
+    pub format: u32,
+
+    /// Version of the bindings of the game, used to migrate renamed bindings.
+    pub version: u32,
+
+    /// Bindings shared by every player.
+    pub bindings: InputUserBindings,
+
+    /// Bindings of specific players.
+    #[serde(default, serialize_with = "order")]
+    pub players: AHashMap<usize, InputUserBindings>,
+}
+
+/// A binding that was renamed by the game, applied when loading bindings files saved before `version`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BindingRename {
+    /// First bindings version that uses the new name.
+    pub version: u32,
+
+    /// Name of the binding before the rename.
+    pub old: String,
+
+    /// Name of the binding after the rename.
+    pub new: String,
+}
+
+impl InputUserBindings {
+    // Rename every binding (and action) that uses the old name
+    pub(crate) fn rename(&mut self, old: &str, new: &str) {
+        fn rename<V>(map: &mut AHashMap<String, V>, old: &str, new: &str) {
+            if let Some(value) = map.remove(old) {
+                map.insert(new.to_owned(), value);
+            }
+        }
+
+        rename(&mut self.key_bindings, old, new);
+        rename(&mut self.axis_bindings, old, new);
+        rename(&mut self.virtual_axis_bindings, old, new);
+        rename(&mut self.axis2_bindings, old, new);
+        for map in self.action_maps.values_mut() {
+            rename(&mut map.actions, old, new);
+        }
+    }
+}
+
+impl InputBindingsFile {
+    // Apply all the renames that happened after this file was saved
+    fn migrate(&mut self, renames: &[BindingRename]) {
+        let mut renames = renames
+            .iter()
+            .filter(|rename| rename.version > self.version)
+            .collect::<Vec<_>>();
+        renames.sort_by_key(|rename| rename.version);
+
+        for rename in renames {
+            log::debug!(
+                "Migrating binding '{}' to '{}' (version {})",
+                rename.old,
+                rename.new,
+                rename.version
+            );
+
+            self.bindings.rename(&rename.old, &rename.new);
+            for bindings in self.players.values_mut() {
+                bindings.rename(&rename.old, &rename.new);
+            }
+            self.version = rename.version;
+        }
+    }
+}
+
+impl Input {
+    /// Register a binding rename, so bindings files saved before `version` get migrated when loaded.
+    /// The version of the saved bindings is the highest version of all the registered renames.
+    pub fn rename_binding(&mut self, version: u32, old: impl Into<String>, new: impl Into<String>) {
+        self.renames.push(BindingRename {
+            version,
+            old: old.into(),
+            new: new.into(),
+        });
+    }
+
+    /// Get the current version of the bindings of the game.
+    pub fn bindings_version(&self) -> u32 {
+        self.renames
+            .iter()
+            .map(|rename| rename.version)
+            .max()
+            .unwrap_or_default()
+    }
+
+    /// Convert the bindings of every player to a versioned bindings file.
+    pub fn as_bindings_file(&self) -> InputBindingsFile {
+        InputBindingsFile {
+            format: BINDINGS_FORMAT,
+            version: self.bindings_version(),
+            bindings: self.bindings.clone(),
+            players: self.player_bindings.clone(),
+        }
+    }
+
+    /// Load the bindings of every player from a bindings file, migrating renamed bindings if needed.
+    pub fn read_bindings_file(&mut self, mut file: InputBindingsFile) {
+        file.migrate(&self.renames);
+        self.read_bindings_from_user_bindings(file.bindings);
+        for (player, bindings) in file.players {
+            self.read_player_bindings(player, bindings);
+        }
+    }
+
+    /// Save the bindings of every player into a RON file within the config directory.
+    pub fn save_bindings(&self, manager: &mut FileManager, path: impl AsRef<Path>) -> Option<()> {
+        manager.serialize_into_file(
+            &self.as_bindings_file(),
+            path,
+            FileType::Config,
+            SerdeFormat::RON,
+        )
+    }
+
+    /// Load the bindings of every player from a RON file within the config directory.
+    /// Files written by older builds (without any version) are supported as well.
+    pub fn load_bindings(
+        &mut self,
+        manager: &mut FileManager,
+        path: impl AsRef<Path>,
+    ) -> Option<()> {
+        let path = path.as_ref();
+        let file = manager
+            .deserialize_from_file::<InputBindingsFile>(path, FileType::Config, SerdeFormat::RON)
+            .or_else(|| {
+                manager
+                    .deserialize_from_file::<InputUserBindings>(
+                        path,
+                        FileType::Config,
+                        SerdeFormat::RON,
+                    )
+                    .map(|bindings| InputBindingsFile {
+                        bindings,
+                        ..Default::default()
+                    })
+            })?;
+
+        if file.format > BINDINGS_FORMAT {
+            log::error!(
+                "Bindings file {path:?} uses format {}, but only format {BINDINGS_FORMAT} is supported",
+                file.format
+            );
+            return None;
+        }
+
+        self.read_bindings_file(file);
+        Some(())
+    }
+}
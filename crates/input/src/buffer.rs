@@ -0,0 +1,165 @@
+use crate::{Button, ButtonState, Input, InputButtonId, InputDevice};
+use std::time::{Duration, Instant};
+
+/// Default duration for which button transitions are kept within the input buffer.
+pub const DEFAULT_INPUT_BUFFER_DURATION: Duration = Duration::from_secs(1);
+
+/// A single press or release of a button, timestamped when the event was received (or when it was recorded while replaying).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Transition {
+    /// The button that was pressed or released.
+    pub button: Button,
+
+    /// The device that the button belongs to.
+    pub device: InputDevice,
+
+    /// Whether the button was pressed or released.
+    pub pressed: bool,
+
+    /// When the event was received.
+    pub time: Instant,
+}
+
+impl Input {
+    /// Get every buffered button transition, from oldest to newest.
+    pub fn transitions(&self) -> impl Iterator<Item = &Transition> {
+        self.transitions.iter()
+    }
+
+    /// Get the button transitions that happened since the last tick.
+    /// Only available within tick events. If multiple ticks run within a frame, each one only sees the transitions of its own fixed tick step.
+    pub fn tick_transitions(&self) -> impl Iterator<Item = &Transition> {
+        let (start, end) = self.tick_window;
+        self.transitions
+            .iter()
+            .filter(move |transition| transition.time > start && transition.time <= end)
+    }
+
+    /// Get the state of a button mapping or a key mapping during the current tick, using the devices of every player.
+    /// Unlike [Input::get_button], presses that were released within the same frame are never lost.
+    pub fn get_button_tick<B: InputButtonId>(&self, button: B) -> ButtonState {
+        B::resolve(button, self, None)
+            .map(|button| self.tick_state(button, None))
+            .unwrap_or(ButtonState::None)
+    }
+
+    /// Get the state of a button mapping or a key mapping during the current tick, using the devices and bindings of a specific player.
+    pub fn get_button_tick_for<B: InputButtonId>(&self, player: usize, button: B) -> ButtonState {
+        B::resolve(button, self, Some(player))
+            .map(|button| self.tick_state(button, Some(player)))
+            .unwrap_or(ButtonState::None)
+    }
+
+    /// Check if a button mapping or a key mapping was pressed within the given duration (fighting game style input buffering).
+    pub fn pressed_within<B: InputButtonId>(&self, button: B, within: Duration) -> bool {
+        let Some(button) = B::resolve(button, self, None) else {
+            return false;
+        };
+
+        self.button_transitions(button, None).any(|transition| {
+            transition.pressed && self.now.saturating_duration_since(transition.time) <= within
+        })
+    }
+
+    /// Set how long button transitions are kept within the input buffer.
+    pub fn set_input_buffer_duration(&mut self, duration: Duration) {
+        self.buffer_duration = duration;
+    }
+
+    // Buffer a button transition that we just received
+    pub(crate) fn push_transition(&mut self, button: Button, device: InputDevice, pressed: bool) {
+        self.transitions.push_back(Transition {
+            button,
+            device,
+            pressed,
+            time: self.now,
+        });
+    }
+
+    // Move the tick window forward along the fixed tick timeline
+    // Ticks that run within the same frame are spaced by the tick delta and the last one ends at the latest event
+    pub(crate) fn advance_tick_window(&mut self) {
+        let start = self.tick_window.1;
+        let remaining = self.scheduled_ticks.saturating_sub(1);
+        self.scheduled_ticks = remaining;
+
+        let end = self
+            .now
+            .checked_sub(self.tick_delta * remaining)
+            .unwrap_or(self.now)
+            .max(start);
+        self.tick_window = (start, end);
+    }
+
+    // Remove the old transitions that were already seen by the ticks
+    pub(crate) fn prune_transitions(&mut self) {
+        let Some(oldest) = self.now.checked_sub(self.buffer_duration) else {
+            return;
+        };
+
+        let oldest = oldest.min(self.tick_window.1);
+        while self
+            .transitions
+            .front()
+            .is_some_and(|transition| transition.time < oldest)
+        {
+            self.transitions.pop_front();
+        }
+    }
+
+    // Get the buffered transitions of a button that come from the devices of a player (or all devices)
+    fn button_transitions(
+        &self,
+        button: Button,
+        player: Option<usize>,
+    ) -> impl DoubleEndedIterator<Item = &Transition> {
+        self.transitions.iter().filter(move |transition| {
+            transition.button == button && self.used_by(transition.device, player)
+        })
+    }
+
+    // Get the state of a button within the current tick window
+    fn tick_state(&self, button: Button, player: Option<usize>) -> ButtonState {
+        if matches!(button, Button::Keyboard(_)) && self.text_focused {
+            return ButtonState::None;
+        }
+
+        let (start, end) = self.tick_window;
+        let mut pressed = false;
+        let mut released = false;
+        for transition in self.button_transitions(button, player) {
+            if transition.time > start && transition.time <= end {
+                pressed |= transition.pressed;
+                released |= !transition.pressed;
+            }
+        }
+
+        // Use the last transition before the end of the window, the opposite of the first one after it, or the live state
+        let down = self
+            .button_transitions(button, player)
+            .rev()
+            .find(|transition| transition.time <= end)
+            .map(|transition| transition.pressed)
+            .or_else(|| {
+                self.button_transitions(button, player)
+                    .next()
+                    .map(|transition| !transition.pressed)
+            })
+            .unwrap_or_else(|| {
+                matches!(
+                    self.button_state(button, player),
+                    ButtonState::Pressed | ButtonState::Held
+                )
+            });
+
+        if pressed {
+            ButtonState::Pressed
+        } else if down {
+            ButtonState::Held
+        } else if released {
+            ButtonState::Released
+        } else {
+            ButtonState::None
+        }
+    }
+}
@@ -0,0 +1,375 @@
+use crate::{Axis, Button, ButtonState, Input, InputUserBindings};
+use serde::{Deserialize, Serialize};
+
+/// Dead zone that hides small values of an axis (like the drift of gamepad sticks).
+#[derive(Debug, Default, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum DeadZone {
+    /// No dead zone at all.
+    #[default]
+    None,
+
+    /// Each component is handled separately, which snaps 2D axes to the X and Y directions.
+    Axial(f32),
+
+    /// The length of the 2D value is used, which keeps the direction of sticks intact.
+    /// This is the same as an axial dead zone for 1D axes.
+    Radial(f32),
+}
+
+/// Response curve applied to the value of an axis after its dead zone.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum ResponseCurve {
+    /// The value is kept as is.
+    #[default]
+    Linear,
+
+    /// The magnitude of the value is raised to the given power, which gives more precision near the center.
+    Exponential(f32),
+}
+
+/// Processing applied to the raw values of a virtual axis.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AxisSettings {
+    /// Dead zone applied to the raw value.
+    pub dead_zone: DeadZone,
+
+    /// Response curve applied after the dead zone.
+    pub curve: ResponseCurve,
+
+    /// Multiplier applied to the final value.
+    pub sensitivity: f32,
+
+    /// Invert the X axis (or the only axis for 1D axes).
+    pub invert_x: bool,
+
+    /// Invert the Y axis of 2D axes.
+    pub invert_y: bool,
+}
+
+impl Default for AxisSettings {
+    fn default() -> Self {
+        Self {
+            dead_zone: DeadZone::None,
+            curve: ResponseCurve::Linear,
+            sensitivity: 1.0,
+            invert_x: false,
+            invert_y: false,
+        }
+    }
+}
+
+/// Source of the raw value of a virtual 1D axis.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum AxisSource {
+    /// A mouse or gamepad axis.
+    Axis(Axis),
+
+    /// Two buttons that push the axis towards -1 and 1.
+    Buttons {
+        /// Button that pushes the axis towards -1.
+        negative: Button,
+
+        /// Button that pushes the axis towards 1.
+        positive: Button,
+    },
+}
+
+/// Source of the raw value of a virtual 2D axis.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum Axis2Source {
+    /// Two mouse or gamepad axii (like the X and Y axii of a gamepad stick).
+    Axii {
+        /// Axis used for the X component.
+        x: Axis,
+
+        /// Axis used for the Y component.
+        y: Axis,
+    },
+
+    /// Four buttons (like WASD). Diagonals are normalized.
+    Buttons {
+        /// Button that pushes the Y component towards 1.
+        up: Button,
+
+        /// Button that pushes the Y component towards -1.
+        down: Button,
+
+        /// Button that pushes the X component towards -1.
+        left: Button,
+
+        /// Button that pushes the X component towards 1.
+        right: Button,
+    },
+}
+
+/// A 1D axis that is built from a raw axis or a pair of buttons, with some processing on top.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct VirtualAxis {
+    /// Where the raw value comes from.
+    pub source: AxisSource,
+
+    /// Processing applied to the raw value.
+    #[serde(default)]
+    pub settings: AxisSettings,
+}
+
+impl VirtualAxis {
+    /// Create a virtual axis from a raw mouse or gamepad axis.
+    pub fn axis(axis: impl Into<Axis>) -> Self {
+        Self {
+            source: AxisSource::Axis(axis.into()),
+            settings: AxisSettings::default(),
+        }
+    }
+
+    /// Create a virtual axis from two buttons.
+    pub fn buttons(negative: impl Into<Button>, positive: impl Into<Button>) -> Self {
+        Self {
+            source: AxisSource::Buttons {
+                negative: negative.into(),
+                positive: positive.into(),
+            },
+            settings: AxisSettings::default(),
+        }
+    }
+
+    /// Replace the processing settings of the axis.
+    pub fn with_settings(mut self, settings: AxisSettings) -> Self {
+        self.settings = settings;
+        self
+    }
+}
+
+/// A 2D axis that is built from two raw axii or four buttons, with some processing on top.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct VirtualAxis2 {
+    /// Where the raw values come from.
+    pub source: Axis2Source,
+
+    /// Processing applied to the raw values.
+    #[serde(default)]
+    pub settings: AxisSettings,
+}
+
+impl VirtualAxis2 {
+    /// Create a virtual 2D axis from two raw mouse or gamepad axii.
+    pub fn axii(x: impl Into<Axis>, y: impl Into<Axis>) -> Self {
+        Self {
+            source: Axis2Source::Axii {
+                x: x.into(),
+                y: y.into(),
+            },
+            settings: AxisSettings::default(),
+        }
+    }
+
+    /// Create a virtual 2D axis from four buttons.
+    pub fn buttons(
+        up: impl Into<Button>,
+        down: impl Into<Button>,
+        left: impl Into<Button>,
+        right: impl Into<Button>,
+    ) -> Self {
+        Self {
+            source: Axis2Source::Buttons {
+                up: up.into(),
+                down: down.into(),
+                left: left.into(),
+                right: right.into(),
+            },
+            settings: AxisSettings::default(),
+        }
+    }
+
+    /// Replace the processing settings of the axis.
+    pub fn with_settings(mut self, settings: AxisSettings) -> Self {
+        self.settings = settings;
+        self
+    }
+}
+
+// Remove the dead zone from a single value and rescale what's left to the full range
+fn dead_zone(value: f32, size: f32) -> f32 {
+    if value.abs() <= size {
+        0.0
+    } else {
+        value.signum() * (value.abs() - size) / (1.0 - size).max(f32::EPSILON)
+    }
+}
+
+// Apply the response curve to the magnitude of a value
+fn curve(value: f32, curve: ResponseCurve) -> f32 {
+    match curve {
+        // Zero must stay zero, since signum(0.0) is 1.0 and 0.0.powf(0.0) is 1.0
+        _ if value == 0.0 => 0.0,
+        ResponseCurve::Linear => value,
+        ResponseCurve::Exponential(power) => value.signum() * value.abs().powf(power),
+    }
+}
+
+impl AxisSettings {
+    // Process the raw value of a 1D axis
+    pub(crate) fn apply(&self, value: f32) -> f32 {
+        let value = match self.dead_zone {
+            DeadZone::None => value,
+            DeadZone::Axial(size) | DeadZone::Radial(size) => dead_zone(value, size),
+        };
+
+        let value = curve(value, self.curve) * self.sensitivity;
+        if self.invert_x {
+            -value
+        } else {
+            value
+        }
+    }
+
+    // Process the raw value of a 2D axis
+    pub(crate) fn apply2(&self, value: vek::Vec2<f32>) -> vek::Vec2<f32> {
+        let mut value = match self.dead_zone {
+            DeadZone::None => value.map(|x| curve(x, self.curve)),
+            DeadZone::Axial(size) => value.map(|x| curve(dead_zone(x, size), self.curve)),
+            DeadZone::Radial(size) => {
+                let length = value.magnitude();
+                let scaled = curve(dead_zone(length, size), self.curve);
+                if length > 0.0 {
+                    value * (scaled / length)
+                } else {
+                    value
+                }
+            }
+        };
+
+        value *= self.sensitivity;
+        if self.invert_x {
+            value.x = -value.x;
+        }
+        if self.invert_y {
+            value.y = -value.y;
+        }
+        value
+    }
+}
+
+impl Input {
+    /// Create a new virtual 1D axis binding, which can be fetched like any other axis binding.
+    pub fn bind_virtual_axis(&mut self, name: impl Into<String>, axis: VirtualAxis) {
+        let name = name.into();
+        log::debug!("Binding virtual axis {axis:?} to '{name}'");
+        self.bindings.virtual_axis_bindings.insert(name, axis);
+    }
+
+    /// Create a new virtual 2D axis binding.
+    pub fn bind_axis2(&mut self, name: impl Into<String>, axis: VirtualAxis2) {
+        let name = name.into();
+        log::debug!("Binding virtual 2D axis {axis:?} to '{name}'");
+        self.bindings.axis2_bindings.insert(name, axis);
+    }
+
+    /// Create a new virtual 1D axis binding that is only used by a specific player.
+    pub fn bind_virtual_axis_for(
+        &mut self,
+        player: usize,
+        name: impl Into<String>,
+        axis: VirtualAxis,
+    ) {
+        let name = name.into();
+        log::debug!("Binding virtual axis {axis:?} to '{name}' for player {player}");
+        self.player_bindings
+            .entry(player)
+            .or_default()
+            .virtual_axis_bindings
+            .insert(name, axis);
+    }
+
+    /// Create a new virtual 2D axis binding that is only used by a specific player.
+    pub fn bind_axis2_for(&mut self, player: usize, name: impl Into<String>, axis: VirtualAxis2) {
+        let name = name.into();
+        log::debug!("Binding virtual 2D axis {axis:?} to '{name}' for player {player}");
+        self.player_bindings
+            .entry(player)
+            .or_default()
+            .axis2_bindings
+            .insert(name, axis);
+    }
+
+    /// Get the value of a virtual 2D axis binding using the devices of every player.
+    pub fn get_axis2(&self, name: &str) -> vek::Vec2<f32> {
+        self.axis2_state(name, None)
+    }
+
+    /// Get the value of a virtual 2D axis binding using the devices and bindings of a specific player.
+    pub fn get_axis2_for(&self, player: usize, name: &str) -> vek::Vec2<f32> {
+        self.axis2_state(name, Some(player))
+    }
+
+    // Find a binding, looking at the bindings of the player first
+    fn find_binding<T: Copy>(
+        &self,
+        player: Option<usize>,
+        map: impl Fn(&InputUserBindings) -> Option<&T>,
+    ) -> Option<T> {
+        player
+            .and_then(|player| self.player_bindings.get(&player))
+            .and_then(&map)
+            .or_else(|| map(&self.bindings))
+            .copied()
+    }
+
+    // Convert a button to a value between 0 and 1
+    fn button_value(&self, button: Button, player: Option<usize>) -> f32 {
+        match self.button_state(button, player) {
+            ButtonState::Pressed | ButtonState::Held => 1.0,
+            _ => 0.0,
+        }
+    }
+
+    // Get the processed value of a virtual 1D axis binding
+    pub(crate) fn virtual_axis_state(&self, name: &str, player: Option<usize>) -> Option<f32> {
+        let axis =
+            self.find_binding(player, |bindings| bindings.virtual_axis_bindings.get(name))?;
+
+        let raw = match axis.source {
+            AxisSource::Axis(axis) => self.axis_state(axis, player),
+            AxisSource::Buttons { negative, positive } => {
+                self.button_value(positive, player) - self.button_value(negative, player)
+            }
+        };
+
+        Some(axis.settings.apply(raw))
+    }
+
+    // Get the processed value of a virtual 2D axis binding
+    fn axis2_state(&self, name: &str, player: Option<usize>) -> vek::Vec2<f32> {
+        let Some(axis) = self.find_binding(player, |bindings| bindings.axis2_bindings.get(name))
+        else {
+            return vek::Vec2::zero();
+        };
+
+        let raw = match axis.source {
+            Axis2Source::Axii { x, y } => {
+                vek::Vec2::new(self.axis_state(x, player), self.axis_state(y, player))
+            }
+            Axis2Source::Buttons {
+                up,
+                down,
+                left,
+                right,
+            } => {
+                let value = vek::Vec2::new(
+                    self.button_value(right, player) - self.button_value(left, player),
+                    self.button_value(up, player) - self.button_value(down, player),
+                );
+
+                // Diagonals should not be faster than straight movement
+                if value.magnitude_squared() > 1.0 {
+                    value.normalized()
+                } else {
+                    value
+                }
+            }
+        };
+
+        axis.settings.apply2(raw)
+    }
+}
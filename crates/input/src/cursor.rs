@@ -0,0 +1,118 @@
+use crate::{Input, InputEvent};
+
+/// Icon of the mouse cursor, same as winit's cursor icon.
+pub type CursorIcon = winit::window::CursorIcon;
+
+/// How the mouse cursor is kept within the window.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CursorGrab {
+    /// The cursor can freely leave the window.
+    #[default]
+    None,
+
+    /// The cursor is kept within the bounds of the window.
+    /// Falls back to [CursorGrab::Locked] on platforms that don't support it.
+    Confined,
+
+    /// The cursor is locked in place.
+    /// Falls back to [CursorGrab::Confined] on platforms that don't support it.
+    Locked,
+}
+
+/// Requested state of the mouse cursor, applied to the window by the app once per frame.
+/// The cursor is automatically freed while the window is unfocused or while the UI owns the cursor.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CursorState {
+    /// How the cursor is kept within the window.
+    pub grab: CursorGrab,
+
+    /// Whether the cursor is visible or not.
+    pub visible: bool,
+
+    /// Relative motion mode, used by first person cameras.
+    /// Mouse motion is ignored while this mode is suspended (window unfocused or cursor owned by the UI),
+    /// so the mouse position axes don't jump when the cursor gets grabbed again.
+    pub relative: bool,
+
+    /// Icon of the cursor while it is visible.
+    pub icon: CursorIcon,
+}
+
+impl Default for CursorState {
+    fn default() -> Self {
+        Self::free()
+    }
+}
+
+impl CursorState {
+    /// A visible cursor that can freely leave the window.
+    pub fn free() -> Self {
+        Self {
+            grab: CursorGrab::None,
+            visible: true,
+            relative: false,
+            icon: CursorIcon::Default,
+        }
+    }
+
+    /// A hidden cursor that is locked in place, used for relative mouse motion.
+    pub fn locked() -> Self {
+        Self {
+            grab: CursorGrab::Locked,
+            visible: false,
+            relative: true,
+            icon: CursorIcon::Default,
+        }
+    }
+
+    /// Change the icon of the cursor.
+    pub fn with_icon(mut self, icon: CursorIcon) -> Self {
+        self.icon = icon;
+        self
+    }
+}
+
+impl Input {
+    /// Request a new state for the mouse cursor.
+    pub fn set_cursor(&mut self, state: CursorState) {
+        self.cursor = state;
+    }
+
+    /// Get the requested state of the mouse cursor.
+    pub fn cursor(&self) -> CursorState {
+        self.cursor
+    }
+
+    /// Get the state of the mouse cursor that should currently be applied to the window.
+    /// This is the requested state unless the window is unfocused or the cursor was released.
+    pub fn applied_cursor(&self) -> CursorState {
+        if self.focused && !self.cursor_released {
+            self.cursor
+        } else {
+            CursorState::free()
+        }
+    }
+
+    /// Temporarily give the cursor back to the user without changing the requested state.
+    /// The app releases the cursor while the UI consumes the window events.
+    pub fn release_cursor(&mut self, released: bool) {
+        self.cursor_released = released;
+    }
+
+    /// Check if the cursor is currently released.
+    pub fn is_cursor_released(&self) -> bool {
+        self.cursor_released
+    }
+
+    /// Check if the window currently has focus.
+    pub fn is_window_focused(&self) -> bool {
+        self.focused
+    }
+
+    // Check if the event is mouse motion that must be ignored since relative mode is suspended
+    pub(crate) fn is_suspended_motion(&self, event: &InputEvent) -> bool {
+        matches!(event, InputEvent::MouseMotion { .. })
+            && self.cursor.relative
+            && !self.applied_cursor().relative
+    }
+}
@@ -0,0 +1,251 @@
+use crate::{
+    Axis, Button, ButtonState, Composition, GamepadAxis, GamepadButton, GamepadId, Input,
+    InputDevice, MouseAxis, TextEvent, TouchId, TouchPhase,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::hash_map::Entry;
+
+/// A single input event, reduced to what the input system needs to update its state.
+/// Live window / device / gamepad events are converted to these before being applied, so they can be recorded and replayed.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum InputEvent {
+    /// A keyboard key or a mouse button was pressed or released.
+    Button {
+        /// The keyboard key or mouse button.
+        button: Button,
+
+        /// Whether the button was pressed or released.
+        pressed: bool,
+    },
+
+    /// A character was typed.
+    Character(char),
+
+    /// The IME composition changed. An empty composition means that it ended.
+    Composition(Composition),
+
+    /// The IME committed some composed text.
+    Commit(String),
+
+    /// The mouse moved by some delta.
+    MouseMotion {
+        /// Delta on the X axis.
+        x: f32,
+
+        /// Delta on the Y axis.
+        y: f32,
+    },
+
+    /// The mouse wheel scrolled by some delta.
+    MouseWheel(f32),
+
+    /// A finger / pen touched the screen, moved, or stopped touching it.
+    Touch {
+        /// Unique identifier of the touch.
+        id: TouchId,
+
+        /// Phase of the touch.
+        phase: TouchPhase,
+
+        /// Position on the X axis in physical pixels.
+        x: f32,
+
+        /// Position on the Y axis in physical pixels.
+        y: f32,
+
+        /// Normalized pressure (0 - 1), if the device supports it.
+        pressure: Option<f32>,
+
+        /// Angle between the pen and the screen in radians, if the device supports it.
+        altitude: Option<f32>,
+    },
+
+    /// A gamepad was connected.
+    GamepadConnected(GamepadId),
+
+    /// A gamepad was disconnected.
+    GamepadDisconnected(GamepadId),
+
+    /// A button of a gamepad was pressed or released.
+    GamepadButton {
+        /// The gamepad that the button belongs to.
+        id: GamepadId,
+
+        /// The gamepad button.
+        button: GamepadButton,
+
+        /// Whether the button was pressed or released.
+        pressed: bool,
+    },
+
+    /// An axis of a gamepad changed.
+    GamepadAxis {
+        /// The gamepad that the axis belongs to.
+        id: GamepadId,
+
+        /// The gamepad axis.
+        axis: GamepadAxis,
+
+        /// The new value of the axis.
+        value: f32,
+    },
+}
+
+impl InputEvent {
+    /// Check if this event comes from gilrs instead of winit.
+    pub fn is_gamepad(&self) -> bool {
+        matches!(
+            self,
+            InputEvent::GamepadConnected(_)
+                | InputEvent::GamepadDisconnected(_)
+                | InputEvent::GamepadButton { .. }
+                | InputEvent::GamepadAxis { .. }
+        )
+    }
+}
+
+impl Input {
+    // Update the state of the input devices using a single event
+    pub(crate) fn apply(&mut self, event: InputEvent) {
+        match event {
+            InputEvent::Button { button, pressed } => self.apply_button(button, pressed),
+
+            // Editing keys are sent as key events, so only printable characters are kept
+            InputEvent::Character(char) if !char.is_control() => {
+                self.push_text(TextEvent::Char(char))
+            }
+            InputEvent::Character(_) => {}
+            InputEvent::Composition(composition) => self.compose(composition),
+            InputEvent::Commit(text) => {
+                self.composition = None;
+                self.push_text(TextEvent::Commit(text));
+            }
+
+            // Sum the mouse motion of this frame and the summed pos
+            InputEvent::MouseMotion { x, y } => {
+                *self
+                    .axii
+                    .entry(Axis::Mouse(MouseAxis::DeltaX))
+                    .or_insert(0.0) += x;
+                *self
+                    .axii
+                    .entry(Axis::Mouse(MouseAxis::DeltaY))
+                    .or_insert(0.0) += y;
+                *self
+                    .axii
+                    .entry(Axis::Mouse(MouseAxis::PositionX))
+                    .or_insert(0.0) += x;
+                *self
+                    .axii
+                    .entry(Axis::Mouse(MouseAxis::PositionY))
+                    .or_insert(0.0) += y;
+            }
+
+            // Update mouse wheel delta and summed value
+            InputEvent::MouseWheel(delta) => {
+                self.axii.insert(Axis::Mouse(MouseAxis::ScrollDelta), delta);
+                *self
+                    .axii
+                    .entry(Axis::Mouse(MouseAxis::Scroll))
+                    .or_insert(0.0) += delta;
+                self.capture_axis(
+                    Axis::Mouse(MouseAxis::ScrollDelta),
+                    delta,
+                    InputDevice::KeyboardMouse,
+                );
+            }
+
+            InputEvent::Touch {
+                id,
+                phase,
+                x,
+                y,
+                pressure,
+                altitude,
+            } => self.apply_touch(id, phase, vek::Vec2::new(x, y), pressure, altitude),
+
+            // Add the gamepad controller and assign it to a player
+            InputEvent::GamepadConnected(id) => self.connect(id),
+
+            // Remove the gamepad controller from its player
+            InputEvent::GamepadDisconnected(id) => self.disconnect(id),
+
+            InputEvent::GamepadButton {
+                id,
+                button,
+                pressed,
+            } => self.apply_gamepad_button(id, button, pressed),
+
+            InputEvent::GamepadAxis { id, axis, value } => {
+                if let Some(gamepad) = self.gamepads.get_mut(&id) {
+                    gamepad.axii.insert(axis, value);
+                    self.capture_axis(Axis::Gamepad(axis), value, InputDevice::Gamepad(id));
+                }
+            }
+        }
+    }
+
+    // Update the state of a gamepad button
+    fn apply_gamepad_button(&mut self, id: GamepadId, button: GamepadButton, pressed: bool) {
+        let Some(gamepad) = self.gamepads.get_mut(&id) else {
+            return;
+        };
+
+        let state = if pressed {
+            ButtonState::Pressed
+        } else {
+            ButtonState::Released
+        };
+
+        let previous = gamepad.buttons.insert(button, state);
+        let down = matches!(previous, Some(ButtonState::Pressed | ButtonState::Held));
+        if down != pressed {
+            self.push_transition(Button::Gamepad(button), InputDevice::Gamepad(id), pressed);
+        }
+
+        if pressed {
+            self.record_press(Button::Gamepad(button), InputDevice::Gamepad(id));
+            self.capture_button(Button::Gamepad(button), InputDevice::Gamepad(id));
+        }
+    }
+
+    // Update the state of a keyboard key or a mouse button
+    fn apply_button(&mut self, key: Button, pressed: bool) {
+        let state = if pressed {
+            ButtonState::Pressed
+        } else {
+            ButtonState::Released
+        };
+
+        let changed = match self.keys.entry(key) {
+            Entry::Occupied(mut current) => {
+                // Check if the key is "down" (either pressed or held)
+                let down = matches!(*current.get(), ButtonState::Pressed | ButtonState::Held);
+
+                // If the key is pressed while it is currently down, it repeated itself, and we must ignore it
+                let changed = down ^ pressed;
+                if changed {
+                    current.insert(state);
+                }
+                changed
+            }
+            Entry::Vacant(v) => {
+                v.insert(state);
+                true
+            }
+        };
+
+        if changed {
+            self.push_transition(key, InputDevice::KeyboardMouse, pressed);
+        }
+
+        if changed && pressed {
+            self.record_press(key, InputDevice::KeyboardMouse);
+            self.capture_button(key, InputDevice::KeyboardMouse);
+
+            if let Button::Keyboard(key) = key {
+                self.push_text(TextEvent::Key(key));
+            }
+        }
+    }
+}
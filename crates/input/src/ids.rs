@@ -4,52 +4,57 @@ use crate::{Axis, Button, ButtonState, Input};
 /// Allows for use to pass `&str` and `Button` interchangeably into methods that require fetching button state.
 pub trait InputButtonId {
     /// Get the button state using `self` as an identifier
-    fn get(self, input: &Input) -> ButtonState;
+    /// Only the devices and bindings of the player are used if one is given
+    fn get(self, input: &Input, player: Option<usize>) -> ButtonState;
+
+    /// Get the button that `self` refers to, using the bindings of the player if one is given
+    fn resolve(self, input: &Input, player: Option<usize>) -> Option<Button>;
 }
 
 impl<T: Into<Button>> InputButtonId for T {
-    fn get(self, input: &Input) -> ButtonState {
-        let converted = self.into();
-        input
-            .keys
-            .get(&converted)
-            .cloned()
-            .unwrap_or(ButtonState::None)
+    fn get(self, input: &Input, player: Option<usize>) -> ButtonState {
+        input.button_state(self.into(), player)
+    }
+
+    fn resolve(self, _input: &Input, _player: Option<usize>) -> Option<Button> {
+        Some(self.into())
     }
 }
 
-impl InputButtonId for &'static str {
-    fn get(self, input: &Input) -> ButtonState {
+impl InputButtonId for &str {
+    fn get(self, input: &Input, player: Option<usize>) -> ButtonState {
         input
-            .bindings
-            .key_bindings
-            .get(self)
-            .map(|key| Button::get(*key, input))
+            .key_binding(self, player)
+            .map(|key| Button::get(key, input, player))
             .unwrap_or(ButtonState::None)
     }
+
+    fn resolve(self, input: &Input, player: Option<usize>) -> Option<Button> {
+        input.key_binding(self, player)
+    }
 }
 
 /// Trait implemented for structs that allow us to fetch the axis state from the main input handler.
 /// Allows for use to pass `&str` and `Axis` interchangeably into methods that require fetching button state.
 pub trait InputAxisId {
     /// Get the input state using `self` as an identifier
-    fn get(self, input: &Input) -> f32;
+    /// Only the devices and bindings of the player are used if one is given
+    fn get(self, input: &Input, player: Option<usize>) -> f32;
 }
 
 impl<T: Into<Axis>> InputAxisId for T {
-    fn get(self, input: &Input) -> f32 {
-        let converted = self.into();
-        input.axii.get(&converted).cloned().unwrap_or_default()
+    fn get(self, input: &Input, player: Option<usize>) -> f32 {
+        input.axis_state(self.into(), player)
     }
 }
 
-impl InputAxisId for &'static str {
-    fn get(self, input: &Input) -> f32 {
+impl InputAxisId for &str {
+    fn get(self, input: &Input, player: Option<usize>) -> f32 {
+        // Virtual axis bindings are used if there isn't any raw axis binding with this name
         input
-            .bindings
-            .axis_bindings
-            .get(self)
-            .map(|axis| Axis::get(*axis, input))
+            .axis_binding(self, player)
+            .map(|axis| Axis::get(axis, input, player))
+            .or_else(|| input.virtual_axis_state(self, player))
             .unwrap_or_default()
     }
 }
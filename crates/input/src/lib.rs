@@ -2,18 +2,46 @@
 
 //! TODO: Docs
 
+mod action;
 mod axis;
+mod bindings;
+mod buffer;
 mod button;
+mod composite;
+mod cursor;
+mod event;
 mod ids;
+mod player;
+mod rebind;
+mod replay;
+mod rumble;
 mod system;
+mod tests;
+mod text;
+mod touch;
+pub use action::*;
 pub use axis::*;
+pub use bindings::*;
+pub use buffer::*;
 pub use button::*;
+pub use composite::*;
+pub use cursor::*;
+pub use event::*;
 pub use ids::*;
+pub use player::*;
+pub use rebind::*;
+pub use replay::*;
+pub use rumble::*;
 pub use system::*;
+pub use text::*;
+pub use touch::*;
 
 use ahash::AHashMap;
 use serde::*;
-use std::collections::BTreeMap;
+use std::{
+    collections::{BTreeMap, VecDeque},
+    time::{Duration, Instant},
+};
 
 /// Main input resource responsible for keyboard / mouse / gamepad input events.
 /// This resource will automatically be added into the world at startup.
@@ -21,32 +49,97 @@ pub struct Input {
     // Key and axis bindings
     pub(crate) bindings: InputUserBindings,
 
-    // Key::W -> State::Pressed
+    // Key::W -> State::Pressed (keyboard and mouse only)
     pub(crate) keys: AHashMap<Button, ButtonState>,
 
-    // Axis::MousePositionX -> 561.56
+    // Axis::MousePositionX -> 561.56 (mouse only)
     pub(crate) axii: AHashMap<Axis, f32>,
 
     // Used only for gamepad support
     pub(crate) gilrs: gilrs::Gilrs,
-    pub(crate) gamepad: Option<gilrs::GamepadId>,
+    pub(crate) gamepads: AHashMap<GamepadId, GamepadState>,
+
+    // Local multiplayer support (device -> player index)
+    pub(crate) players: AHashMap<InputDevice, usize>,
+    pub(crate) player_bindings: AHashMap<usize, InputUserBindings>,
+    pub(crate) connections: Vec<DeviceConnection>,
+    pub(crate) auto_assign: bool,
+
+    // Action contexts (bottom to top) and the timings used by hold / double tap triggers
+    // The time of the event that is being applied comes from the recording while replaying
+    pub(crate) contexts: Vec<String>,
+    pub(crate) presses: AHashMap<(InputDevice, Button), Press>,
+    pub(crate) time: Instant,
+    pub(crate) previous: Instant,
+    pub(crate) now: Instant,
+
+    // Renamed bindings and the state of the "listen for next input" rebinding
+    pub(crate) renames: Vec<BindingRename>,
+    pub(crate) capturing: Option<(RebindTarget, Option<usize>)>,
+    pub(crate) captured: Option<Capture>,
+
+    // Input recording / replay, using the frame and tick indices of the current frame
+    pub(crate) frame: u64,
+    pub(crate) tick: u64,
+    pub(crate) recorder: Option<Recorder>,
+    pub(crate) replay: Option<Replay>,
+
+    // Text input stream of the focused text field and the system clipboard
+    pub(crate) text_focused: bool,
+    pub(crate) text_events: Vec<TextEvent>,
+    pub(crate) composition: Option<Composition>,
+    pub(crate) clipboard: Option<arboard::Clipboard>,
+
+    // Force feedback effects that are currently playing
+    pub(crate) rumbles: AHashMap<RumbleId, PlayingRumble>,
+    pub(crate) rumble_counter: u64,
+    pub(crate) rumble_gain: f32,
+
+    // Timestamped button transitions, the time window of the current tick and the ticks left within this frame
+    pub(crate) transitions: VecDeque<Transition>,
+    pub(crate) buffer_duration: Duration,
+    pub(crate) tick_window: (Instant, Instant),
+    pub(crate) scheduled_ticks: u32,
+    pub(crate) tick_delta: Duration,
+
+    // Touches on the screen, recognized gestures and the touch that emulates the mouse
+    pub(crate) touches: AHashMap<TouchId, Touch>,
+    pub(crate) gestures: Vec<Gesture>,
+    pub(crate) touch_mouse: bool,
+    pub(crate) emulated: Option<TouchId>,
+
+    // Requested cursor state, whether it was released to the UI, and the focus of the window
+    pub(crate) cursor: CursorState,
+    pub(crate) cursor_released: bool,
+    pub(crate) focused: bool,
 }
 
 /// User input bindings that can be serialized / deserialized.
 #[derive(Default, Clone, Serialize, Deserialize)]
-#[serde(bound(deserialize = "'de: 'static"))]
 pub struct InputUserBindings {
     // "forward_key_bind" -> Key::W
     #[serde(serialize_with = "order")]
-    pub(crate) key_bindings: AHashMap<&'static str, Button>,
+    pub(crate) key_bindings: AHashMap<String, Button>,
 
     // "camera rotation" -> Axis:MousePositionX,
     #[serde(serialize_with = "order")]
-    pub(crate) axis_bindings: AHashMap<&'static str, Axis>,
+    pub(crate) axis_bindings: AHashMap<String, Axis>,
+
+    // "steering" -> Axis::Gamepad(LeftStickX) with a dead zone
+    #[serde(default, serialize_with = "order")]
+    pub(crate) virtual_axis_bindings: AHashMap<String, VirtualAxis>,
+
+    // "movement" -> W, S, A, D
+    #[serde(default, serialize_with = "order")]
+    pub(crate) axis2_bindings: AHashMap<String, VirtualAxis2>,
+
+    // "gameplay" -> { "jump" -> [Key::Space, Gamepad::South] }
+    #[serde(default, serialize_with = "order")]
+    pub(crate) action_maps: AHashMap<String, ActionMap>,
 }
 
-fn order<S, V: Serialize>(
-    value: &AHashMap<&'static str, V>,
+pub(crate) fn order<S, K: Ord + Serialize, V: Serialize>(
+    value: &AHashMap<K, V>,
     serializer: S,
 ) -> Result<S::Ok, S::Error>
 where
@@ -62,6 +155,11 @@ impl Input {
     pub fn read_bindings_from_user_bindings(&mut self, user: InputUserBindings) {
         self.bindings.axis_bindings.extend(user.axis_bindings);
         self.bindings.key_bindings.extend(user.key_bindings);
+        self.bindings
+            .virtual_axis_bindings
+            .extend(user.virtual_axis_bindings);
+        self.bindings.axis2_bindings.extend(user.axis2_bindings);
+        self.bindings.action_maps.extend(user.action_maps);
     }
 
     /// Convert the bindings to a user binding struct.
@@ -70,26 +168,40 @@ impl Input {
     }
 
     /// Create a new button binding using a name and a unique key.
-    pub fn bind_button(&mut self, name: &'static str, key: impl Into<Button>) {
+    pub fn bind_button(&mut self, name: impl Into<String>, key: impl Into<Button>) {
+        let name = name.into();
         let key = key.into();
         log::debug!("Binding button/key {key:?} to '{name}'");
         self.bindings.key_bindings.insert(name, key);
     }
 
     /// Create a new axis binding using a name and a unique axis.
-    pub fn bind_axis(&mut self, name: &'static str, axis: impl Into<Axis>) {
+    pub fn bind_axis(&mut self, name: impl Into<String>, axis: impl Into<Axis>) {
+        let name = name.into();
         let axis = axis.into();
         log::debug!("Binding axis {axis:?} to '{name}'");
         self.bindings.axis_bindings.insert(name, axis);
     }
 
     /// Get the state of a button mapping or a key mapping.
+    /// This uses the devices of every player, so gamepad buttons are pressed if they are pressed on any gamepad.
     pub fn get_button<B: InputButtonId>(&self, button: B) -> ButtonState {
-        B::get(button, self)
+        B::get(button, self, None)
     }
 
     /// Get the state of a unique axis or an axis mapping.
+    /// This uses the devices of every player.
     pub fn get_axis<A: InputAxisId>(&self, axis: A) -> f32 {
-        A::get(axis, self)
+        A::get(axis, self, None)
+    }
+
+    /// Get the state of a button mapping or a key mapping using the devices and bindings of a specific player.
+    pub fn get_button_for<B: InputButtonId>(&self, player: usize, button: B) -> ButtonState {
+        B::get(button, self, Some(player))
+    }
+
+    /// Get the state of a unique axis or an axis mapping using the devices and bindings of a specific player.
+    pub fn get_axis_for<A: InputAxisId>(&self, player: usize, axis: A) -> f32 {
+        A::get(axis, self, Some(player))
     }
 }
@@ -0,0 +1,221 @@
+use crate::{
+    strongest, Axis, Button, ButtonState, GamepadAxis, GamepadButton, Input, InputUserBindings,
+};
+use ahash::AHashMap;
+
+/// Gamepad identifier given by GilRS.
+pub type GamepadId = gilrs::GamepadId;
+
+/// An input device that can be assigned to a local player.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum InputDevice {
+    /// The keyboard and the mouse, which are always assigned together.
+    KeyboardMouse,
+
+    /// A single gamepad.
+    Gamepad(GamepadId),
+}
+
+/// Event that is sent whenever a gamepad is connected or disconnected.
+/// These are only available during the frame after the gamepad was (dis)connected.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DeviceConnection {
+    /// A gamepad was connected, and optionally assigned to a player.
+    Connected(InputDevice, Option<usize>),
+
+    /// A gamepad was disconnected, and removed from the player it was assigned to.
+    Disconnected(InputDevice, Option<usize>),
+}
+
+// Button and axis states of a single connected gamepad
+#[derive(Default)]
+pub(crate) struct GamepadState {
+    pub(crate) buttons: AHashMap<GamepadButton, ButtonState>,
+    pub(crate) axii: AHashMap<GamepadAxis, f32>,
+}
+
+impl Input {
+    /// Assign an input device to a player, removing it from its previous player.
+    pub fn assign(&mut self, device: InputDevice, player: usize) {
+        log::debug!("Assigning input device {device:?} to player {player}");
+        self.players.insert(device, player);
+    }
+
+    /// Remove an input device from the player it was assigned to.
+    /// Returns the index of the player that used the device.
+    pub fn unassign(&mut self, device: InputDevice) -> Option<usize> {
+        self.players.remove(&device)
+    }
+
+    /// Get the index of the player that an input device is assigned to.
+    pub fn player(&self, device: InputDevice) -> Option<usize> {
+        self.players.get(&device).copied()
+    }
+
+    /// Get all the input devices that are assigned to a player.
+    pub fn devices(&self, player: usize) -> Vec<InputDevice> {
+        self.players
+            .iter()
+            .filter(|(_, index)| **index == player)
+            .map(|(device, _)| *device)
+            .collect()
+    }
+
+    /// Get the IDs of all the gamepads that are currently connected.
+    pub fn gamepads(&self) -> Vec<GamepadId> {
+        self.gamepads.keys().copied().collect()
+    }
+
+    /// Enable or disable the automatic assignment of newly connected gamepads.
+    /// When enabled (by default), a new gamepad is given to the first player that does not have any gamepad.
+    pub fn set_auto_assign(&mut self, enabled: bool) {
+        self.auto_assign = enabled;
+    }
+
+    /// Get the gamepad connection events that occured during the last frame.
+    pub fn connections(&self) -> &[DeviceConnection] {
+        &self.connections
+    }
+
+    /// Create a new button binding that is only used by a specific player.
+    /// Player bindings take precedence over the global bindings of the same name.
+    pub fn bind_button_for(
+        &mut self,
+        player: usize,
+        name: impl Into<String>,
+        key: impl Into<Button>,
+    ) {
+        let name = name.into();
+        let key = key.into();
+        log::debug!("Binding button/key {key:?} to '{name}' for player {player}");
+        self.player_bindings
+            .entry(player)
+            .or_default()
+            .key_bindings
+            .insert(name, key);
+    }
+
+    /// Create a new axis binding that is only used by a specific player.
+    /// Player bindings take precedence over the global bindings of the same name.
+    pub fn bind_axis_for(&mut self, player: usize, name: impl Into<String>, axis: impl Into<Axis>) {
+        let name = name.into();
+        let axis = axis.into();
+        log::debug!("Binding axis {axis:?} to '{name}' for player {player}");
+        self.player_bindings
+            .entry(player)
+            .or_default()
+            .axis_bindings
+            .insert(name, axis);
+    }
+
+    /// Load the bindings of a specific player from a user binding struct.
+    pub fn read_player_bindings(&mut self, player: usize, user: InputUserBindings) {
+        let bindings = self.player_bindings.entry(player).or_default();
+        bindings.axis_bindings.extend(user.axis_bindings);
+        bindings.key_bindings.extend(user.key_bindings);
+        bindings
+            .virtual_axis_bindings
+            .extend(user.virtual_axis_bindings);
+        bindings.axis2_bindings.extend(user.axis2_bindings);
+        bindings.action_maps.extend(user.action_maps);
+    }
+
+    /// Convert the bindings of a specific player to a user binding struct.
+    pub fn as_player_binding(&self, player: usize) -> InputUserBindings {
+        self.player_bindings
+            .get(&player)
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    // Add a newly connected gamepad and assign it to a player if needed
+    pub(crate) fn connect(&mut self, id: GamepadId) {
+        if self.gamepads.contains_key(&id) {
+            return;
+        }
+
+        self.gamepads.insert(id, GamepadState::default());
+        let device = InputDevice::Gamepad(id);
+
+        if self.auto_assign && self.player(device).is_none() {
+            let player = (0..)
+                .find(|player| {
+                    !self.players.iter().any(|(device, index)| {
+                        matches!(device, InputDevice::Gamepad(_)) && index == player
+                    })
+                })
+                .unwrap();
+            self.assign(device, player);
+        }
+
+        self.connections
+            .push(DeviceConnection::Connected(device, self.player(device)));
+    }
+
+    // Remove a disconnected gamepad from its player
+    pub(crate) fn disconnect(&mut self, id: GamepadId) {
+        let device = InputDevice::Gamepad(id);
+        self.gamepads.remove(&id);
+        let player = self.unassign(device);
+        self.connections
+            .push(DeviceConnection::Disconnected(device, player));
+    }
+
+    // Check if a device is used by a player (or by anyone if no player is specified)
+    pub(crate) fn used_by(&self, device: InputDevice, player: Option<usize>) -> bool {
+        player.is_none_or(|player| self.player(device) == Some(player))
+    }
+
+    // Find a button binding, looking at the bindings of the player first
+    pub(crate) fn key_binding(&self, name: &str, player: Option<usize>) -> Option<Button> {
+        player
+            .and_then(|player| self.player_bindings.get(&player))
+            .and_then(|bindings| bindings.key_bindings.get(name))
+            .or_else(|| self.bindings.key_bindings.get(name))
+            .copied()
+    }
+
+    // Find an axis binding, looking at the bindings of the player first
+    pub(crate) fn axis_binding(&self, name: &str, player: Option<usize>) -> Option<Axis> {
+        player
+            .and_then(|player| self.player_bindings.get(&player))
+            .and_then(|bindings| bindings.axis_bindings.get(name))
+            .or_else(|| self.bindings.axis_bindings.get(name))
+            .copied()
+    }
+
+    // Get the state of a button using the devices of a player (or all devices)
+    pub(crate) fn button_state(&self, button: Button, player: Option<usize>) -> ButtonState {
+        match button {
+            Button::Gamepad(button) => self
+                .gamepads
+                .iter()
+                .filter(|(id, _)| self.used_by(InputDevice::Gamepad(**id), player))
+                .filter_map(|(_, gamepad)| gamepad.buttons.get(&button).copied())
+                .fold(ButtonState::None, strongest),
+            // The keyboard belongs to the focused text field
+            Button::Keyboard(_) if self.text_focused => ButtonState::None,
+            button if self.used_by(InputDevice::KeyboardMouse, player) => {
+                self.keys.get(&button).copied().unwrap_or(ButtonState::None)
+            }
+            _ => ButtonState::None,
+        }
+    }
+
+    // Get the value of an axis using the devices of a player (or all devices)
+    // If multiple gamepads are used, the value with the largest magnitude is returned
+    pub(crate) fn axis_state(&self, axis: Axis, player: Option<usize>) -> f32 {
+        match axis {
+            Axis::Gamepad(axis) => self
+                .gamepads
+                .iter()
+                .filter(|(id, _)| self.used_by(InputDevice::Gamepad(**id), player))
+                .filter_map(|(_, gamepad)| gamepad.axii.get(&axis).copied())
+                .fold(0.0, |a: f32, b: f32| if b.abs() > a.abs() { b } else { a }),
+            axis if self.used_by(InputDevice::KeyboardMouse, player) => {
+                self.axii.get(&axis).copied().unwrap_or_default()
+            }
+            _ => 0.0,
+        }
+    }
+}
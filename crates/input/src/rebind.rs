@@ -0,0 +1,264 @@
+use crate::{
+    ActionBinding, Axis, Axis2Source, AxisSource, Button, Input, InputDevice, InputUserBindings,
+};
+
+/// Minimum absolute value that an axis must reach to be captured.
+pub const CAPTURE_AXIS_THRESHOLD: f32 = 0.5;
+
+/// Binding that is rebound using the next input of the user.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RebindTarget {
+    /// A button binding, rebound using the next pressed button.
+    Button(String),
+
+    /// An axis binding, rebound using the next moved gamepad axis (or the mouse wheel).
+    Axis(String),
+
+    /// An action within a context, rebound using the next pressed button.
+    /// This replaces the binding of the action that uses the same kind of device (keyboard / mouse or gamepad).
+    Action {
+        /// Context of the action.
+        context: String,
+
+        /// Name of the action.
+        name: String,
+    },
+}
+
+/// Input that was captured while listening for the next input.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CapturedInput {
+    /// A pressed button.
+    Button(Button),
+
+    /// A moved axis.
+    Axis(Axis),
+}
+
+/// Result of listening for the next input, which can be applied using [Input::apply_capture].
+#[derive(Debug, Clone, PartialEq)]
+pub struct Capture {
+    /// Binding that should be rebound.
+    pub target: RebindTarget,
+
+    /// Player whose bindings should be changed, or none for the shared bindings.
+    pub player: Option<usize>,
+
+    /// The input that was captured.
+    pub input: CapturedInput,
+
+    /// Names of the other bindings that already use the captured input.
+    /// Actions are named "context/action".
+    pub conflicts: Vec<String>,
+}
+
+// Check if two buttons come from the same kind of device
+fn same_device(a: Button, b: Button) -> bool {
+    matches!(a, Button::Gamepad(_)) == matches!(b, Button::Gamepad(_))
+}
+
+impl InputUserBindings {
+    // Get the names of the bindings that use an input
+    fn users(&self, input: CapturedInput) -> impl Iterator<Item = String> + '_ {
+        let keys = self
+            .key_bindings
+            .iter()
+            .filter(move |(_, button)| input == CapturedInput::Button(**button))
+            .map(|(name, _)| name.clone());
+
+        let axii = self
+            .axis_bindings
+            .iter()
+            .filter(move |(_, axis)| input == CapturedInput::Axis(**axis))
+            .map(|(name, _)| name.clone());
+
+        let virtual_axii = self
+            .virtual_axis_bindings
+            .iter()
+            .filter(move |(_, axis)| match axis.source {
+                AxisSource::Axis(axis) => input == CapturedInput::Axis(axis),
+                AxisSource::Buttons { negative, positive } => [negative, positive]
+                    .into_iter()
+                    .any(|button| input == CapturedInput::Button(button)),
+            })
+            .map(|(name, _)| name.clone());
+
+        let axii2 = self
+            .axis2_bindings
+            .iter()
+            .filter(move |(_, axis)| match axis.source {
+                Axis2Source::Axii { x, y } => [x, y]
+                    .into_iter()
+                    .any(|axis| input == CapturedInput::Axis(axis)),
+                Axis2Source::Buttons {
+                    up,
+                    down,
+                    left,
+                    right,
+                } => [up, down, left, right]
+                    .into_iter()
+                    .any(|button| input == CapturedInput::Button(button)),
+            })
+            .map(|(name, _)| name.clone());
+
+        let actions = self.action_maps.iter().flat_map(move |(context, map)| {
+            map.actions
+                .iter()
+                .filter(move |(_, bindings)| {
+                    bindings
+                        .iter()
+                        .any(|binding| input == CapturedInput::Button(binding.button))
+                })
+                .map(move |(name, _)| format!("{context}/{name}"))
+        });
+
+        keys.chain(axii)
+            .chain(virtual_axii)
+            .chain(axii2)
+            .chain(actions)
+    }
+}
+
+impl RebindTarget {
+    // Name of the target as it would appear in a list of conflicts
+    fn conflict_name(&self) -> String {
+        match self {
+            RebindTarget::Button(name) | RebindTarget::Axis(name) => name.clone(),
+            RebindTarget::Action { context, name } => format!("{context}/{name}"),
+        }
+    }
+}
+
+impl Input {
+    /// Start listening for the next input to rebind a shared binding.
+    /// The result can be fetched using [Input::take_capture] once the user pressed a button or moved an axis.
+    pub fn listen(&mut self, target: RebindTarget) {
+        log::debug!("Listening for the next input to rebind {target:?}");
+        self.captured = None;
+        self.capturing = Some((target, None));
+    }
+
+    /// Start listening for the next input of a specific player to rebind one of their bindings.
+    pub fn listen_for(&mut self, player: usize, target: RebindTarget) {
+        log::debug!("Listening for the next input of player {player} to rebind {target:?}");
+        self.captured = None;
+        self.capturing = Some((target, Some(player)));
+    }
+
+    /// Check if we are still waiting for an input to capture.
+    pub fn is_listening(&self) -> bool {
+        self.capturing.is_some()
+    }
+
+    /// Stop listening for the next input without capturing anything.
+    pub fn cancel_listening(&mut self) {
+        self.capturing = None;
+    }
+
+    /// Take the result of the last input capture, if the user gave us an input.
+    pub fn take_capture(&mut self) -> Option<Capture> {
+        self.captured.take()
+    }
+
+    /// Get the names of every binding that uses an input, either within the shared bindings or the bindings of a player.
+    /// Actions are named "context/action".
+    pub fn conflicts(&self, input: CapturedInput, player: Option<usize>) -> Vec<String> {
+        let player = player.and_then(|player| self.player_bindings.get(&player));
+        let mut names = self
+            .bindings
+            .users(input)
+            .chain(
+                player
+                    .into_iter()
+                    .flat_map(|bindings| bindings.users(input)),
+            )
+            .collect::<Vec<_>>();
+        names.sort();
+        names.dedup();
+        names
+    }
+
+    /// Rebind the target of a capture to the captured input.
+    /// Conflicting bindings are left untouched.
+    pub fn apply_capture(&mut self, capture: &Capture) {
+        log::debug!("Rebinding {:?} to {:?}", capture.target, capture.input);
+
+        let bindings = match capture.player {
+            Some(player) => self.player_bindings.entry(player).or_default(),
+            None => &mut self.bindings,
+        };
+
+        match (&capture.target, capture.input) {
+            (RebindTarget::Button(name), CapturedInput::Button(button)) => {
+                bindings.key_bindings.insert(name.clone(), button);
+            }
+
+            (RebindTarget::Axis(name), CapturedInput::Axis(axis)) => {
+                bindings.axis_bindings.insert(name.clone(), axis);
+            }
+
+            // Keep the modifiers and trigger of the binding that we replace
+            (RebindTarget::Action { context, name }, CapturedInput::Button(button)) => {
+                let actions = bindings
+                    .action_maps
+                    .entry(context.clone())
+                    .or_default()
+                    .actions
+                    .entry(name.clone())
+                    .or_default();
+
+                match actions
+                    .iter_mut()
+                    .find(|binding| same_device(binding.button, button))
+                {
+                    Some(binding) => binding.button = button,
+                    None => actions.push(ActionBinding::new(button)),
+                }
+            }
+
+            (target, input) => log::warn!("Cannot rebind {target:?} to {input:?}"),
+        }
+    }
+
+    // Capture a pressed button if we are listening for one
+    pub(crate) fn capture_button(&mut self, button: Button, device: InputDevice) {
+        if let Some((RebindTarget::Button(_) | RebindTarget::Action { .. }, _)) = self.capturing {
+            self.capture(CapturedInput::Button(button), device);
+        }
+    }
+
+    // Capture a moved axis if we are listening for one
+    pub(crate) fn capture_axis(&mut self, axis: Axis, value: f32, device: InputDevice) {
+        if value.abs() < CAPTURE_AXIS_THRESHOLD {
+            return;
+        }
+
+        if let Some((RebindTarget::Axis(_), _)) = self.capturing {
+            self.capture(CapturedInput::Axis(axis), device);
+        }
+    }
+
+    // Finish listening if the device belongs to the player that we listen to
+    fn capture(&mut self, input: CapturedInput, device: InputDevice) {
+        let Some((_, player)) = self.capturing else {
+            return;
+        };
+
+        if !self.used_by(device, player) {
+            return;
+        }
+
+        let (target, player) = self.capturing.take().unwrap();
+        let name = target.conflict_name();
+        let mut conflicts = self.conflicts(input, player);
+        conflicts.retain(|conflict| *conflict != name);
+
+        log::debug!("Captured {input:?} to rebind {target:?}");
+        self.captured = Some(Capture {
+            target,
+            player,
+            input,
+            conflicts,
+        });
+    }
+}
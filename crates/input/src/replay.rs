@@ -0,0 +1,260 @@
+use crate::{GamepadId, Input, InputDevice, InputEvent};
+use serde::{Deserialize, Serialize};
+use std::{
+    io::BufWriter,
+    path::Path,
+    time::{Duration, Instant},
+};
+use utils::{FileManager, FileType};
+
+/// An input event that was recorded during a specific frame and tick.
+/// The indices and the timestamp are relative to the start of the recording.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RecordedEvent {
+    /// Frame during which the event was handled.
+    pub frame: u64,
+
+    /// Tick count when the event was handled.
+    pub tick: u64,
+
+    /// Time when the event was handled.
+    #[serde(default)]
+    pub time: Duration,
+
+    /// The recorded event.
+    pub event: InputEvent,
+}
+
+/// Every input event that was handled by the input system during a recording.
+/// Recordings are stored as CBOR within the data directory.
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct InputRecording {
+    /// Gamepads that were already connected when the recording started.
+    pub gamepads: Vec<GamepadId>,
+
+    /// Number of frames that were recorded.
+    pub frames: u64,
+
+    /// Time at the end of every recorded frame. Empty for recordings without timestamps.
+    #[serde(default)]
+    pub times: Vec<Duration>,
+
+    /// Recorded events, sorted by frame.
+    pub events: Vec<RecordedEvent>,
+}
+
+impl InputRecording {
+    /// Save the recording into a file within the data directory.
+    pub fn save(&self, manager: &mut FileManager, path: impl AsRef<Path>) -> Option<()> {
+        let file = manager.write_file(&path, true, FileType::Data)?;
+        ciborium::ser::into_writer(self, BufWriter::new(file))
+            .map_err(|err| log::error!("Could not save input recording {:?}: {err}", path.as_ref()))
+            .ok()
+    }
+
+    /// Load a recording from a file within the data directory.
+    pub fn load(manager: &FileManager, path: impl AsRef<Path>) -> Option<Self> {
+        let reader = manager.read_file(&path, FileType::Data)?;
+        ciborium::de::from_reader(reader)
+            .map_err(|err| log::error!("Could not load input recording {:?}: {err}", path.as_ref()))
+            .ok()
+    }
+}
+
+// Recording that is currently being written
+pub(crate) struct Recorder {
+    start: Instant,
+    start_frame: u64,
+    start_tick: u64,
+    recording: InputRecording,
+}
+
+// Recording that is currently being replayed
+pub(crate) struct Replay {
+    start: Instant,
+    start_frame: u64,
+    start_tick: u64,
+    recording: InputRecording,
+    cursor: usize,
+    desync: bool,
+}
+
+impl Input {
+    /// Start recording every input event handled by the input system.
+    /// The gamepads that are already connected are stored within the recording.
+    pub fn start_recording(&mut self) {
+        log::debug!("Started recording input events at frame {}", self.frame);
+        let mut gamepads = self.gamepads();
+        gamepads.sort_by_key(|id| usize::from(*id));
+
+        self.recorder = Some(Recorder {
+            start: Instant::now(),
+            start_frame: self.frame,
+            start_tick: self.tick,
+            recording: InputRecording {
+                gamepads,
+                ..Default::default()
+            },
+        });
+    }
+
+    /// Stop the current recording and return it.
+    pub fn stop_recording(&mut self) -> Option<InputRecording> {
+        let mut recorder = self.recorder.take()?;
+        recorder.recording.frames = self.frame - recorder.start_frame;
+        log::debug!(
+            "Stopped recording input events after {} frames",
+            recorder.recording.frames
+        );
+        Some(recorder.recording)
+    }
+
+    /// Check if we are currently recording input events.
+    pub fn is_recording(&self) -> bool {
+        self.recorder.is_some()
+    }
+
+    /// Start replaying a recording. Live devices are ignored until the replay finishes or gets stopped.
+    /// The state of the keyboard, mouse and gamepads is reset so the replay starts from a clean slate.
+    pub fn start_replay(&mut self, recording: InputRecording) {
+        log::debug!(
+            "Started replaying {} input events over {} frames",
+            recording.events.len(),
+            recording.frames
+        );
+
+        self.reset_devices();
+        for id in recording.gamepads.iter() {
+            self.connect(*id);
+        }
+
+        self.replay = Some(Replay {
+            start: Instant::now(),
+            start_frame: self.frame,
+            start_tick: self.tick,
+            recording,
+            cursor: 0,
+            desync: false,
+        });
+    }
+
+    /// Stop the current replay and go back to using the live devices.
+    pub fn stop_replay(&mut self) -> Option<InputRecording> {
+        let replay = self.replay.take()?;
+        log::debug!("Stopped replaying input events");
+
+        // Live gamepads must be connected again since the replay removed them
+        self.reset_devices();
+        let connected = self.gilrs.gamepads().map(|(id, _)| id).collect::<Vec<_>>();
+        for id in connected {
+            self.connect(id);
+        }
+
+        Some(replay.recording)
+    }
+
+    /// Check if we are currently replaying a recording.
+    pub fn is_replaying(&self) -> bool {
+        self.replay.is_some()
+    }
+
+    // Clear the state of every device and forget about the connected gamepads
+    fn reset_devices(&mut self) {
+        self.keys.clear();
+        self.axii.clear();
+        self.presses.clear();
+        self.gamepads.clear();
+        self.players
+            .retain(|device, _| !matches!(device, InputDevice::Gamepad(_)));
+    }
+
+    // Handle an event coming from a live device (ignored while replaying or while relative motion is suspended)
+    pub(crate) fn handle_live(&mut self, event: InputEvent) {
+        if self.replay.is_some() || self.is_suspended_motion(&event) {
+            return;
+        }
+
+        self.now = Instant::now();
+        if let Some(recorder) = &mut self.recorder {
+            recorder.recording.events.push(RecordedEvent {
+                frame: self.frame - recorder.start_frame,
+                tick: self.tick.saturating_sub(recorder.start_tick),
+                time: self.now.saturating_duration_since(recorder.start),
+                event: event.clone(),
+            });
+        }
+
+        self.apply(event);
+    }
+
+    // Apply the recorded events of the current frame that come from winit or gilrs
+    // Gamepad events are replayed at the same point of the frame as live gilrs events
+    pub(crate) fn handle_replay(&mut self, gamepad: bool) {
+        let Some(replay) = &mut self.replay else {
+            return;
+        };
+
+        let frame = self.frame - replay.start_frame;
+        let tick = self.tick.saturating_sub(replay.start_tick);
+        let mut events = Vec::new();
+
+        while let Some(recorded) = replay.recording.events.get(replay.cursor) {
+            if recorded.frame > frame || recorded.event.is_gamepad() != gamepad {
+                break;
+            }
+
+            // Only warn once since every event after a desync is most likely off as well
+            if !replay.desync && recorded.frame == frame && recorded.tick != tick {
+                replay.desync = true;
+                log::warn!(
+                    "Input replay desync at frame {frame}: recorded at tick {}, replayed at tick {tick}",
+                    recorded.tick
+                );
+            }
+
+            // Recordings without timestamps can only be replayed using the current time
+            let time = if replay.recording.times.is_empty() {
+                Instant::now()
+            } else {
+                replay.start + recorded.time
+            };
+
+            events.push((time, recorded.event.clone()));
+            replay.cursor += 1;
+        }
+
+        // Gamepad events are the last events of their frame, so the replay ends after them
+        let finished = gamepad && frame >= replay.recording.frames;
+
+        for (time, event) in events {
+            self.now = time;
+            self.apply(event);
+        }
+
+        if finished {
+            log::debug!("Finished replaying input events");
+            self.stop_replay();
+        }
+    }
+    // Get the time of the current frame update. Recorded frames reuse the time they were recorded at
+    pub(crate) fn frame_time(&mut self) -> Instant {
+        let now = Instant::now();
+
+        if let Some(replay) = &self.replay {
+            let frame = (self.frame - replay.start_frame) as usize;
+            return replay
+                .recording
+                .times
+                .get(frame)
+                .map(|time| replay.start + *time)
+                .unwrap_or(now);
+        }
+
+        if let Some(recorder) = &mut self.recorder {
+            let time = now.saturating_duration_since(recorder.start);
+            recorder.recording.times.push(time);
+        }
+
+        now
+    }
+}
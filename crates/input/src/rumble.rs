@@ -0,0 +1,262 @@
+use crate::{GamepadId, Input, InputDevice};
+use gilrs::ff::{
+    BaseEffect, BaseEffectType, DistanceModel, Effect, EffectBuilder, Envelope, Replay, Ticks,
+};
+use std::time::{Duration, Instant};
+
+/// Fade in / fade out of a rumble effect.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct RumbleEnvelope {
+    /// Duration of the fade in, in seconds.
+    pub attack: f32,
+
+    /// Strength multiplier at the start of the fade in (0 - 1).
+    pub attack_level: f32,
+
+    /// Duration of the fade out, in seconds.
+    pub fade: f32,
+
+    /// Strength multiplier at the end of the fade out (0 - 1).
+    pub fade_level: f32,
+}
+
+/// World position of a rumble effect, which gets weaker the further it is from the player.
+/// The position of the player is set using [Input::set_rumble_listener].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RumbleEmitter {
+    /// Position of the effect in world space.
+    pub position: vek::Vec3<f32>,
+
+    /// Distance at which the effect starts to get weaker.
+    pub radius: f32,
+
+    /// Distance at which the effect cannot be felt anymore.
+    pub max_distance: f32,
+}
+
+/// A rumble / haptic effect that can be played on the gamepads of a player.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Rumble {
+    /// Strength of the strong (low frequency) motor (0 - 1).
+    pub strong: f32,
+
+    /// Strength of the weak (high frequency) motor (0 - 1).
+    pub weak: f32,
+
+    /// Duration of the effect, in seconds.
+    pub duration: f32,
+
+    /// Fade in / fade out of the effect.
+    pub envelope: Option<RumbleEnvelope>,
+
+    /// Position of the effect, or none if the effect is felt the same everywhere.
+    pub emitter: Option<RumbleEmitter>,
+}
+
+impl Rumble {
+    /// Create a rumble effect using the strength of both motors and a duration in seconds.
+    pub fn new(strong: f32, weak: f32, duration: f32) -> Self {
+        Self {
+            strong,
+            weak,
+            duration,
+            envelope: None,
+            emitter: None,
+        }
+    }
+
+    /// Create a short rumble that is used for impacts (like physics contacts).
+    /// The intensity of the impact should be between 0 and 1.
+    pub fn impact(intensity: f32) -> Self {
+        let intensity = intensity.clamp(0.0, 1.0);
+        Self::new(intensity, intensity * 0.5, 0.1 + intensity * 0.2).with_envelope(RumbleEnvelope {
+            fade: 0.1,
+            ..Default::default()
+        })
+    }
+
+    /// Fade the effect in and out.
+    pub fn with_envelope(mut self, envelope: RumbleEnvelope) -> Self {
+        self.envelope = Some(envelope);
+        self
+    }
+
+    /// Play the effect at a world position, making it weaker the further it is from the player.
+    pub fn at(mut self, position: vek::Vec3<f32>, radius: f32, max_distance: f32) -> Self {
+        self.emitter = Some(RumbleEmitter {
+            position,
+            radius,
+            max_distance,
+        });
+        self
+    }
+}
+
+/// Handle of a rumble effect that is currently playing, used to stop it early.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct RumbleId(u64);
+
+// A gilrs effect that must be kept alive until it finishes playing
+pub(crate) struct PlayingRumble {
+    effect: Effect,
+    end: Instant,
+}
+
+// Convert a duration in seconds to force feedback ticks
+fn ticks(seconds: f32) -> Ticks {
+    Ticks::from_ms((seconds.max(0.0) * 1000.0) as u32)
+}
+
+// Convert a strength between 0 and 1 to a motor magnitude
+fn magnitude(strength: f32) -> u16 {
+    (strength.clamp(0.0, 1.0) * u16::MAX as f32) as u16
+}
+
+impl Input {
+    /// Play a rumble effect on every gamepad that is assigned to a player.
+    /// Returns none if the player doesn't have any gamepad with force feedback support.
+    pub fn play_rumble(&mut self, player: usize, rumble: Rumble) -> Option<RumbleId> {
+        let gamepads = self
+            .devices(player)
+            .into_iter()
+            .filter_map(|device| match device {
+                InputDevice::Gamepad(id) => Some(id),
+                InputDevice::KeyboardMouse => None,
+            })
+            .collect::<Vec<_>>();
+        self.play_rumble_on(&gamepads, rumble)
+    }
+
+    /// Play a rumble effect on specific gamepads.
+    /// Returns none if none of the gamepads support force feedback, if rumble is disabled, or while replaying a recording.
+    pub fn play_rumble_on(&mut self, gamepads: &[GamepadId], rumble: Rumble) -> Option<RumbleId> {
+        if self.rumble_gain <= 0.0 || self.replay.is_some() {
+            return None;
+        }
+
+        let gamepads = gamepads
+            .iter()
+            .copied()
+            .filter(|id| {
+                self.gilrs
+                    .connected_gamepad(*id)
+                    .is_some_and(|gamepad| gamepad.is_ff_supported())
+            })
+            .collect::<Vec<_>>();
+
+        if gamepads.is_empty() {
+            return None;
+        }
+
+        let envelope = rumble
+            .envelope
+            .map(|envelope| Envelope {
+                attack_length: ticks(envelope.attack),
+                attack_level: envelope.attack_level,
+                fade_length: ticks(envelope.fade),
+                fade_level: envelope.fade_level,
+            })
+            .unwrap_or_default();
+
+        let scheduling = Replay {
+            play_for: ticks(rumble.duration),
+            ..Default::default()
+        };
+
+        let mut builder = EffectBuilder::new();
+        for kind in [
+            BaseEffectType::Strong {
+                magnitude: magnitude(rumble.strong),
+            },
+            BaseEffectType::Weak {
+                magnitude: magnitude(rumble.weak),
+            },
+        ] {
+            builder.add_effect(BaseEffect {
+                kind,
+                scheduling,
+                envelope,
+            });
+        }
+
+        // Positional effects are attenuated using the listener position of each gamepad
+        if let Some(emitter) = rumble.emitter {
+            builder
+                .distance_model(DistanceModel::LinearClamped {
+                    ref_distance: emitter.radius,
+                    rolloff_factor: 1.0,
+                    max_distance: emitter.max_distance,
+                })
+                .position(emitter.position.into_array());
+        }
+
+        let effect = builder
+            .gamepads(&gamepads)
+            .gain(self.rumble_gain)
+            .finish(&mut self.gilrs)
+            .and_then(|effect| effect.play().map(|_| effect))
+            .map_err(|err| log::warn!("Could not play rumble effect: {err}"))
+            .ok()?;
+
+        let id = RumbleId(self.rumble_counter);
+        self.rumble_counter += 1;
+        self.rumbles.insert(
+            id,
+            PlayingRumble {
+                effect,
+                end: Instant::now() + Duration::from_secs_f32(rumble.duration.max(0.0)),
+            },
+        );
+        Some(id)
+    }
+
+    /// Stop a rumble effect before it finishes playing.
+    pub fn stop_rumble(&mut self, id: RumbleId) {
+        if let Some(rumble) = self.rumbles.remove(&id) {
+            if let Err(err) = rumble.effect.stop() {
+                log::warn!("Could not stop rumble effect: {err}");
+            }
+        }
+    }
+
+    /// Stop every rumble effect that is currently playing.
+    pub fn stop_all_rumble(&mut self) {
+        let ids = self.rumbles.keys().copied().collect::<Vec<_>>();
+        for id in ids {
+            self.stop_rumble(id);
+        }
+    }
+
+    /// Set the strength multiplier of every rumble effect (0 disables rumble completely).
+    pub fn set_rumble_gain(&mut self, gain: f32) {
+        self.rumble_gain = gain.max(0.0);
+        if self.rumble_gain == 0.0 {
+            self.stop_all_rumble();
+        }
+    }
+
+    /// Set the world position of a player, used by the rumble effects that have an emitter.
+    pub fn set_rumble_listener(&mut self, player: usize, position: vek::Vec3<f32>) {
+        for device in self.devices(player) {
+            let InputDevice::Gamepad(id) = device else {
+                continue;
+            };
+
+            let Some(gamepad) = self.gilrs.connected_gamepad(id) else {
+                continue;
+            };
+
+            if gamepad.is_ff_supported() {
+                if let Err(err) = gamepad.set_listener_position(position.into_array()) {
+                    log::warn!("Could not set the rumble listener position: {err}");
+                }
+            }
+        }
+    }
+
+    // Drop the rumble effects that finished playing
+    pub(crate) fn clean_rumble(&mut self) {
+        let now = Instant::now();
+        self.rumbles.retain(|_, rumble| rumble.end > now);
+    }
+}
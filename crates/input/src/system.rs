@@ -1,80 +1,227 @@
-use std::collections::hash_map::Entry;
+use std::{
+    num::NonZeroU32,
+    time::{Duration, Instant},
+};
 
-use crate::{Axis, Button, ButtonState, Input, MouseAxis};
+use crate::{
+    Axis, Button, ButtonState, Composition, Input, InputDevice, InputEvent, MouseAxis, TouchPhase,
+};
 use gilrs::PowerInfo;
-use winit::event::{DeviceEvent, ElementState};
+use utils::Time;
+use winit::event::{DeviceEvent, ElementState, Force, Ime};
 use world::{post_user, user, System, WindowEvent, World};
 
+impl Input {
+    // Create the input resource without any bindings, connected gamepads or assigned devices
+    pub(crate) fn new(gilrs: gilrs::Gilrs, clipboard: Option<arboard::Clipboard>) -> Self {
+        Self {
+            bindings: Default::default(),
+            keys: Default::default(),
+            axii: Default::default(),
+            gilrs,
+            gamepads: Default::default(),
+            players: Default::default(),
+            player_bindings: Default::default(),
+            connections: Default::default(),
+            auto_assign: true,
+            contexts: vec![crate::DEFAULT_CONTEXT.to_owned()],
+            presses: Default::default(),
+            time: Instant::now(),
+            previous: Instant::now(),
+            now: Instant::now(),
+            renames: Default::default(),
+            capturing: None,
+            captured: None,
+            frame: 0,
+            tick: 0,
+            recorder: None,
+            replay: None,
+            text_focused: false,
+            text_events: Default::default(),
+            composition: None,
+            clipboard,
+            rumbles: Default::default(),
+            rumble_counter: 0,
+            rumble_gain: 1.0,
+            transitions: Default::default(),
+            buffer_duration: crate::DEFAULT_INPUT_BUFFER_DURATION,
+            tick_window: (Instant::now(), Instant::now()),
+            scheduled_ticks: 0,
+            tick_delta: Duration::ZERO,
+            touches: Default::default(),
+            gestures: Default::default(),
+            touch_mouse: true,
+            emulated: None,
+            cursor: Default::default(),
+            cursor_released: false,
+            focused: true,
+        }
+    }
+
+    // Advance the sticky button states and clear the per frame events at the end of every frame
+    pub(crate) fn advance_frame(&mut self) {
+        // Update the state of the keys/buttons
+        fn advance(state: &mut ButtonState) {
+            *state = match state {
+                crate::ButtonState::Pressed => ButtonState::Held,
+                crate::ButtonState::Released => ButtonState::None,
+                crate::ButtonState::Held => ButtonState::Held,
+                crate::ButtonState::None => ButtonState::None,
+            };
+        }
+
+        self.keys.values_mut().for_each(advance);
+        for gamepad in self.gamepads.values_mut() {
+            gamepad.buttons.values_mut().for_each(advance);
+        }
+
+        // Reset the mouse scroll and motion deltas (since winit doesn't reset them for us)
+        for axis in [MouseAxis::ScrollDelta, MouseAxis::DeltaX, MouseAxis::DeltaY] {
+            if let Some(data) = self.axii.get_mut(&Axis::Mouse(axis)) {
+                *data = 0f32;
+            }
+        }
+
+        // Connection events are only kept for a single frame
+        self.connections.clear();
+
+        // Text events are only kept for a single frame as well
+        self.text_events.clear();
+
+        // Rumble effects are kept alive until they finish playing
+        self.clean_rumble();
+
+        // Ended touches and gestures are only kept for a single frame
+        self.clean_touches();
+
+        // Old button transitions are only kept for a limited amount of time
+        self.prune_transitions();
+
+        // Timings of the current and last update for hold triggers
+        self.previous = self.time;
+        self.time = self.frame_time();
+        self.now = self.time;
+    }
+}
+
 // Init event (called once at the start of program)
 fn init(world: &mut World) {
-    world.insert(Input {
-        bindings: Default::default(),
-        keys: Default::default(),
-        axii: Default::default(),
-        gilrs: gilrs::Gilrs::new().unwrap(),
-        gamepad: None,
-    });
+    let clipboard = arboard::Clipboard::new()
+        .map_err(|err| log::warn!("Could not access the clipboard: {err}"))
+        .ok();
+    let mut input = Input::new(gilrs::Gilrs::new().unwrap(), clipboard);
+
+    // The keyboard and mouse always start as the first player's devices
+    input.assign(InputDevice::KeyboardMouse, 0);
+
+    // Gamepads that were connected before we started
+    let connected = input.gilrs.gamepads().map(|(id, _)| id).collect::<Vec<_>>();
+    for id in connected {
+        input.connect(id);
+    }
+
+    world.insert(input);
+}
+
+// Get the current tick count (if the time resource is available)
+fn tick_count(world: &World) -> u64 {
+    world
+        .get::<Time>()
+        .map(|time| time.tick_count() as u64)
+        .unwrap_or_default()
+}
+
+// Get the number of ticks that will run at the start of the next frame and their duration
+fn scheduled_ticks(world: &World) -> (u32, Duration) {
+    world
+        .get::<Time>()
+        .map(|time| {
+            let count = time.ticks_to_execute().map(NonZeroU32::get);
+            (count.unwrap_or_default(), time.tick_delta())
+        })
+        .unwrap_or_default()
 }
 
 // Winit window event since it seems that DeviceEvent::Key is broken on other machines
 // TODO: Report bug
 fn window_event(world: &mut World, ev: &mut WindowEvent) {
-    fn handle_button_input(input: &mut Input, key: Button, state: ElementState) {
-        match input.keys.entry(key) {
-            Entry::Occupied(mut current) => {
-                // Check if the key is "down" (either pressed or held)
-                let down = matches!(*current.get(), ButtonState::Pressed | ButtonState::Held);
-
-                // If the key is pressed while it is currently down, it repeated itself, and we must ignore it
-                if down ^ (state == ElementState::Pressed) {
-                    current.insert(state.into());
-                }
-            }
-            Entry::Vacant(v) => {
-                v.insert(state.into());
-            }
-        }
-    }
-
-    let mut input = world.get_mut::<Input>().unwrap();
-
-    match ev {
+    let event = match ev {
         // Handles keyboard keys
         WindowEvent::KeyboardInput { input: key, .. } => {
-            if let Some(keycode) = key.virtual_keycode {
-                handle_button_input(&mut input, Button::Keyboard(keycode), key.state);
-            }
+            key.virtual_keycode.map(|keycode| InputEvent::Button {
+                button: Button::Keyboard(keycode),
+                pressed: key.state == ElementState::Pressed,
+            })
         }
 
         // Handles mouse buttons
-        WindowEvent::MouseInput { state, button, .. } => {
-            handle_button_input(&mut input, Button::Mouse(*button), *state);
+        WindowEvent::MouseInput { state, button, .. } => Some(InputEvent::Button {
+            button: Button::Mouse(*button),
+            pressed: *state == ElementState::Pressed,
+        }),
+
+        // Handles touch screens and pens
+        WindowEvent::Touch(touch) => Some(InputEvent::Touch {
+            id: touch.id,
+            phase: match touch.phase {
+                winit::event::TouchPhase::Started => TouchPhase::Started,
+                winit::event::TouchPhase::Moved => TouchPhase::Moved,
+                winit::event::TouchPhase::Ended => TouchPhase::Ended,
+                winit::event::TouchPhase::Cancelled => TouchPhase::Cancelled,
+            },
+            x: touch.location.x as f32,
+            y: touch.location.y as f32,
+            pressure: touch.force.map(|force| force.normalized() as f32),
+            altitude: match touch.force {
+                Some(Force::Calibrated {
+                    altitude_angle: Some(angle),
+                    ..
+                }) => Some(angle as f32),
+                _ => None,
+            },
+        }),
+
+        // Handles typed characters and IME composition
+        WindowEvent::ReceivedCharacter(char) => Some(InputEvent::Character(*char)),
+        WindowEvent::Ime(Ime::Preedit(text, cursor)) => {
+            Some(InputEvent::Composition(Composition {
+                text: text.clone(),
+                cursor: *cursor,
+            }))
+        }
+        WindowEvent::Ime(Ime::Commit(text)) => Some(InputEvent::Commit(text.clone())),
+        WindowEvent::Ime(Ime::Disabled) => Some(InputEvent::Composition(Composition {
+            text: String::new(),
+            cursor: None,
+        })),
+
+        // The cursor is freed while the window is unfocused (not an input event, so it isn't recorded)
+        WindowEvent::Focused(focused) => {
+            world.get_mut::<Input>().unwrap().focused = *focused;
+            None
         }
 
-        _ => {}
+        _ => None,
+    };
+
+    if let Some(event) = event {
+        let tick = tick_count(world);
+        let mut input = world.get_mut::<Input>().unwrap();
+        input.tick = tick;
+        input.handle_live(event);
     }
 }
 
 // Winit device event (called by handler when needed)
 fn device_event(world: &mut World, ev: &DeviceEvent) {
-    let mut input = world.get_mut::<Input>().unwrap();
-
-    match ev {
+    let event = match ev {
         // Update mouse position delta and summed  pos
         DeviceEvent::MouseMotion { delta } => {
             let delta = vek::Vec2::<f64>::from(*delta).as_::<f32>();
-            input.axii.insert(Axis::Mouse(MouseAxis::DeltaX), delta.x);
-            input.axii.insert(Axis::Mouse(MouseAxis::DeltaY), delta.y);
-            let x = input
-                .axii
-                .entry(Axis::Mouse(MouseAxis::PositionX))
-                .or_insert(0.0);
-            *x += delta.x;
-            let y = input
-                .axii
-                .entry(Axis::Mouse(MouseAxis::PositionY))
-                .or_insert(0.0);
-            *y += delta.y;
+            Some(InputEvent::MouseMotion {
+                x: delta.x,
+                y: delta.y,
+            })
         }
 
         // Update mouse wheel delta and summed value
@@ -84,47 +231,41 @@ fn device_event(world: &mut World, ev: &DeviceEvent) {
                 winit::event::MouseScrollDelta::PixelDelta(physical) => physical.x as f32,
             };
 
-            input
-                .axii
-                .insert(Axis::Mouse(MouseAxis::ScrollDelta), delta);
-            let scroll = input
-                .axii
-                .entry(Axis::Mouse(MouseAxis::Scroll))
-                .or_insert(0.0);
-            *scroll += delta;
+            Some(InputEvent::MouseWheel(delta))
         }
 
-        _ => {}
+        _ => None,
+    };
+
+    if let Some(event) = event {
+        let tick = tick_count(world);
+        let mut input = world.get_mut::<Input>().unwrap();
+        input.tick = tick;
+        input.handle_live(event);
     }
 }
 
+// Replays the recorded winit events of the current frame before the user systems run
+fn replay(world: &mut World) {
+    let tick = tick_count(world);
+    let mut input = world.get_mut::<Input>().unwrap();
+    input.tick = tick;
+    input.handle_replay(false);
+}
+
 // Update event that will change the state of the keyboard keys (some states are sticky while others are not sticky)
 // This will also read the state from gamepads using gilrs
 fn update(world: &mut World) {
+    let tick = tick_count(world);
+    let (ticks, delta) = scheduled_ticks(world);
     let mut input = world.get_mut::<Input>().unwrap();
-
-    // Update the state of the keys/buttons
-    for (_, state) in input.keys.iter_mut() {
-        *state = match state {
-            crate::ButtonState::Pressed => ButtonState::Held,
-            crate::ButtonState::Released => ButtonState::None,
-            crate::ButtonState::Held => ButtonState::Held,
-            crate::ButtonState::None => ButtonState::None,
-        };
-    }
-
-    // Reset the mouse scroll delta (since winit doesn't reset it for us)
-    if let Some(data) = input.axii.get_mut(&Axis::Mouse(MouseAxis::ScrollDelta)) {
-        *data = 0f32;
-    }
-
-    // Try to get the currently used gamepad
-    let gamepad = input
-        .gamepad
-        .and_then(|main| input.gilrs.connected_gamepad(main));
+    input.tick = tick;
+    input.scheduled_ticks = ticks;
+    input.tick_delta = delta;
+    input.advance_frame();
 
     // Report battery level if critical
-    if let Some(gamepad) = gamepad {
+    for (_, gamepad) in input.gilrs.gamepads() {
         let name = gamepad.name();
         let info = gamepad.power_info();
 
@@ -136,57 +277,49 @@ fn update(world: &mut World) {
         }
     }
 
-    // Update the gamepad axii and buttson
+    // Update the gamepad axii and buttons of every connected gamepad
     while let Some(event) = input.gilrs.next_event() {
-        // Skip if we already have a main controller and it isn't it
-        if input
-            .gamepad
-            .map(|main| main != event.id)
-            .unwrap_or_default()
-        {
-            return;
-        }
+        let id = event.id;
 
-        match event.event {
-            // Button pressed event
-            gilrs::EventType::ButtonPressed(button, _) => {
-                if button != gilrs::Button::Unknown {
-                    let state = input
-                        .keys
-                        .entry(Button::Gamepad(button))
-                        .or_insert(ButtonState::Pressed);
-                    *state = ButtonState::Pressed;
-                }
-            }
+        let event = match event.event {
+            gilrs::EventType::Connected => InputEvent::GamepadConnected(id),
+            gilrs::EventType::Disconnected => InputEvent::GamepadDisconnected(id),
 
-            // Button released event
-            gilrs::EventType::ButtonReleased(button, _) => {
-                if button != gilrs::Button::Unknown {
-                    let state = input
-                        .keys
-                        .entry(Button::Gamepad(button))
-                        .or_insert(ButtonState::Released);
-                    *state = ButtonState::Released;
+            gilrs::EventType::ButtonPressed(button, _) if button != gilrs::Button::Unknown => {
+                InputEvent::GamepadButton {
+                    id,
+                    button,
+                    pressed: true,
                 }
             }
 
-            // Axis changed event
-            gilrs::EventType::AxisChanged(axis, value, _) => {
-                if axis != gilrs::Axis::Unknown {
-                    input.axii.insert(Axis::Gamepad(axis), value);
+            gilrs::EventType::ButtonReleased(button, _) if button != gilrs::Button::Unknown => {
+                InputEvent::GamepadButton {
+                    id,
+                    button,
+                    pressed: false,
                 }
             }
 
-            // Add the main gamepad controller
-            gilrs::EventType::Connected => {
-                input.gamepad.get_or_insert(event.id);
+            gilrs::EventType::AxisChanged(axis, value, _) if axis != gilrs::Axis::Unknown => {
+                InputEvent::GamepadAxis { id, axis, value }
             }
 
-            // Remove the main gamepad controller
-            gilrs::EventType::Disconnected => input.gamepad = None,
-            _ => (),
-        }
+            _ => continue,
+        };
+
+        input.handle_live(event);
     }
+
+    // Recorded gamepad events are replayed at the same point as live ones
+    input.handle_replay(true);
+    input.frame += 1;
+}
+
+// Tick event that gives each tick the button transitions that happened since the last tick
+fn tick(world: &mut World) {
+    let mut input = world.get_mut::<Input>().unwrap();
+    input.advance_tick_window();
 }
 
 // This system will automatically insert the input resource and update it each frame using the window events
@@ -195,5 +328,7 @@ pub fn system(system: &mut System) {
     system.insert_init(init).before(user);
     system.insert_device(device_event).before(user);
     system.insert_window(window_event).before(user);
+    system.insert_update(replay).before(user);
     system.insert_update(update).after(post_user);
+    system.insert_tick(tick).before(user);
 }
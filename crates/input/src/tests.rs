@@ -0,0 +1,604 @@
+// Create an input resource that is not connected to any window or gamepad
+#[cfg(test)]
+fn input() -> crate::Input {
+    let gilrs = match gilrs::Gilrs::new() {
+        Ok(gilrs) | Err(gilrs::Error::NotImplemented(gilrs)) => gilrs,
+        Err(err) => panic!("Could not create the gilrs context: {err}"),
+    };
+
+    let mut input = crate::Input::new(gilrs, None);
+    input.assign(crate::InputDevice::KeyboardMouse, 0);
+    input
+}
+
+#[cfg(test)]
+mod action {
+    use super::input;
+    use crate::{ActionBinding, ButtonState, InputEvent, KeyboardButton};
+
+    fn key(input: &mut crate::Input, key: KeyboardButton, pressed: bool) {
+        input.handle_live(InputEvent::Button {
+            button: key.into(),
+            pressed,
+        });
+    }
+
+    #[test]
+    fn chords() {
+        let mut input = input();
+        input.bind_action("default", "back", ActionBinding::new(KeyboardButton::S));
+        input.bind_action(
+            "editor",
+            "save",
+            ActionBinding::new(KeyboardButton::S).with_modifier(KeyboardButton::LControl),
+        );
+        input.push_context("editor");
+
+        // Pressing S alone must not be hidden by the Ctrl+S chord
+        key(&mut input, KeyboardButton::S, true);
+        assert_eq!(input.get_action("back"), ButtonState::Pressed);
+        assert_eq!(input.get_action("save"), ButtonState::None);
+        key(&mut input, KeyboardButton::S, false);
+
+        // Once the modifier is held, the chord consumes the button
+        key(&mut input, KeyboardButton::LControl, true);
+        key(&mut input, KeyboardButton::S, true);
+        assert_eq!(input.get_action("save"), ButtonState::Pressed);
+        assert_eq!(input.get_action("back"), ButtonState::None);
+    }
+
+    #[test]
+    fn exclusive() {
+        let mut input = input();
+        input.bind_action("default", "jump", ActionBinding::new(KeyboardButton::Space));
+        input.bind_action(
+            "menu",
+            "confirm",
+            ActionBinding::new(KeyboardButton::Return),
+        );
+        input.set_exclusive("menu", true);
+
+        key(&mut input, KeyboardButton::Space, true);
+        assert_eq!(input.get_action("jump"), ButtonState::Pressed);
+        input.push_context("menu");
+        assert_eq!(input.get_action("jump"), ButtonState::None);
+        input.pop_context();
+        assert_eq!(input.get_action("jump"), ButtonState::Pressed);
+    }
+
+    #[test]
+    fn players() {
+        let mut input = input();
+        input.bind_action(
+            "default",
+            "fire",
+            ActionBinding::new(KeyboardButton::F).double_tap(10.0),
+        );
+
+        // Presses are only seen by the player that owns the device
+        key(&mut input, KeyboardButton::F, true);
+        key(&mut input, KeyboardButton::F, false);
+        key(&mut input, KeyboardButton::F, true);
+        assert_eq!(input.get_action_for(0, "fire"), ButtonState::Pressed);
+        assert_eq!(input.get_action_for(1, "fire"), ButtonState::None);
+        assert_eq!(input.get_action("fire"), ButtonState::Pressed);
+    }
+}
+
+#[cfg(test)]
+mod composite {
+    use super::input;
+    use crate::{AxisSettings, DeadZone, InputEvent, KeyboardButton, ResponseCurve, VirtualAxis2};
+    use vek::Vec2;
+
+    fn close(a: Vec2<f32>, b: Vec2<f32>) -> bool {
+        a.distance(b) < 1e-5
+    }
+
+    #[test]
+    fn axial_dead_zone() {
+        let settings = AxisSettings {
+            dead_zone: DeadZone::Axial(0.2),
+            ..Default::default()
+        };
+
+        assert_eq!(settings.apply(0.1), 0.0);
+        assert_eq!(settings.apply(-0.2), 0.0);
+        assert!((settings.apply(0.6) - 0.5).abs() < 1e-5);
+        assert!((settings.apply(-1.0) + 1.0).abs() < 1e-5);
+
+        // Each component is handled separately, so small components snap to zero
+        let value = settings.apply2(Vec2::new(0.1, 0.6));
+        assert!(close(value, Vec2::new(0.0, 0.5)));
+    }
+
+    #[test]
+    fn radial_dead_zone() {
+        let settings = AxisSettings {
+            dead_zone: DeadZone::Radial(0.2),
+            ..Default::default()
+        };
+
+        assert_eq!(settings.apply2(Vec2::new(0.1, 0.1)), Vec2::zero());
+        assert_eq!(settings.apply2(Vec2::zero()), Vec2::zero());
+
+        // The direction is kept intact, only the length is rescaled
+        let value = settings.apply2(Vec2::new(0.1, 0.6));
+        let expected = Vec2::new(0.1f32, 0.6).normalized() * ((0.37f32.sqrt() - 0.2) / 0.8);
+        assert!(close(value, expected));
+        assert!(close(
+            settings.apply2(Vec2::new(0.0, 1.0)),
+            Vec2::new(0.0, 1.0)
+        ));
+    }
+
+    #[test]
+    fn curves() {
+        let settings = AxisSettings {
+            curve: ResponseCurve::Exponential(2.0),
+            ..Default::default()
+        };
+
+        assert!((settings.apply(0.5) - 0.25).abs() < 1e-5);
+        assert!((settings.apply(-0.5) + 0.25).abs() < 1e-5);
+        assert_eq!(settings.apply(0.0), 0.0);
+
+        // Zero must never turn into one, even with a zero exponent
+        for power in [0.0, 1.0, 2.0] {
+            let settings = AxisSettings {
+                curve: ResponseCurve::Exponential(power),
+                ..Default::default()
+            };
+
+            assert_eq!(settings.apply(0.0), 0.0);
+            assert_eq!(settings.apply(-0.0), 0.0);
+            assert_eq!(settings.apply2(Vec2::zero()), Vec2::zero());
+        }
+
+        let settings = AxisSettings {
+            dead_zone: DeadZone::Radial(0.1),
+            curve: ResponseCurve::Exponential(0.0),
+            ..Default::default()
+        };
+        assert_eq!(settings.apply2(Vec2::zero()), Vec2::zero());
+        assert_eq!(settings.apply2(Vec2::new(0.05, 0.0)), Vec2::zero());
+    }
+
+    #[test]
+    fn inversion() {
+        let settings = AxisSettings {
+            sensitivity: 2.0,
+            invert_x: true,
+            ..Default::default()
+        };
+
+        assert_eq!(settings.apply(0.5), -1.0);
+        assert_eq!(settings.apply2(Vec2::new(0.5, 0.25)), Vec2::new(-1.0, 0.5));
+
+        let settings = AxisSettings {
+            invert_y: true,
+            ..Default::default()
+        };
+        assert_eq!(settings.apply(0.5), 0.5);
+        assert_eq!(settings.apply2(Vec2::new(0.5, 0.25)), Vec2::new(0.5, -0.25));
+    }
+
+    #[test]
+    fn diagonals() {
+        let mut input = input();
+        input.bind_axis2(
+            "movement",
+            VirtualAxis2::buttons(
+                KeyboardButton::W,
+                KeyboardButton::S,
+                KeyboardButton::A,
+                KeyboardButton::D,
+            ),
+        );
+
+        for key in [KeyboardButton::W, KeyboardButton::D] {
+            input.handle_live(InputEvent::Button {
+                button: key.into(),
+                pressed: true,
+            });
+        }
+
+        // Diagonals are normalized so they are not faster than straight movement
+        let value = input.get_axis2("movement");
+        assert!((value.magnitude() - 1.0).abs() < 1e-5);
+        assert!(close(value, Vec2::new(1.0, 1.0).normalized()));
+
+        // Opposite buttons cancel each other out
+        input.handle_live(InputEvent::Button {
+            button: KeyboardButton::S.into(),
+            pressed: true,
+        });
+        assert!(close(input.get_axis2("movement"), Vec2::new(1.0, 0.0)));
+    }
+}
+
+#[cfg(test)]
+mod bindings {
+    use super::input;
+    use crate::{
+        ActionBinding, Button, CapturedInput, InputBindingsFile, InputUserBindings, KeyboardButton,
+        RebindTarget,
+    };
+    use utils::{FileManager, FileType, SerdeFormat};
+
+    // Bindings files are written within a temporary directory (absolute paths ignore the config directory)
+    // Each test uses its own directory since tests run in parallel and remove their directory once done
+    fn manager(test: &str) -> (FileManager, std::path::PathBuf) {
+        let name = format!("input-bindings-{test}-{}", std::process::id());
+        let directory = std::env::temp_dir().join(name);
+        std::fs::create_dir_all(&directory).unwrap();
+        (FileManager::new("cflake-engine", "input-tests"), directory)
+    }
+
+    #[test]
+    fn round_trip() {
+        let (mut manager, directory) = manager("round_trip");
+        let path = directory.join("round_trip.ron");
+
+        let mut saved = input();
+        saved.bind_button("jump", KeyboardButton::Space);
+        saved.bind_button_for(1, "jump", KeyboardButton::Return);
+        saved.bind_action(
+            "default",
+            "jump",
+            ActionBinding::new(KeyboardButton::Space).hold(0.5),
+        );
+        saved.save_bindings(&mut manager, &path).unwrap();
+
+        // The game renamed the binding since the file was saved
+        let mut loaded = input();
+        loaded.rename_binding(2, "jump", "leap");
+        loaded.load_bindings(&mut manager, &path).unwrap();
+
+        let leap = Button::Keyboard(KeyboardButton::Space);
+        assert_eq!(loaded.bindings.key_bindings.get("leap"), Some(&leap));
+        assert!(!loaded.bindings.key_bindings.contains_key("jump"));
+        assert_eq!(
+            loaded.player_bindings[&1].key_bindings.get("leap"),
+            Some(&Button::Keyboard(KeyboardButton::Return))
+        );
+        assert_eq!(
+            loaded.bindings.action_maps["default"].actions["leap"],
+            vec![ActionBinding::new(KeyboardButton::Space).hold(0.5)]
+        );
+
+        // Saving again stores the new version, so the renames are not applied twice
+        let file = loaded.as_bindings_file();
+        assert_eq!(file.version, 2);
+        loaded.save_bindings(&mut manager, &path).unwrap();
+        let mut again = input();
+        again.rename_binding(2, "jump", "leap");
+        again.rename_binding(2, "leap", "hop");
+        again.load_bindings(&mut manager, &path).unwrap();
+        assert!(again.bindings.key_bindings.contains_key("leap"));
+
+        std::fs::remove_dir_all(&directory).unwrap();
+    }
+
+    #[test]
+    fn format_zero() {
+        let (mut manager, directory) = manager("format_zero");
+        let path = directory.join("format_zero.ron");
+
+        // Older builds stored the raw user bindings without any version
+        let mut old = input();
+        old.bind_button("jump", KeyboardButton::Space);
+        let bindings: InputUserBindings = old.as_user_binding();
+        manager
+            .serialize_into_file(&bindings, &path, FileType::Global, SerdeFormat::RON)
+            .unwrap();
+
+        let mut loaded = input();
+        loaded.rename_binding(1, "jump", "leap");
+        loaded.load_bindings(&mut manager, &path).unwrap();
+        assert_eq!(
+            loaded.bindings.key_bindings.get("leap"),
+            Some(&Button::Keyboard(KeyboardButton::Space))
+        );
+
+        // Files written by newer builds are rejected
+        let file = InputBindingsFile {
+            format: crate::BINDINGS_FORMAT + 1,
+            ..Default::default()
+        };
+        manager
+            .serialize_into_file(&file, &path, FileType::Global, SerdeFormat::RON)
+            .unwrap();
+        assert!(input().load_bindings(&mut manager, &path).is_none());
+
+        std::fs::remove_dir_all(&directory).unwrap();
+    }
+
+    #[test]
+    fn conflicts() {
+        let mut input = input();
+        let space = Button::Keyboard(KeyboardButton::Space);
+        input.bind_button("jump", space);
+        input.bind_button("fire", KeyboardButton::F);
+        input.bind_button_for(1, "dash", space);
+        input.bind_action("menu", "confirm", ActionBinding::new(space));
+
+        let conflicts = input.conflicts(CapturedInput::Button(space), None);
+        assert_eq!(conflicts, ["jump", "menu/confirm"]);
+        let conflicts = input.conflicts(CapturedInput::Button(space), Some(1));
+        assert_eq!(conflicts, ["dash", "jump", "menu/confirm"]);
+
+        // The rebound binding itself is never reported as a conflict
+        input.listen(RebindTarget::Button("jump".to_owned()));
+        input.handle_live(crate::InputEvent::Button {
+            button: space,
+            pressed: true,
+        });
+        let capture = input.take_capture().unwrap();
+        assert_eq!(capture.input, CapturedInput::Button(space));
+        assert_eq!(capture.conflicts, ["menu/confirm"]);
+
+        // Rebinding an action keeps its trigger and only replaces the binding of the same device
+        input.bind_action(
+            "menu",
+            "back",
+            ActionBinding::new(KeyboardButton::Escape).hold(1.0),
+        );
+        input.listen(RebindTarget::Action {
+            context: "menu".to_owned(),
+            name: "back".to_owned(),
+        });
+        input.handle_live(crate::InputEvent::Button {
+            button: KeyboardButton::F.into(),
+            pressed: true,
+        });
+        let capture = input.take_capture().unwrap();
+        assert_eq!(capture.conflicts, ["fire"]);
+        input.apply_capture(&capture);
+        assert_eq!(
+            input.bindings.action_maps["menu"].actions["back"],
+            vec![ActionBinding::new(KeyboardButton::F).hold(1.0)]
+        );
+    }
+}
+
+#[cfg(test)]
+mod replay {
+    use super::input;
+    use crate::{ActionBinding, ButtonState, Input, InputEvent, KeyboardButton};
+    use std::time::Duration;
+
+    // Run a single frame the same way the input systems do, and return what the user systems saw
+    fn frame(input: &mut Input, events: Vec<InputEvent>) -> (ButtonState, bool) {
+        input.handle_replay(false);
+        for event in events {
+            input.handle_live(event);
+        }
+
+        let seen = (
+            input.get_action("charge"),
+            input.pressed_within(KeyboardButton::Space, Duration::from_millis(30)),
+        );
+
+        input.advance_frame();
+        input.handle_replay(true);
+        input.frame += 1;
+        seen
+    }
+
+    fn space(pressed: bool) -> Vec<InputEvent> {
+        vec![InputEvent::Button {
+            button: KeyboardButton::Space.into(),
+            pressed,
+        }]
+    }
+
+    #[test]
+    fn deterministic() {
+        let bind = |input: &mut Input| {
+            input.bind_action(
+                "default",
+                "charge",
+                ActionBinding::new(KeyboardButton::Space).hold(0.05),
+            );
+        };
+
+        let mut recorded = input();
+        bind(&mut recorded);
+        recorded.start_recording();
+
+        let mut live = Vec::new();
+        for index in 0..8 {
+            let events = match index {
+                1 => space(true),
+                6 => space(false),
+                _ => Vec::new(),
+            };
+
+            live.push(frame(&mut recorded, events));
+            std::thread::sleep(Duration::from_millis(20));
+        }
+
+        let recording = recorded.stop_recording().unwrap();
+        assert_eq!(recording.times.len(), 8);
+        assert!(live.iter().any(|(state, _)| *state == ButtonState::Pressed));
+
+        // Replaying without any delay must see the exact same states
+        let mut replayed = input();
+        bind(&mut replayed);
+        replayed.start_replay(recording);
+        let replay = (0..8)
+            .map(|_| frame(&mut replayed, Vec::new()))
+            .collect::<Vec<_>>();
+
+        assert_eq!(live, replay);
+
+        // The replay ends at the start of the frame that comes after the recording
+        frame(&mut replayed, Vec::new());
+        assert!(!replayed.is_replaying());
+    }
+}
+
+#[cfg(test)]
+mod buffer {
+    use super::input;
+    use crate::{ButtonState, InputEvent, KeyboardButton};
+    use std::time::Duration;
+
+    #[test]
+    fn catch_up_ticks() {
+        let mut input = input();
+        let start = input.now;
+        let ms = Duration::from_millis;
+
+        // A tap that happened during the first tick step and a press during the last one
+        for (time, button, pressed) in [
+            (ms(2), KeyboardButton::A, true),
+            (ms(5), KeyboardButton::A, false),
+            (ms(25), KeyboardButton::B, true),
+        ] {
+            input.now = start + time;
+            input.apply(InputEvent::Button {
+                button: button.into(),
+                pressed,
+            });
+        }
+
+        // Three ticks of 10ms are caught up within a single frame
+        input.scheduled_ticks = 3;
+        input.tick_delta = ms(10);
+        input.now = start + ms(30);
+
+        input.advance_tick_window();
+        assert_eq!(
+            input.get_button_tick(KeyboardButton::A),
+            ButtonState::Pressed
+        );
+        assert_eq!(input.get_button_tick(KeyboardButton::B), ButtonState::None);
+
+        input.advance_tick_window();
+        assert_eq!(input.get_button_tick(KeyboardButton::A), ButtonState::None);
+        assert_eq!(input.get_button_tick(KeyboardButton::B), ButtonState::None);
+
+        input.advance_tick_window();
+        assert_eq!(input.get_button_tick(KeyboardButton::A), ButtonState::None);
+        assert_eq!(
+            input.get_button_tick(KeyboardButton::B),
+            ButtonState::Pressed
+        );
+    }
+}
+
+#[cfg(test)]
+mod touch {
+    use super::input;
+    use crate::{ButtonState, Gesture, Input, InputEvent, MouseButton, TouchId, TouchPhase};
+    use std::time::Duration;
+
+    fn touch(input: &mut Input, id: TouchId, phase: TouchPhase, x: f32, y: f32) {
+        input.apply(InputEvent::Touch {
+            id,
+            phase,
+            x,
+            y,
+            pressure: None,
+            altitude: None,
+        });
+    }
+
+    #[test]
+    fn tap() {
+        let mut input = input();
+        touch(&mut input, 0, TouchPhase::Started, 100.0, 100.0);
+        assert_eq!(input.get_button(MouseButton::Left), ButtonState::Pressed);
+        touch(&mut input, 0, TouchPhase::Moved, 102.0, 101.0);
+        touch(&mut input, 0, TouchPhase::Ended, 102.0, 101.0);
+
+        let position = vek::Vec2::new(102.0, 101.0);
+        assert_eq!(input.gestures(), &[Gesture::Tap { position }]);
+        assert_eq!(input.get_button(MouseButton::Left), ButtonState::Released);
+
+        // Ended touches and gestures only last a single frame
+        input.advance_frame();
+        assert!(input.gestures().is_empty());
+        assert_eq!(input.touches().count(), 0);
+    }
+
+    #[test]
+    fn slow_touch() {
+        let mut input = input();
+        touch(&mut input, 0, TouchPhase::Started, 100.0, 100.0);
+        input.now += Duration::from_secs(1);
+        touch(&mut input, 0, TouchPhase::Ended, 100.0, 100.0);
+        assert!(input.gestures().is_empty());
+    }
+
+    #[test]
+    fn drag() {
+        let mut input = input();
+        touch(&mut input, 0, TouchPhase::Started, 100.0, 100.0);
+        touch(&mut input, 0, TouchPhase::Moved, 105.0, 100.0);
+        assert!(input.gestures().is_empty());
+
+        touch(&mut input, 0, TouchPhase::Moved, 150.0, 100.0);
+        let position = vek::Vec2::new(150.0, 100.0);
+        let delta = vek::Vec2::new(45.0, 0.0);
+        assert_eq!(input.gestures(), &[Gesture::Drag { position, delta }]);
+
+        // Moving away from the start prevents the touch from being a tap
+        touch(&mut input, 0, TouchPhase::Ended, 150.0, 100.0);
+        assert_eq!(input.gestures().len(), 1);
+    }
+
+    #[test]
+    fn pinch() {
+        let mut input = input();
+        touch(&mut input, 0, TouchPhase::Started, 100.0, 100.0);
+        touch(&mut input, 1, TouchPhase::Started, 200.0, 100.0);
+        touch(&mut input, 1, TouchPhase::Moved, 300.0, 100.0);
+
+        let center = vek::Vec2::new(200.0, 100.0);
+        assert_eq!(input.gestures(), &[Gesture::Pinch { center, scale: 2.0 }]);
+        input.advance_frame();
+
+        // Neither finger is a tap after the pinch, even though the first one never moved
+        touch(&mut input, 1, TouchPhase::Ended, 300.0, 100.0);
+        touch(&mut input, 0, TouchPhase::Ended, 100.0, 100.0);
+        assert!(input.gestures().is_empty());
+    }
+
+    #[test]
+    fn rotate() {
+        let mut input = input();
+        touch(&mut input, 0, TouchPhase::Started, 0.0, 0.0);
+        touch(&mut input, 1, TouchPhase::Started, 100.0, 0.0);
+        touch(&mut input, 1, TouchPhase::Moved, 0.0, 100.0);
+
+        let Some(Gesture::Rotate { angle, .. }) = input
+            .gestures()
+            .iter()
+            .find(|gesture| matches!(gesture, Gesture::Rotate { .. }))
+            .copied()
+        else {
+            panic!("Expected a rotate gesture");
+        };
+        assert!((angle - std::f32::consts::FRAC_PI_2).abs() < 1e-5);
+    }
+
+    #[test]
+    fn mouse_emulation() {
+        let mut input = input();
+        input.set_touch_mouse_emulation(false);
+        touch(&mut input, 0, TouchPhase::Started, 100.0, 100.0);
+        assert_eq!(input.get_button(MouseButton::Left), ButtonState::None);
+
+        // Only the first touch drives the left mouse button
+        input.set_touch_mouse_emulation(true);
+        touch(&mut input, 1, TouchPhase::Started, 200.0, 100.0);
+        touch(&mut input, 2, TouchPhase::Started, 300.0, 100.0);
+        touch(&mut input, 2, TouchPhase::Ended, 300.0, 100.0);
+        assert_eq!(input.get_button(MouseButton::Left), ButtonState::Pressed);
+        touch(&mut input, 1, TouchPhase::Ended, 200.0, 100.0);
+        assert_eq!(input.get_button(MouseButton::Left), ButtonState::Released);
+    }
+}
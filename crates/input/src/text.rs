@@ -0,0 +1,102 @@
+use crate::{Input, KeyboardButton};
+use serde::{Deserialize, Serialize};
+
+/// Text input event that is only sent while a text field is focused.
+/// These are only available during the frame after they were received.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TextEvent {
+    /// A printable character was typed.
+    Char(char),
+
+    /// The IME committed some composed text.
+    Commit(String),
+
+    /// A keyboard key was pressed, used for editing (backspace, enter, arrows, shortcuts).
+    Key(KeyboardButton),
+}
+
+/// Text that is currently being composed using the IME (not committed yet).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Composition {
+    /// The text that is being composed.
+    pub text: String,
+
+    /// Byte range of the cursor / selection within the composed text.
+    pub cursor: Option<(usize, usize)>,
+}
+
+impl Input {
+    /// Focus a text field, which enables the text input stream and the IME.
+    /// Keyboard keys are hidden from bindings and actions while a text field is focused.
+    pub fn focus_text(&mut self) {
+        self.text_focused = true;
+    }
+
+    /// Unfocus the text field, which gives the keyboard back to the bindings and actions.
+    pub fn unfocus_text(&mut self) {
+        self.text_focused = false;
+        self.text_events.clear();
+        self.composition = None;
+    }
+
+    /// Check if a text field is currently focused.
+    pub fn is_text_focused(&self) -> bool {
+        self.text_focused
+    }
+
+    /// Get the text input events that were received during the last frame.
+    pub fn text_events(&self) -> &[TextEvent] {
+        &self.text_events
+    }
+
+    /// Get the text that was typed or committed during the last frame.
+    pub fn text(&self) -> String {
+        self.text_events
+            .iter()
+            .filter_map(|event| match event {
+                TextEvent::Char(char) => Some(char.to_string()),
+                TextEvent::Commit(text) => Some(text.clone()),
+                TextEvent::Key(_) => None,
+            })
+            .collect()
+    }
+
+    /// Get the text that is currently being composed using the IME.
+    pub fn composition(&self) -> Option<&Composition> {
+        self.composition.as_ref()
+    }
+
+    /// Get the current text stored within the system clipboard.
+    pub fn clipboard_text(&mut self) -> Option<String> {
+        let clipboard = self.clipboard.as_mut()?;
+        clipboard
+            .get_text()
+            .map_err(|err| log::warn!("Could not read the clipboard: {err}"))
+            .ok()
+    }
+
+    /// Replace the text stored within the system clipboard.
+    pub fn set_clipboard_text(&mut self, text: impl Into<String>) -> Option<()> {
+        let clipboard = self.clipboard.as_mut()?;
+        clipboard
+            .set_text(text.into())
+            .map_err(|err| log::warn!("Could not write to the clipboard: {err}"))
+            .ok()
+    }
+
+    // Add a text event if a text field is focused
+    pub(crate) fn push_text(&mut self, event: TextEvent) {
+        if self.text_focused {
+            self.text_events.push(event);
+        }
+    }
+
+    // Replace the current IME composition (empty text means that the composition ended)
+    pub(crate) fn compose(&mut self, composition: Composition) {
+        if !self.text_focused || composition.text.is_empty() {
+            self.composition = None;
+        } else {
+            self.composition = Some(composition);
+        }
+    }
+}
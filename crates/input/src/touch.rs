@@ -0,0 +1,249 @@
+use crate::{Button, Input, InputEvent, MouseButton};
+use serde::{Deserialize, Serialize};
+use std::time::{Duration, Instant};
+
+/// Unique identifier of a finger / pen touching the screen, given by winit.
+pub type TouchId = u64;
+
+/// Maximum distance (in pixels) that a touch can move and still count as a tap.
+pub const TAP_DISTANCE: f32 = 10.0;
+
+/// Maximum duration of a touch that still counts as a tap.
+pub const TAP_DURATION: Duration = Duration::from_millis(300);
+
+/// Phase of a touch, same as winit's touch phase.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum TouchPhase {
+    /// The finger / pen started touching the screen.
+    Started,
+
+    /// The finger / pen moved on the screen.
+    Moved,
+
+    /// The finger / pen stopped touching the screen.
+    Ended,
+
+    /// The system cancelled the touch.
+    Cancelled,
+}
+
+/// A finger / pen that is touching the screen.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Touch {
+    /// Unique identifier of the touch.
+    pub id: TouchId,
+
+    /// Latest phase of the touch.
+    pub phase: TouchPhase,
+
+    /// Current position in physical pixels.
+    pub position: vek::Vec2<f32>,
+
+    /// Position in physical pixels when the touch started.
+    pub start: vek::Vec2<f32>,
+
+    /// Normalized pressure (0 - 1), if the device supports it.
+    pub pressure: Option<f32>,
+
+    /// Angle between the pen and the screen in radians (pi / 2 is perpendicular), if the device supports it.
+    pub altitude: Option<f32>,
+
+    /// When the touch started.
+    pub started: Instant,
+
+    /// Whether another touch was on the screen at some point during this touch (so it can't be a tap).
+    pub multi: bool,
+}
+
+/// Gesture that was recognized from the active touches.
+/// These are only available during the frame after they were recognized.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Gesture {
+    /// A short touch that barely moved.
+    Tap {
+        /// Position of the tap in physical pixels.
+        position: vek::Vec2<f32>,
+    },
+
+    /// A single touch that moved.
+    Drag {
+        /// Current position of the touch in physical pixels.
+        position: vek::Vec2<f32>,
+
+        /// Movement since the last drag event in physical pixels.
+        delta: vek::Vec2<f32>,
+    },
+
+    /// Two touches that moved closer or further apart.
+    Pinch {
+        /// Center of the two touches in physical pixels.
+        center: vek::Vec2<f32>,
+
+        /// Ratio between the current and last distance of the touches (above 1 when zooming in).
+        scale: f32,
+    },
+
+    /// Two touches that rotated around their center.
+    Rotate {
+        /// Center of the two touches in physical pixels.
+        center: vek::Vec2<f32>,
+
+        /// Rotation since the last rotate event in radians (counter clockwise).
+        angle: f32,
+    },
+}
+
+impl Input {
+    /// Get all the touches that are currently on the screen.
+    /// Touches that ended are kept until the end of the frame.
+    pub fn touches(&self) -> impl Iterator<Item = &Touch> {
+        self.touches.values()
+    }
+
+    /// Get a specific touch using its ID.
+    pub fn touch(&self, id: TouchId) -> Option<&Touch> {
+        self.touches.get(&id)
+    }
+
+    /// Get the gestures that were recognized during the last frame.
+    pub fn gestures(&self) -> &[Gesture] {
+        &self.gestures
+    }
+
+    /// Enable or disable the emulation of the left mouse button (and mouse motion) using the first touch.
+    /// Enabled by default. Egui already receives touches as pointer events, so this only matters for gameplay code.
+    pub fn set_touch_mouse_emulation(&mut self, enabled: bool) {
+        self.touch_mouse = enabled;
+    }
+
+    // Get the touches that are still on the screen
+    fn active_touches(&self) -> impl Iterator<Item = &Touch> {
+        self.touches
+            .values()
+            .filter(|touch| matches!(touch.phase, TouchPhase::Started | TouchPhase::Moved))
+    }
+
+    // Update the tracked touches and recognize gestures
+    pub(crate) fn apply_touch(
+        &mut self,
+        id: TouchId,
+        phase: TouchPhase,
+        position: vek::Vec2<f32>,
+        pressure: Option<f32>,
+        altitude: Option<f32>,
+    ) {
+        let previous = self.touches.get(&id).copied();
+        let others = self
+            .active_touches()
+            .filter(|touch| touch.id != id)
+            .copied()
+            .collect::<Vec<_>>();
+
+        let touch = Touch {
+            id,
+            phase,
+            position,
+            start: previous.map(|touch| touch.start).unwrap_or(position),
+            pressure,
+            altitude,
+            started: previous.map(|touch| touch.started).unwrap_or(self.now),
+            multi: previous.is_some_and(|touch| touch.multi) || !others.is_empty(),
+        };
+        self.touches.insert(id, touch);
+
+        // Touches that were on the screen at the same time are all part of a multi touch gesture
+        if touch.multi {
+            for other in others.iter() {
+                if let Some(other) = self.touches.get_mut(&other.id) {
+                    other.multi = true;
+                }
+            }
+        }
+
+        match (phase, previous, others.as_slice()) {
+            // A single touch that moved past the tap distance is dragged
+            (TouchPhase::Moved, Some(previous), []) => {
+                let delta = position - previous.position;
+                if touch.start.distance(position) > TAP_DISTANCE && delta != vek::Vec2::zero() {
+                    self.gestures.push(Gesture::Drag { position, delta });
+                }
+            }
+
+            // Two touches can be pinched and rotated around their center
+            (TouchPhase::Moved, Some(previous), [other]) => {
+                let before = previous.position - other.position;
+                let after = position - other.position;
+                let center = (position + other.position) / 2.0;
+
+                if before.magnitude() > 0.0 && after.magnitude() > 0.0 {
+                    let scale = after.magnitude() / before.magnitude();
+                    if scale != 1.0 {
+                        self.gestures.push(Gesture::Pinch { center, scale });
+                    }
+
+                    let angle = after.y.atan2(after.x) - before.y.atan2(before.x);
+                    let angle = (angle + std::f32::consts::PI).rem_euclid(std::f32::consts::TAU)
+                        - std::f32::consts::PI;
+                    if angle != 0.0 {
+                        self.gestures.push(Gesture::Rotate { center, angle });
+                    }
+                }
+            }
+
+            // A short touch that barely moved and was never part of a multi touch gesture is a tap
+            (TouchPhase::Ended, Some(_), [])
+                if !touch.multi
+                    && touch.start.distance(position) <= TAP_DISTANCE
+                    && self.now.saturating_duration_since(touch.started) <= TAP_DURATION =>
+            {
+                self.gestures.push(Gesture::Tap { position });
+            }
+
+            _ => {}
+        }
+
+        self.emulate_mouse(touch, previous);
+    }
+
+    // Make the first touch act like the left mouse button
+    fn emulate_mouse(&mut self, touch: Touch, previous: Option<Touch>) {
+        if !self.touch_mouse {
+            return;
+        }
+
+        match touch.phase {
+            TouchPhase::Started if self.emulated.is_none() => {
+                self.emulated = Some(touch.id);
+                self.apply(InputEvent::Button {
+                    button: Button::Mouse(MouseButton::Left),
+                    pressed: true,
+                });
+            }
+
+            TouchPhase::Moved if self.emulated == Some(touch.id) => {
+                let delta = touch.position - previous.map_or(touch.position, |p| p.position);
+                self.apply(InputEvent::MouseMotion {
+                    x: delta.x,
+                    y: delta.y,
+                });
+            }
+
+            TouchPhase::Ended | TouchPhase::Cancelled if self.emulated == Some(touch.id) => {
+                self.emulated = None;
+                self.apply(InputEvent::Button {
+                    button: Button::Mouse(MouseButton::Left),
+                    pressed: false,
+                });
+            }
+
+            _ => {}
+        }
+    }
+
+    // Forget about the touches that ended during the last frame
+    pub(crate) fn clean_touches(&mut self) {
+        self.touches
+            .retain(|_, touch| matches!(touch.phase, TouchPhase::Started | TouchPhase::Moved));
+        self.gestures.clear();
+    }
+}
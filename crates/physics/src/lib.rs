@@ -8,3 +8,4 @@ pub use resource::*;
 mod system;
 pub use system::*;
 pub use util::*;
+mod tests;
@@ -1,3 +1,5 @@
+use ahash::AHashMap;
+use ecs::SceneId;
 use rapier3d::prelude::*;
 use utils::Time;
 
@@ -89,3 +91,24 @@ impl Physics {
         );
     }
 }
+
+// Contains the physics worlds of the resident scenes that are simulated alongside the main scene
+// These get created automatically for the resident scenes that have physics enabled in their targets
+// The worlds follow their scenes when they get swapped with the main scene, so the rapier handles stay valid
+#[derive(Default)]
+pub struct ResidentPhysics {
+    pub(crate) worlds: AHashMap<SceneId, Physics>,
+    pub(crate) swaps: AHashMap<SceneId, u64>,
+}
+
+impl ResidentPhysics {
+    // Get the physics world of a simulated resident scene
+    pub fn get(&self, id: SceneId) -> Option<&Physics> {
+        self.worlds.get(&id)
+    }
+
+    // Get the physics world of a simulated resident scene mutably
+    pub fn get_mut(&mut self, id: SceneId) -> Option<&mut Physics> {
+        self.worlds.get_mut(&id)
+    }
+}
@@ -1,30 +1,58 @@
 use crate::{
     AngularVelocity, CapsuleCollider, CharacterController, CuboidCollider, GenericCollider,
-    MeshCollider, Physics, PhysicsSurface, RigidBody, SphereCollider, Velocity,
+    MeshCollider, Physics, PhysicsSurface, ResidentPhysics, RigidBody, SphereCollider, Velocity,
 };
 use crate::{
     CurrentTickedAngularVelocity, CurrentTickedVelocity, LastTickedAngularVelocity,
     LastTickedVelocity,
 };
+use ahash::AHashSet;
 use coords::{
     CurrentTickedPosition, CurrentTickedRotation, LastTickedPosition, LastTickedRotation,
 };
 use coords::{Position, Rotation};
-use ecs::{added, Component, Entity, Scene};
+use ecs::{added, contains, include_disabled, modified, Component, Entity, Scene, Scenes};
 use rapier3d::prelude::*;
 use utils::{Storage, Time};
 use world::{post_user, user, System, World};
 
 // This will spawn in the required rapier counter-part of the components
-fn pre_step_spawn_rapier_counterparts(physics: &mut Physics, scene: &mut Scene) {
+// Worlds that were just created or swapped in never saw the bodies that were added before, so they must be resynced
+pub(crate) fn pre_step_spawn_rapier_counterparts(
+    physics: &mut Physics,
+    scene: &mut Scene,
+    resync: bool,
+) {
     // Spawn in the RigidBody components (and keep track of the new entities)
-    let filter = added::<&RigidBody>();
-    let mut interpolated_entities = Vec::<Entity>::new();
-    for (entity, rigid_body) in scene.query_mut_with::<(&Entity, &mut RigidBody)>(filter) {
-        if rigid_body.handle.is_some() {
-            continue;
-        };
+    // Disabled entities must also get their counter-parts, otherwise they would never get them
+    // Bodies that were transferred from another scene still contain the handle of their previous world, so they get a new one
+    let filter = added::<&RigidBody>() & include_disabled();
+    let mut spawned = scene
+        .query_with::<&Entity>(filter)
+        .into_iter()
+        .copied()
+        .collect::<AHashSet<Entity>>();
+
+    // Resynced bodies that don't have a counter-part within this world get a new one
+    if resync {
+        let query = scene.query_with::<(&Entity, &RigidBody)>(include_disabled());
+        spawned.extend(
+            query
+                .into_iter()
+                .filter(|(_, rigid_body)| {
+                    !rigid_body
+                        .handle
+                        .is_some_and(|handle| physics.bodies.contains(handle))
+                })
+                .map(|(entity, _)| *entity),
+        );
+    }
 
+    let mut interpolated_entities = Vec::<Entity>::new();
+    for entity in spawned.iter() {
+        let mut entry = scene.entry_mut(*entity).unwrap();
+        let ticked = entry.contains::<LastTickedPosition>();
+        let rigid_body = entry.get_mut::<RigidBody>().unwrap();
         let _type = rigid_body._type;
         let rb = rapier3d::dynamics::RigidBodyBuilder::new(_type)
             .user_data(entity.to_raw() as u128)
@@ -34,25 +62,48 @@ fn pre_step_spawn_rapier_counterparts(physics: &mut Physics, scene: &mut Scene)
         let handle = physics.bodies.insert(rb);
         rigid_body.handle = Some(handle);
 
-        if !_type.is_fixed() && rigid_body.interpolated {
+        if !_type.is_fixed() && rigid_body.interpolated && !ticked {
             interpolated_entities.push(*entity);
         }
     }
 
+    // Colliders that were added or whose rigid-body got a new counter-part are attached to it
     fn insert_rapier_collider<C: GenericCollider + Component>(
         scene: &mut Scene,
         colliders: &mut ColliderSet,
         bodies: &mut RigidBodySet,
+        spawned: &AHashSet<Entity>,
+        resync: bool,
     ) {
-        let filter = added::<&C>();
-        for (entity, component, rigid_body) in
-            scene.query_mut_with::<(&Entity, &mut C, &RigidBody)>(filter)
-        {
-            let Some(handle) = rigid_body.handle else {
+        let filter = added::<&C>() & include_disabled();
+        let mut attached = scene
+            .query_with::<&Entity>(filter)
+            .into_iter()
+            .copied()
+            .collect::<AHashSet<Entity>>();
+
+        let filter = contains::<&RigidBody>() & include_disabled();
+        let query = scene.query_with::<(&Entity, &C)>(filter);
+        attached.extend(
+            query
+                .into_iter()
+                .filter(|(entity, collider)| {
+                    let missing = !collider
+                        .handle()
+                        .is_some_and(|handle| colliders.contains(handle));
+                    spawned.contains(*entity) || (resync && missing)
+                })
+                .map(|(entity, _)| *entity),
+        );
+
+        for entity in attached {
+            let mut entry = scene.entry_mut(entity).unwrap();
+            let Some(handle) = entry.get::<RigidBody>().and_then(|rb| rb.handle) else {
                 continue;
             };
 
-            if let Some(collider) = C::build_collider(component, entity) {
+            let component = entry.get_mut::<C>().unwrap();
+            if let Some(collider) = C::build_collider(component, &entity) {
                 log::trace!("create rapier generic collider type");
                 let handle = colliders.insert_with_parent(collider, handle, bodies);
                 C::set_handle(component, handle);
@@ -65,10 +116,10 @@ fn pre_step_spawn_rapier_counterparts(physics: &mut Physics, scene: &mut Scene)
     } = &mut *physics;
 
     // Insert the rapier colliders
-    insert_rapier_collider::<SphereCollider>(scene, colliders, bodies);
-    insert_rapier_collider::<CuboidCollider>(scene, colliders, bodies);
-    insert_rapier_collider::<CapsuleCollider>(scene, colliders, bodies);
-    insert_rapier_collider::<MeshCollider>(scene, colliders, bodies);
+    insert_rapier_collider::<SphereCollider>(scene, colliders, bodies, &spawned, resync);
+    insert_rapier_collider::<CuboidCollider>(scene, colliders, bodies, &spawned, resync);
+    insert_rapier_collider::<CapsuleCollider>(scene, colliders, bodies, &spawned, resync);
+    insert_rapier_collider::<MeshCollider>(scene, colliders, bodies, &spawned, resync);
 
     // Automatically add the ticked coord components
     for entity in interpolated_entities {
@@ -94,6 +145,24 @@ fn pre_step_spawn_rapier_counterparts(physics: &mut Physics, scene: &mut Scene)
     }
 }
 
+// This will enable / disable the rapier counter-parts of entities that were enabled / disabled
+fn pre_step_sync_enabled_states(physics: &mut Physics, scene: &Scene) {
+    // Toggling an entity marks all of its components as modified
+    let filter = modified::<&RigidBody>() & include_disabled();
+    for (entity, rigid_body) in scene.query_with::<(&Entity, &RigidBody)>(filter) {
+        let Some(handle) = rigid_body.handle else {
+            continue;
+        };
+
+        let enabled = scene.is_enabled(*entity).unwrap();
+        let rb = physics.bodies.get_mut(handle).unwrap();
+        if rb.is_enabled() != enabled {
+            log::trace!("toggle rapier rigidbody enabled state");
+            rb.set_enabled(enabled);
+        }
+    }
+}
+
 // This will de-spawn the required rapier counter-part of the components
 fn pre_step_despawn_rapier_counterparts(physics: &mut Physics, scene: &mut Scene) {
     let Physics {
@@ -137,6 +206,23 @@ fn pre_step_despawn_rapier_counterparts(physics: &mut Physics, scene: &mut Scene
     destroy_removed_collider::<CuboidCollider>(scene, colliders, islands, bodies);
     destroy_removed_collider::<CapsuleCollider>(scene, colliders, islands, bodies);
     destroy_removed_collider::<MeshCollider>(scene, colliders, islands, bodies);
+
+    // Entities that were transferred to another scene leave their rapier counter-parts behind
+    let transferred = bodies
+        .iter()
+        .filter(|(_, rb)| !scene.contains(Entity::from_raw(rb.user_data as u64)))
+        .map(|(handle, _)| handle)
+        .collect::<Vec<_>>();
+    for handle in transferred {
+        bodies.remove(
+            handle,
+            islands,
+            colliders,
+            impulse_joints,
+            multibody_joints,
+            true,
+        );
+    }
 }
 
 // This will synchronize the rapier counter-part to the data of the components
@@ -281,26 +367,80 @@ fn init(world: &mut World) {
     let physics = Physics::new(tick_rate);
     drop(time);
     world.insert(physics);
+    world.insert(ResidentPhysics::default());
     world.insert(Storage::<PhysicsSurface>::default());
 }
 
-// Step through the physics simulation
+// Step through the physics simulation of the main scene and the simulated resident scenes
 fn tick(world: &mut World) {
-    let mut _physics = world.get_mut::<Physics>().unwrap();
-    let mut _scene = world.get_mut::<Scene>().unwrap();
+    let mut physics = world.get_mut::<Physics>().unwrap();
+    let mut scene = world.get_mut::<Scene>().unwrap();
+    let mut scenes = world.get_mut::<Scenes>().unwrap();
+    let mut resident = world.get_mut::<ResidentPhysics>().unwrap();
     let surfaces = world.get::<Storage<PhysicsSurface>>().unwrap();
     let time = world.get::<Time>().unwrap();
-    let physics = &mut *_physics;
-    let scene = &mut *_scene;
+    let resident = &mut *resident;
+
+    // Drop the physics worlds of the resident scenes that were removed
+    // Scenes that aren't simulated anymore keep their world, since their components still reference it
+    resident.worlds.retain(|id, _| scenes.contains(*id));
+    resident.swaps.retain(|id, _| scenes.contains(*id));
+
+    let swapped = swap_worlds(&mut physics, &scenes, resident, time.tick_rate());
+    simulate(&mut physics, &mut scene, &surfaces, &time, swapped);
+
+    for (id, scene) in scenes.targeted_mut(|targets| targets.physics) {
+        let created = !resident.worlds.contains_key(&id);
+        let physics = resident
+            .worlds
+            .entry(id)
+            .or_insert_with(|| Physics::new(time.tick_rate()));
+        simulate(physics, scene, &surfaces, &time, created);
+    }
+}
 
+// Move the physics worlds along with the scenes that were swapped with the main scene
+// Returns true if the main scene received the world of another scene (which might have been just created)
+pub(crate) fn swap_worlds(
+    physics: &mut Physics,
+    scenes: &Scenes,
+    resident: &mut ResidentPhysics,
+    tick_rate: u32,
+) -> bool {
+    let mut swapped = false;
+    for (id, _) in scenes.iter() {
+        let swaps = scenes.swaps(id).unwrap();
+        let seen = resident.swaps.insert(id, swaps).unwrap_or_default();
+        if (swaps - seen) % 2 == 1 {
+            let world = resident
+                .worlds
+                .entry(id)
+                .or_insert_with(|| Physics::new(tick_rate));
+            std::mem::swap(physics, world);
+            swapped = true;
+        }
+    }
+
+    swapped
+}
+
+// Step through the physics simulation of a single scene
+fn simulate(
+    physics: &mut Physics,
+    scene: &mut Scene,
+    surfaces: &Storage<PhysicsSurface>,
+    time: &Time,
+    resync: bool,
+) {
     // Executed before the physics step
-    pre_step_spawn_rapier_counterparts(physics, scene);
+    pre_step_spawn_rapier_counterparts(physics, scene, resync);
     pre_step_despawn_rapier_counterparts(physics, scene);
+    pre_step_sync_enabled_states(physics, scene);
 
     // Update character controller rigid-bodies
     post_step_update_character_controllers(physics, scene);
 
-    pre_step_sync_rapier_to_comps(physics, scene, &surfaces);
+    pre_step_sync_rapier_to_comps(physics, scene, surfaces);
 
     // Swap next tick with current tick
     if time.tick_count() > 0 {
@@ -387,9 +527,18 @@ fn tick(world: &mut World) {
 // Sub tick interpolation for rigidbodies
 fn update(world: &mut World) {
     let mut scene = world.get_mut::<Scene>().unwrap();
+    let mut scenes = world.get_mut::<Scenes>().unwrap();
     let time = world.get::<Time>().unwrap();
     let t = time.tick_interpolation();
+    interpolate(&mut scene, t);
+
+    for (_, scene) in scenes.targeted_mut(|targets| targets.physics) {
+        interpolate(scene, t);
+    }
+}
 
+// Interpolate the coordinates of the rigidbodies of a single scene
+fn interpolate(scene: &mut Scene, t: f32) {
     let query = scene.query_mut::<(
         &mut RigidBody,
         &LastTickedPosition,
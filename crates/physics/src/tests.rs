@@ -0,0 +1,49 @@
+#[cfg(test)]
+mod system {
+    use crate::{
+        pre_step_spawn_rapier_counterparts, swap_worlds, Physics, ResidentPhysics, RigidBody,
+        RigidBodyBuilder, RigidBodyType, SphereCollider, SphereColliderBuilder,
+    };
+    use ecs::{register_snapshottable, Scene, SceneTargets, Scenes};
+
+    #[test]
+    fn swap_untargeted_scene() {
+        let mut main = Scene::default();
+        let mut physics = Physics::new(60);
+        let mut resident = ResidentPhysics::default();
+        let mut scenes = Scenes::default();
+
+        // A level that was streamed in the background without any physics
+        let mut level = Scene::default();
+        let entity = level.insert((
+            RigidBodyBuilder::new(RigidBodyType::Dynamic)
+                .set_interpolated(false)
+                .build(),
+            SphereColliderBuilder::new(1.0, 0.5).build(),
+        ));
+
+        // Restoring the level in place only marks its bodies as modified, like bodies that were added many ticks ago
+        register_snapshottable::<RigidBody>();
+        register_snapshottable::<SphereCollider>();
+        let snapshot = level.snapshot();
+        level.restore(&snapshot);
+        let id = scenes.insert("level", level, SceneTargets::default());
+
+        scenes.swap(id, &mut main).unwrap();
+        let swapped = swap_worlds(&mut physics, &scenes, &mut resident, 60);
+        assert!(swapped);
+
+        pre_step_spawn_rapier_counterparts(&mut physics, &mut main, swapped);
+        physics.step();
+
+        let entry = main.entry(entity).unwrap();
+        let handle = entry.get::<RigidBody>().unwrap().handle.unwrap();
+        assert!(physics.bodies.contains(handle));
+        let collider = entry.get::<SphereCollider>().unwrap().handle.unwrap();
+        assert_eq!(
+            physics.colliders.get(collider).unwrap().parent(),
+            Some(handle)
+        );
+        assert_eq!(physics.bodies.len(), 1);
+    }
+}
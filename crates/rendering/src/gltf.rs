@@ -1,8 +1,11 @@
-use std::{path::Path, sync::Arc};
+use std::{borrow::Cow, path::Path, sync::Arc};
 
-use crate::{AlbedoMap, MaskMap, Mesh, NormalMap, PbrMaterial, Pipelines, SubSurface, Surface, CullResult};
+use crate::{
+    AlbedoMap, AlbedoTexel, CullResult, MaskMap, MaskTexel, Mesh, NormalMap, NormalTexel,
+    PbrMaterial, Pipelines, SubSurface, Surface,
+};
 use ahash::AHashMap;
-use assets::{Asset, Data};
+use assets::{Asset, AssetLoadError, Assets, Data, DeferredAsset, ImportCache, Meta, MetaError};
 use base64::{
     alphabet,
     engine::{GeneralPurpose, GeneralPurposeConfig},
@@ -14,11 +17,12 @@ use dashmap::DashMap;
 use ecs::{Entity, Scene};
 use gltf::json::accessor::{ComponentType, Type};
 use graphics::{
-    texture2d_from_raw, BufferMode, BufferUsage, Graphics, ImageTexel, Normalized, RawTexels,
-    SamplerFilter, SamplerSettings, SamplerWrap, Texel, Texture, Texture2D, TextureImportSettings,
-    TextureMipMaps, TextureScale, TextureUsage, TextureViewSettings, R, RGBA,
+    generate_mip_map, BufferMode, BufferUsage, Graphics, ImageTexel, Normalized, RawTexels,
+    SamplerFilter, SamplerSettings, SamplerWrap, Texture, Texture2D, TextureMipMaps, TextureScale,
+    TextureUsage, TextureViewSettings, R, RGBA,
 };
 use rayon::prelude::{IntoParallelRefIterator, ParallelIterator};
+use serde::Deserialize;
 use utils::{Handle, Storage};
 use world::{Read, World, Write};
 
@@ -69,7 +73,7 @@ pub struct GltfSettings<'a> {
 
     // We can only load one scene at a time
     // If this is default, then it uses the default scene
-    pub scene: Option<&'a str>,
+    pub scene: Option<Cow<'a, str>>,
 }
 
 impl<'a> Default for GltfSettings<'a> {
@@ -91,9 +95,142 @@ impl<'a> Default for GltfSettings<'a> {
     }
 }
 
-// Marker type that implements asset
-// Doesn't store anything on it's own; everything will be inserted into the world automatically
-pub struct GltfScene;
+// glTF import settings that artists can write within the "<scene>.meta.ron" sidecar file
+// Missing fields use the same values as the default glTF settings
+#[derive(Deserialize)]
+#[serde(default)]
+pub struct GltfMeta {
+    // Name of the scene that we should load
+    pub scene: Option<String>,
+
+    // PBR parameters of the fallback material
+    pub bumpiness_factor: f32,
+    pub roughness_factor: f32,
+    pub metallic_factor: f32,
+    pub ambient_occlusion_factor: f32,
+    pub scale: [f32; 2],
+    pub tint: [f32; 3],
+}
+
+impl Default for GltfMeta {
+    fn default() -> Self {
+        let fallback = GltfSettings::default().fallback;
+
+        Self {
+            scene: None,
+            bumpiness_factor: fallback.bumpiness_factor,
+            roughness_factor: fallback.roughness_factor,
+            metallic_factor: fallback.metallic_factor,
+            ambient_occlusion_factor: fallback.ambient_occlusion_factor,
+            scale: fallback.scale.into_array(),
+            tint: fallback.tint.into_array(),
+        }
+    }
+}
+
+impl<'a> From<GltfMeta> for GltfSettings<'a> {
+    fn from(meta: GltfMeta) -> Self {
+        let defaults = GltfSettings::default();
+
+        Self {
+            scene: meta.scene.map(Cow::Owned),
+            fallback: PbrMaterial {
+                bumpiness_factor: meta.bumpiness_factor,
+                roughness_factor: meta.roughness_factor,
+                metallic_factor: meta.metallic_factor,
+                ambient_occlusion_factor: meta.ambient_occlusion_factor,
+                scale: meta.scale.into(),
+                tint: meta.tint.into(),
+                ..defaults.fallback
+            },
+        }
+    }
+}
+
+// Asset that represents a loaded glTF scene
+// Everything will be inserted into the world automatically; this only keeps track of the spawned entities
+pub struct GltfScene {
+    entities: Vec<Entity>,
+}
+
+impl GltfScene {
+    // Get the entities that were spawned into the scene when this was loaded
+    pub fn entities(&self) -> &[Entity] {
+        &self.entities
+    }
+
+    // Reload the glTF scene if its file changed since the last frame
+    // This despawns the previously spawned entities so reloading doesn't duplicate them
+    // The old entities are kept if the new version fails to load
+    pub fn reload(
+        &mut self,
+        world: &World,
+        path: &str,
+        settings: GltfSettings,
+    ) -> Option<Result<(), AssetLoadError>> {
+        let assets = world.get::<Assets>().unwrap();
+        if !assets.is_changed(path) {
+            return None;
+        }
+
+        let context = match GtlfContext::from_world(world) {
+            Ok(context) => context,
+            Err(err) => {
+                log::error!("Could not fetch the glTF context to reload {path:?}: {err}");
+                return None;
+            }
+        };
+
+        let reloaded = match assets.reloaded::<GltfScene>((path, settings, context))? {
+            Ok(reloaded) => reloaded,
+            Err(err) => return Some(Err(err)),
+        };
+
+        // Despawn the old entities (the user might have removed some already)
+        let mut scene = world.get_mut::<Scene>().unwrap();
+        let old = std::mem::replace(&mut self.entities, reloaded.entities)
+            .into_iter()
+            .filter(|entity| scene.contains(*entity))
+            .collect::<Vec<_>>();
+        scene.remove_from_iter(old);
+        Some(Ok(()))
+    }
+}
+
+// Key of a mesh that is shared between primitives that use the same accessors
+// Positions, normals, tangents, tex coords, and indices
+type CachedMeshKey = (usize, Option<usize>, Option<usize>, Option<usize>, usize);
+
+// Decoded texels, mip levels, and sampler of a material texture
+struct DecodedTexture<T: ImageTexel> {
+    raw: RawTexels<T>,
+    mips: Option<Vec<Vec<T::Storage>>>,
+    sampling: SamplerSettings,
+}
+
+// Vertices and triangles of a mesh that were decoded and optimized
+struct DecodedMesh {
+    positions: Vec<vek::Vec4<f32>>,
+    normals: Option<Vec<vek::Vec4<i8>>>,
+    tangents: Option<Vec<vek::Vec4<i8>>>,
+    tex_coords: Option<Vec<vek::Vec2<f32>>>,
+    triangles: Vec<[u32; 3]>,
+    aabb: Option<math::Aabb<f32>>,
+}
+
+// Decoded glTF document along with its decoded textures and meshes
+// This is created in a worker thread when the scene is loaded using deferred loading
+// Only the GPU resources and entities must be created on the main thread
+pub struct DecodedGltf {
+    json: gltf::json::Root,
+    albedo_maps: Vec<(usize, DecodedTexture<AlbedoTexel>)>,
+    normal_maps: Vec<(usize, DecodedTexture<NormalTexel>)>,
+    mask_maps: Vec<((Option<usize>, Option<usize>), DecodedTexture<MaskTexel>)>,
+    meshes: Vec<(CachedMeshKey, DecodedMesh)>,
+    mesh_keys: Vec<Vec<CachedMeshKey>>,
+    fallback: PbrMaterial,
+    scene: Option<String>,
+}
 
 // Can load in SINGLE .gltf JSON file
 // Can load in MULTIPLE .gltf files (expects the user to have defined them as asset though)
@@ -111,592 +248,654 @@ impl Asset for GltfScene {
     // Load up the GTLF scene
     fn deserialize(
         data: assets::Data,
-        mut context: Self::Context<'_>,
+        context: Self::Context<'_>,
         settings: Self::Settings<'_>,
     ) -> Result<Self, Self::Err> {
-        // Loads the GTLF file from the loaded up bytes
-        let bytes = data.bytes();
-        let reader = std::io::Cursor::new(bytes);
-
-        // Second variable is the binary data in case we load from a GLB
-        let (json, bin) = match data.extension() {
-            // Load the scene from a GLTF file
-            "gltf" => {
-                let gltf = gltf::Gltf::from_reader(reader)?;
-                let doc = gltf.document;
-                (doc.into_json(), None)
-            }
+        let decoded = decode(data, settings.fallback, settings.scene.map(Cow::into_owned))?;
+        finalize(decoded, context)
+    }
 
-            // Load the scene from a GLB file
-            "glb" => {
-                let glb = gltf::Glb::from_reader(reader)?;
-                let cursor = std::io::Cursor::new(&glb.json);
-                let gltf = gltf::Gltf::from_reader(cursor)?;
-                let doc = gltf.document;
-                (doc.into_json(), glb.bin)
-            }
+    fn from_meta<'s>(meta: &Meta) -> Option<Result<Self::Settings<'s>, MetaError>> {
+        Some(meta.parse::<GltfMeta>().map(GltfSettings::from))
+    }
+}
 
-            _ => panic!(),
-        };
+// Deferred loading decodes the document and buffers in a worker thread
+// Textures, meshes, and entities are then created on the main thread
+impl DeferredAsset for GltfScene {
+    type Decoded = DecodedGltf;
+    type Context<'w> = GtlfContext<'w>;
+    type Settings = GltfSettings<'static>;
+    type Err = gltf::Error;
 
-        // Decompose the JSON document
-        let gltf::json::Root {
-            accessors,
-            buffers,
-            buffer_views,
-            scene,
+    fn extensions() -> &'static [&'static str] {
+        &["gltf", "glb"]
+    }
 
-            images,
-            materials,
-            meshes,
-            nodes,
-            samplers,
-            scenes,
-            textures,
-            ..
-        } = json;
-
-        // Get the asset loader for recrusive asset loading
-        let loader = data.loader().unwrap();
-
-        // ----------- NOTES FOR GLTF -----------
-        // Buffers are just raw containers of raw byte data
-        // In the engine, each buffer accessor for primitives is actually a graphics buffer by itself
-        // Buffer -> BufferView -> BufferAccessor -> Attribute[] -> Mesh
-        // Buffer -> BufferView -> Image -> Texture[] -> Material
-        // TODO: Implement buffer allocation sharing in Graphics API to be able to combine bufferviews as a whole buffer by themselves
-
-        // Create a base64 decoder
-        let base64 = GeneralPurpose::new(&alphabet::STANDARD, GeneralPurposeConfig::default());
-
-        // Map (raw) JSON buffers and store their raw byte values
-        let mut offset = 0usize;
-        let mapped_contents = buffers
-            .iter()
-            .map(|buffer| {
-                // Handle reading buffers from URI or raw bytes directly
-                let bytes = if let Some(uri) = buffer.uri.as_ref() {
-                    const PREFIX: &str = "data:application/octet-stream;base64,";
-
-                    if uri.starts_with(PREFIX) {
-                        // Data is contained within the URI itself
-                        let data = uri.strip_prefix(PREFIX).unwrap();
-
-                        // Decode the raw base64 data
-                        base64.decode(data).unwrap()
-                    } else {
-                        // URI references a file that must be loaded
-                        let mut path = data.path().to_path_buf();
-                        path.pop();
-                        path.push(Path::new(uri));
-
-                        // Load the file that contains the raw binary data
-                        loader.load::<Vec<u8>>(path.to_str().unwrap()).unwrap()
-                    }
+    fn decode(data: Data, settings: Self::Settings) -> Result<Self::Decoded, Self::Err> {
+        decode(data, settings.fallback, settings.scene.map(Cow::into_owned))
+    }
+
+    fn fetch(world: &World) -> Option<Self::Context<'_>> {
+        GtlfContext::from_world(world).ok()
+    }
+
+    fn finalize(decoded: Self::Decoded, context: Self::Context<'_>) -> Result<Self, Self::Err> {
+        finalize(decoded, context)
+    }
+
+    fn from_meta(meta: &Meta) -> Option<Result<Self::Settings, MetaError>> {
+        Some(meta.parse::<GltfMeta>().map(GltfSettings::from))
+    }
+}
+
+// Parse the glTF document and map its raw buffers
+fn decode(
+    data: Data,
+    fallback: PbrMaterial,
+    scene: Option<String>,
+) -> Result<DecodedGltf, gltf::Error> {
+    // Loads the GTLF file from the loaded up bytes
+    let bytes = data.bytes();
+    let reader = std::io::Cursor::new(bytes);
+
+    // Second variable is the binary data in case we load from a GLB
+    let (json, bin) = match data.extension() {
+        // Load the scene from a GLTF file
+        "gltf" => {
+            let gltf = gltf::Gltf::from_reader(reader)?;
+            let doc = gltf.document;
+            (doc.into_json(), None)
+        }
+
+        // Load the scene from a GLB file
+        "glb" => {
+            let glb = gltf::Glb::from_reader(reader)?;
+            let cursor = std::io::Cursor::new(&glb.json);
+            let gltf = gltf::Gltf::from_reader(cursor)?;
+            let doc = gltf.document;
+            (doc.into_json(), glb.bin)
+        }
+
+        _ => panic!(),
+    };
+
+    // ----------- NOTES FOR GLTF -----------
+    // Buffers are just raw containers of raw byte data
+    // In the engine, each buffer accessor for primitives is actually a graphics buffer by itself
+    // Buffer -> BufferView -> BufferAccessor -> Attribute[] -> Mesh
+    // Buffer -> BufferView -> Image -> Texture[] -> Material
+    // TODO: Implement buffer allocation sharing in Graphics API to be able to combine bufferviews as a whole buffer by themselves
+
+    // Create a base64 decoder
+    let base64 = GeneralPurpose::new(&alphabet::STANDARD, GeneralPurposeConfig::default());
+
+    // Map (raw) JSON buffers and store their raw byte values
+    let mut offset = 0usize;
+    let buffers = json
+        .buffers
+        .iter()
+        .map(|buffer| {
+            // Handle reading buffers from URI or raw bytes directly
+            let bytes = if let Some(uri) = buffer.uri.as_ref() {
+                const PREFIX: &str = "data:application/octet-stream;base64,";
+
+                if uri.starts_with(PREFIX) {
+                    // Data is contained within the URI itself
+                    let data = uri.strip_prefix(PREFIX).unwrap();
+
+                    // Decode the raw base64 data
+                    base64.decode(data).unwrap()
                 } else {
-                    // Load the binary data oui oui
-                    let bin = bin.as_ref().unwrap();
-                    let bytes = bin[offset..(offset + buffer.byte_length.0 as usize)].to_vec();
-                    offset += buffer.byte_length.0 as usize;
-                    bytes
-                };
-
-                // Make sure we loaded the right amount of bytes
-                assert_eq!(bytes.len(), buffer.byte_length.0 as usize);
+                    // URI references a file that must be loaded
+                    let mut path = data.path().to_path_buf();
+                    path.pop();
+                    path.push(Path::new(uri));
+
+                    // Load the file that contains the raw binary data (works in worker threads as well)
+                    let bytes = data.load_bytes(&path).ok_or_else(|| {
+                        gltf::Error::Io(std::io::Error::new(
+                            std::io::ErrorKind::Unsupported,
+                            "external glTF buffers need the asset loader",
+                        ))
+                    })?;
+                    let bytes = bytes.map_err(|err| {
+                        gltf::Error::Io(std::io::Error::new(std::io::ErrorKind::NotFound, err))
+                    })?;
+                    bytes.to_vec()
+                }
+            } else {
+                // Load the binary data oui oui
+                let bin = bin.as_ref().unwrap();
+                let bytes = bin[offset..(offset + buffer.byte_length.0 as usize)].to_vec();
+                offset += buffer.byte_length.0 as usize;
                 bytes
-            })
-            .collect::<Vec<_>>();
-        log::debug!("Mapped {} glTF buffers", buffers.len());
-
-        // Map buffer views as buffer slices
-        let mapped_views = buffer_views
-            .iter()
-            .map(|view| {
-                let buffer = &mapped_contents[view.buffer.value()];
-                let len = view.byte_length.0 as usize;
-                let offset = view.byte_offset.unwrap_or_default().0 as usize;
-                assert!(view.byte_stride.is_none());
-                &buffer[offset..(offset + len)]
-            })
-            .collect::<Vec<_>>();
-        log::debug!("Mapped {} glTF buffer views", buffer_views.len());
-
-        // Map images and store their raw byte values (and extension)
-        let mapped_images = images
-            .iter()
-            .map(|image| {
-                let view = &mapped_views[image.buffer_view.unwrap().value()];
-                assert!(image.uri.is_none());
-                let ext = &image.mime_type.as_ref().unwrap().0;
-                (*view, ext.strip_prefix("image/").unwrap())
-            })
-            .collect::<Vec<_>>();
-        log::debug!("Mapped {} glTF images", mapped_images.len());
-
-        // Map buffer accessors and store their component values
-        let mapped_accessors = accessors
-            .iter()
-            .map(|accessor| {
-                let view = &mapped_views[accessor.buffer_view.unwrap().value()];
-                assert!(accessor.sparse.is_none());
-                let offset = accessor.byte_offset.unwrap_or_default().0 as usize;
-                let _type = accessor.type_.as_ref().unwrap();
-                let min = accessor.min.as_ref();
-                let max = accessor.max.as_ref();
-
-                let generic_component_type = accessor.component_type.as_ref().unwrap();
-                (
-                    &view[offset..],
-                    (_type, &generic_component_type.0),
-                    (min, max),
-                )
-            })
-            .collect::<Vec<_>>();
+            };
 
-        // TODO:
-        // Convert the textures to hashmaps w the settings they should be created with
-        // Create rayon scope that will take those hashmaps and convert their inner settings to act textures
-
-        // Map PBR textures first in other threads
-        let cached_albedo_maps = Arc::new(DashMap::<usize, AlbedoMap>::new());
-        let cached_normal_maps = Arc::new(DashMap::<usize, NormalMap>::new());
-        let cached_mask_maps = Arc::new(DashMap::<(Option<usize>, Option<usize>), MaskMap>::new());
-        let graphics = context.graphics.clone();
-
-        // Load the assets in other threads
-        rayon::scope(|s| {
-            for material in materials.iter() {
-                // Decompose into Optional indices
-                let pbr = material.pbr_metallic_roughness.clone();
-                let albedo_map = pbr.base_color_texture.clone();
-                let normal_map = material.normal_texture.clone();
-                let metallic_roughness_map = pbr.metallic_roughness_texture.clone();
-                let occlusion_map = material.occlusion_texture.clone();
-                let textures = &textures;
-                let mapped_images = &mapped_images;
-                let samplers = &samplers;
-                let cached_albedo_maps = cached_albedo_maps.clone();
-                let cached_normal_maps = cached_normal_maps.clone();
-                let cached_mask_maps = cached_mask_maps.clone();
-
-                // Create or load a cached diffuse map texture
-                if let Some(info) = albedo_map {
-                    let graphics = graphics.clone();
-                    s.spawn(move |_| {
-                        cached_albedo_maps
-                            .entry(info.index.value())
-                            .or_insert_with(|| {
-                                let texture = &textures[info.index.value()];
-                                create_material_texture(graphics, texture, samplers, mapped_images)
-                            });
-                    });
-                }
+            // Make sure we loaded the right amount of bytes
+            assert_eq!(bytes.len(), buffer.byte_length.0 as usize);
+            Ok(bytes)
+        })
+        .collect::<Result<Vec<_>, gltf::Error>>()?;
+    log::debug!("Mapped {} glTF buffers", buffers.len());
+
+    // Map buffer views as buffer slices
+    let mapped_views = json
+        .buffer_views
+        .iter()
+        .map(|view| {
+            let buffer = &buffers[view.buffer.value()];
+            let len = view.byte_length.0 as usize;
+            let offset = view.byte_offset.unwrap_or_default().0 as usize;
+            assert!(view.byte_stride.is_none());
+            &buffer[offset..(offset + len)]
+        })
+        .collect::<Vec<_>>();
+    log::debug!("Mapped {} glTF buffer views", mapped_views.len());
 
-                // Create or load a cached normal map texture
-                if let Some(tex) = normal_map {
-                    let graphics = graphics.clone();
-                    s.spawn(move |_| {
-                        cached_normal_maps
-                            .entry(tex.index.value())
-                            .or_insert_with(|| {
-                                let texture = &textures[tex.index.value()];
-                                create_material_texture(graphics, texture, samplers, mapped_images)
-                            });
+    // Map images and store their raw byte values (and extension)
+    let mapped_images = json
+        .images
+        .iter()
+        .map(|image| {
+            let view = &mapped_views[image.buffer_view.unwrap().value()];
+            assert!(image.uri.is_none());
+            let ext = &image.mime_type.as_ref().unwrap().0;
+            (*view, ext.strip_prefix("image/").unwrap())
+        })
+        .collect::<Vec<_>>();
+    log::debug!("Mapped {} glTF images", mapped_images.len());
+
+    // Map buffer accessors and store their component values
+    let mapped_accessors = json
+        .accessors
+        .iter()
+        .map(|accessor| {
+            let view = &mapped_views[accessor.buffer_view.unwrap().value()];
+            assert!(accessor.sparse.is_none());
+            let offset = accessor.byte_offset.unwrap_or_default().0 as usize;
+            let _type = accessor.type_.as_ref().unwrap();
+            let min = accessor.min.as_ref();
+            let max = accessor.max.as_ref();
+
+            let generic_component_type = accessor.component_type.as_ref().unwrap();
+            (
+                &view[offset..],
+                (_type, &generic_component_type.0),
+                (min, max),
+            )
+        })
+        .collect::<Vec<_>>();
+    log::debug!("Mapped {} glTF accessors", mapped_accessors.len());
+
+    // Decode the PBR textures in multiple threads
+    let albedo_maps = DashMap::<usize, DecodedTexture<AlbedoTexel>>::new();
+    let normal_maps = DashMap::<usize, DecodedTexture<NormalTexel>>::new();
+    let mask_maps = DashMap::<(Option<usize>, Option<usize>), DecodedTexture<MaskTexel>>::new();
+    let textures = &json.textures;
+    let samplers = &json.samplers;
+    let mapped_images = &mapped_images;
+
+    // Textures are decoded outside of the asset loader, so pass its import cache down manually
+    let imports = data.import_cache();
+
+    rayon::scope(|s| {
+        for material in json.materials.iter() {
+            let pbr = &material.pbr_metallic_roughness;
+            let (albedo_maps, normal_maps, mask_maps) = (&albedo_maps, &normal_maps, &mask_maps);
+
+            // Decode a cached diffuse map texture
+            if let Some(info) = pbr.base_color_texture.as_ref() {
+                let index = info.index.value();
+                s.spawn(move |_| {
+                    albedo_maps.entry(index).or_insert_with(|| {
+                        decode_material_texture(&textures[index], samplers, mapped_images, imports)
                     });
-                }
+                });
+            }
 
-                // Create or load a cached mask map texture
-                // r: ambient occlusion, g: roughness, b: metallic
-                (metallic_roughness_map.is_some() || occlusion_map.is_some()).then(|| {
-                    let graphics = graphics.clone();
-                    s.spawn(move |_s| {
-                        let metallic_roughness_map =
-                            metallic_roughness_map.map(|x| x.index.value());
-                        let occlusion_map = occlusion_map.map(|x| x.index.value());
-
-                        cached_mask_maps
-                            .entry((metallic_roughness_map, occlusion_map))
-                            .or_insert_with(|| {
-                                let metallic_roughness_map =
-                                    metallic_roughness_map.map(|x| &textures[x]);
-                                let occlusion_map = occlusion_map.map(|x| &textures[x]);
-
-                                create_material_mask_texture(
-                                    graphics,
-                                    metallic_roughness_map,
-                                    occlusion_map,
-                                    samplers,
-                                    mapped_images,
-                                )
-                            });
+            // Decode a cached normal map texture
+            if let Some(tex) = material.normal_texture.as_ref() {
+                let index = tex.index.value();
+                s.spawn(move |_| {
+                    normal_maps.entry(index).or_insert_with(|| {
+                        decode_material_texture(&textures[index], samplers, mapped_images, imports)
                     });
                 });
             }
-        });
-        log::debug!("Mapping of {} glTF materials", materials.len());
 
-        // Unwraps an Arc and panics if it returns an error
-        let cached_albedo_maps = Arc::try_unwrap(cached_albedo_maps).map_err(|_| ()).unwrap();
-        let cached_normal_maps = Arc::try_unwrap(cached_normal_maps).map_err(|_| ()).unwrap();
-        let cached_mask_maps = Arc::try_unwrap(cached_mask_maps).map_err(|_| ()).unwrap();
+            // Decode a cached mask map texture
+            // r: ambient occlusion, g: roughness, b: metallic
+            let metallic_roughness_map = pbr
+                .metallic_roughness_texture
+                .as_ref()
+                .map(|x| x.index.value());
+            let occlusion_map = material.occlusion_texture.as_ref().map(|x| x.index.value());
+            if metallic_roughness_map.is_some() || occlusion_map.is_some() {
+                s.spawn(move |_| {
+                    mask_maps
+                        .entry((metallic_roughness_map, occlusion_map))
+                        .or_insert_with(|| {
+                            decode_material_mask_texture(
+                                metallic_roughness_map.map(|x| &textures[x]),
+                                occlusion_map.map(|x| &textures[x]),
+                                samplers,
+                                mapped_images,
+                                imports,
+                            )
+                        });
+                });
+            }
+        }
+    });
+    log::debug!(
+        "Decoded the textures of {} glTF materials",
+        json.materials.len()
+    );
 
-        // Convert the textures to their appropriate handles
-        let cached_albedo_maps = cached_albedo_maps
-            .into_iter()
-            .map(|(i, map)| (i, context.albedo_maps.insert(map)))
-            .collect::<AHashMap<usize, Handle<AlbedoMap>>>();
-        let cached_normal_maps = cached_normal_maps
-            .into_iter()
-            .map(|(i, map)| (i, context.normal_maps.insert(map)))
-            .collect::<AHashMap<usize, Handle<NormalMap>>>();
-        let cached_mask_maps = cached_mask_maps
-            .into_iter()
-            .map(|(i, map)| (i, context.mask_maps.insert(map)))
-            .collect::<AHashMap<(Option<usize>, Option<usize>), Handle<MaskMap>>>();
-
-        // Map PBR materials (map textures and their samplers as well)
-        // TODO: Implement multiple texture coordinates for the mesh
-        let mapped_materials = materials
-            .iter()
-            .map(|material| {
-                // Decompose into Optional indices
-                let pbr = &material.pbr_metallic_roughness;
-                let albedo_map = pbr.base_color_texture.as_ref();
-                let normal_map = material.normal_texture.as_ref();
-                let metallic_roughness_map = pbr.metallic_roughness_texture.as_ref();
-                let occlusion_map = material.occlusion_texture.as_ref();
-
-                // Get the texture map / tint factors
-                let tint = vek::Rgb::from_slice(&pbr.base_color_factor.0[..3]);
-                let roughness = pbr.roughness_factor.0;
-                let metallic = pbr.metallic_factor.0;
-                let ambient_occlusion = occlusion_map.as_ref().map(|x| x.strength.0).unwrap_or(1.0);
-                let bumpiness = normal_map.as_ref().map(|x| x.scale).unwrap_or(1.0);
-
-                // Get the handles NOW!!!
-                let albedo_map = albedo_map.map(|x| cached_albedo_maps[&x.index.value()].clone());
-                let normal_map = normal_map.map(|x| cached_normal_maps[&x.index.value()].clone());
-                let mask = (
-                    metallic_roughness_map.map(|x| x.index.value()),
-                    occlusion_map.map(|x| x.index.value()),
-                );
-                let mask_map = cached_mask_maps.get(&mask).cloned();
-
-                PbrMaterial {
-                    albedo_map,
-                    normal_map,
-                    mask_map,
-                    bumpiness_factor: bumpiness,
-                    roughness_factor: roughness,
-                    metallic_factor: metallic,
-                    ambient_occlusion_factor: ambient_occlusion,
-                    tint,
-                    scale: vek::Extent2::one(),
-                }
-            })
-            .map(|material| context.pbr_materials.insert(material))
-            .collect::<Vec<Handle<PbrMaterial>>>();
-
-        // Map meshes and create their handles
-        type CachedMeshKey = (usize, Option<usize>, Option<usize>, Option<usize>, usize);
-        let cached_meshes = DashMap::<CachedMeshKey, Mesh>::new();
-        let graphics = context.graphics.clone();
-        let mapped_meshes = meshes
-            .par_iter()
-            .map(|mesh| {
-                let mut meshes = Vec::<CachedMeshKey>::new();
-
-                for primitive in mesh.primitives.iter() {
-                    // Get the accessor indices for the attributes used by this mesh
-                    let mut key = (usize::MAX, None, None, None, usize::MAX);
-                    for (semantic, attribute) in primitive.attributes.iter() {
-                        let index = Some(attribute.value());
-                        let semantic = semantic.as_ref().unwrap();
-
-                        match semantic {
-                            gltf::Semantic::Positions => key.0 = attribute.value(),
-                            gltf::Semantic::Normals => key.1 = index,
-                            gltf::Semantic::Tangents => key.2 = index,
-                            gltf::Semantic::TexCoords(_) => key.3 = index,
-                            _ => {}
-                        }
+    // Decode and optimize the meshes in multiple threads
+    let meshes = DashMap::<CachedMeshKey, DecodedMesh>::new();
+    let mesh_keys = json
+        .meshes
+        .par_iter()
+        .map(|mesh| {
+            let mut keys = Vec::<CachedMeshKey>::new();
+
+            for primitive in mesh.primitives.iter() {
+                // Get the accessor indices for the attributes used by this mesh
+                let mut key = (usize::MAX, None, None, None, usize::MAX);
+                for (semantic, attribute) in primitive.attributes.iter() {
+                    let index = Some(attribute.value());
+                    let semantic = semantic.as_ref().unwrap();
+
+                    match semantic {
+                        gltf::Semantic::Positions => key.0 = attribute.value(),
+                        gltf::Semantic::Normals => key.1 = index,
+                        gltf::Semantic::Tangents => key.2 = index,
+                        gltf::Semantic::TexCoords(_) => key.3 = index,
+                        _ => {}
                     }
-                    key.4 = primitive.indices.unwrap().value();
-
-                    // Create a new mesh if the accessors aren't cached
-                    // TODO: Implement multi-buffer per allocation support to optimize this
-                    cached_meshes.entry(key).or_insert_with(|| {
-                        // Create buffers and AABB
-                        let (mut positions, aabb) = create_positions_vec(&mapped_accessors[key.0]);
-
-                        // TODO: Generate normals / tangents / uvs if missing
-                        let mut normals = key
-                            .1
-                            .map(|index| create_normals_vec(&mapped_accessors[index]));
-                        let mut tangents = key
-                            .2
-                            .map(|index| create_tangents_vec(&mapped_accessors[index]));
-                        let mut tex_coords = Some(key
-                            .3
-                            .map(|index| create_tex_coords_vec(&mapped_accessors[index]))
-                            .unwrap_or_else(|| vec![vek::Vec2::zero(); positions.len()]));
-                        let mut triangles = create_triangles_vec(&mapped_accessors[key.4]);
-
-                        // Optionally generate the tangents
-                        if let (Some(normals), Some(tex_coords)) =
-                            (normals.as_ref(), tex_coords.as_ref())
-                        {
-                            tangents = Some(
-                                super::compute_tangents(
-                                    &positions, normals, tex_coords, &triangles,
-                                )
-                                .unwrap(),
-                            );
-                        }
-
-                        let mut temp_positions = Some(positions.as_mut_slice());
-                        let mut temp_normals = normals.as_deref_mut();
-                        let mut temp_tangents = tangents.as_deref_mut();
-                        let mut temp_tex_coords = tex_coords.as_deref_mut();
-
-                        // Optimize the mesh after we load it
-                        super::optimize(
-                            true,
-                            true,
-                            true,
-                            &mut temp_positions,
-                            &mut temp_normals,
-                            &mut temp_tangents,
-                            &mut temp_tex_coords,
-                            &mut triangles,
-                        );
-
-                        // Create a new mesh for the accessors used
-                        Mesh::from_slices(
-                            &graphics.clone(),
-                            BufferMode::Dynamic,
-                            BufferUsage::COPY_DST,
-                            Some(&positions),
-                            normals.as_deref(),
-                            tangents.as_deref(),
-                            tex_coords.as_deref(),
-                            &triangles,
-                            aabb,
-                        )
-                        .unwrap()
-                    });
-                    meshes.push(key);
                 }
+                key.4 = primitive.indices.unwrap().value();
 
+                // Decode a new mesh if the accessors aren't cached
                 meshes
-            })
-            .collect::<Vec<Vec<CachedMeshKey>>>();
+                    .entry(key)
+                    .or_insert_with(|| decode_mesh(key, &mapped_accessors));
+                keys.push(key);
+            }
 
-        // Convert mapped meshes into their actual handles
-        let cached_meshes = cached_meshes
-            .into_iter()
-            .map(|(id, mesh)| (id, context.meshes.insert(mesh)))
-            .collect::<AHashMap<_, _>>();
-        let mapped_meshes = mapped_meshes
-            .into_iter()
-            .map(|vec| {
-                vec.into_iter()
-                    .map(|id| cached_meshes[&id].clone())
-                    .collect::<Vec<_>>()
-            })
-            .collect::<Vec<_>>();
+            keys
+        })
+        .collect::<Vec<Vec<CachedMeshKey>>>();
+    log::debug!("Decoded {} unique glTF meshes", meshes.len());
+
+    Ok(DecodedGltf {
+        albedo_maps: albedo_maps.into_iter().collect(),
+        normal_maps: normal_maps.into_iter().collect(),
+        mask_maps: mask_maps.into_iter().collect(),
+        meshes: meshes.into_iter().collect(),
+        mesh_keys,
+        json,
+        fallback,
+        scene,
+    })
+}
 
-        // Get the scene that we will load
-        let scene = settings
-            .scene
-            .map(|defined| {
-                scenes
-                    .iter()
-                    .filter_map(|x| x.name.as_ref().map(|y| (x, y)))
-                    .find(|(_, name)| defined == *name)
-                    .map(|(scene, _)| scene)
-                    .unwrap()
-                    .clone()
-            })
-            .or(scene.as_ref().map(|i| scenes[i.value()].clone()))
+// Create the textures, materials, meshes, and entities of a decoded glTF scene
+fn finalize(decoded: DecodedGltf, mut context: GtlfContext<'_>) -> Result<GltfScene, gltf::Error> {
+    // Decompose the JSON document
+    let gltf::json::Root {
+        scene,
+        materials,
+        meshes,
+        nodes,
+        scenes,
+        ..
+    } = decoded.json;
+
+    // Upload the decoded textures and convert them to their appropriate handles
+    let graphics = &*context.graphics;
+    let cached_albedo_maps = decoded
+        .albedo_maps
+        .into_iter()
+        .map(|(i, map)| (i, context.albedo_maps.insert(create_texture(graphics, map))))
+        .collect::<AHashMap<usize, Handle<AlbedoMap>>>();
+    let cached_normal_maps = decoded
+        .normal_maps
+        .into_iter()
+        .map(|(i, map)| (i, context.normal_maps.insert(create_texture(graphics, map))))
+        .collect::<AHashMap<usize, Handle<NormalMap>>>();
+    let cached_mask_maps = decoded
+        .mask_maps
+        .into_iter()
+        .map(|(i, map)| (i, context.mask_maps.insert(create_texture(graphics, map))))
+        .collect::<AHashMap<(Option<usize>, Option<usize>), Handle<MaskMap>>>();
+
+    // Map PBR materials (map textures and their samplers as well)
+    // TODO: Implement multiple texture coordinates for the mesh
+    let mapped_materials = materials
+        .iter()
+        .map(|material| {
+            // Decompose into Optional indices
+            let pbr = &material.pbr_metallic_roughness;
+            let albedo_map = pbr.base_color_texture.as_ref();
+            let normal_map = material.normal_texture.as_ref();
+            let metallic_roughness_map = pbr.metallic_roughness_texture.as_ref();
+            let occlusion_map = material.occlusion_texture.as_ref();
+
+            // Get the texture map / tint factors
+            let tint = vek::Rgb::from_slice(&pbr.base_color_factor.0[..3]);
+            let roughness = pbr.roughness_factor.0;
+            let metallic = pbr.metallic_factor.0;
+            let ambient_occlusion = occlusion_map.as_ref().map(|x| x.strength.0).unwrap_or(1.0);
+            let bumpiness = normal_map.as_ref().map(|x| x.scale).unwrap_or(1.0);
+
+            // Get the handles NOW!!!
+            let albedo_map = albedo_map.map(|x| cached_albedo_maps[&x.index.value()].clone());
+            let normal_map = normal_map.map(|x| cached_normal_maps[&x.index.value()].clone());
+            let mask = (
+                metallic_roughness_map.map(|x| x.index.value()),
+                occlusion_map.map(|x| x.index.value()),
+            );
+            let mask_map = cached_mask_maps.get(&mask).cloned();
+
+            PbrMaterial {
+                albedo_map,
+                normal_map,
+                mask_map,
+                bumpiness_factor: bumpiness,
+                roughness_factor: roughness,
+                metallic_factor: metallic,
+                ambient_occlusion_factor: ambient_occlusion,
+                tint,
+                scale: vek::Extent2::one(),
+            }
+        })
+        .zip(materials.iter())
+        .map(|(material, json)| match &json.name {
+            // Named materials can be fetched from the storage using their glTF name
+            Some(name) => context.pbr_materials.insert_named(name, material),
+            None => context.pbr_materials.insert(material),
+        })
+        .collect::<Vec<Handle<PbrMaterial>>>();
+
+    // Upload the decoded meshes and convert them to their handles
+    let cached_meshes = decoded
+        .meshes
+        .into_iter()
+        .map(|(key, mesh)| {
+            // Create a new mesh for the accessors used
+            let mesh = Mesh::from_slices(
+                graphics,
+                BufferMode::Dynamic,
+                BufferUsage::COPY_DST,
+                Some(&mesh.positions),
+                mesh.normals.as_deref(),
+                mesh.tangents.as_deref(),
+                mesh.tex_coords.as_deref(),
+                &mesh.triangles,
+                mesh.aabb,
+            )
             .unwrap();
+            (key, context.meshes.insert(mesh))
+        })
+        .collect::<AHashMap<_, _>>();
+    let mapped_meshes = decoded
+        .mesh_keys
+        .into_iter()
+        .map(|vec| {
+            vec.into_iter()
+                .map(|id| cached_meshes[&id].clone())
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+
+    // Get the scene that we will load
+    let scene = decoded
+        .scene
+        .as_deref()
+        .map(|defined| {
+            scenes
+                .iter()
+                .filter_map(|x| x.name.as_ref().map(|y| (x, y)))
+                .find(|(_, name)| defined == *name)
+                .map(|(scene, _)| scene)
+                .unwrap()
+                .clone()
+        })
+        .or(scene.as_ref().map(|i| scenes[i.value()].clone()))
+        .unwrap();
 
-        // Add a "default" PBR material
-        let default = context.pbr_materials.insert(settings.fallback);
-
-        // Keep track of the nodes we must evaluate
-        let mut eval = scene
-            .nodes
-            .iter()
-            .map(|node| (&nodes[node.value()], None))
-            .collect::<Vec<(&gltf::json::Node, Option<Entity>)>>();
+    // Add a "default" PBR material
+    let default = context.pbr_materials.insert(decoded.fallback);
 
-        // Number of entities we will add
-        let mut count = 0;
+    // Keep track of the nodes we must evaluate
+    let mut eval = scene
+        .nodes
+        .iter()
+        .map(|node| (&nodes[node.value()], None))
+        .collect::<Vec<(&gltf::json::Node, Option<Entity>)>>();
 
-        // PBR material id
-        let id = context.pipelines.get::<PbrMaterial>().unwrap();
+    // Entities that we added to the scene
+    let mut entities = Vec::<Entity>::new();
 
-        // Iterate until there are no more nodes to pass through
-        while let Some((node, parent)) = {
-            if !eval.is_empty() {
-                Some(eval.remove(0))
-            } else {
-                None
-            }
-        } {
-            // Convert translation, rotation, and scale to proper components
-            let position = node
-                .translation
-                .map(vek::Vec3::from)
-                .unwrap_or(vek::Vec3::zero());
-            let rotation = node
-                .rotation
-                .map(|quat| vek::Quaternion::from_vec4(vek::Vec4::from(quat.0)))
-                .unwrap_or(vek::Quaternion::identity());
-            let scale = node
-                .scale
-                .map(|scale| {
-                    // Biggest scalar in the vector
-                    let uniform = vek::Vec3::from_slice(&scale).reduce_partial_max();
-
-                    // Non-uniform scale isn't supported in the engine
-                    if scale.iter().any(|&x| x != uniform) {
-                        log::warn!(
-                            "Non-uniform scale is not supported in the engine. Given scale: {:?}",
-                            scale
-                        );
-                    }
+    // PBR material id
+    let id = context.pipelines.get::<PbrMaterial>().unwrap();
 
-                    uniform
-                })
-                .unwrap_or(1.0f32);
-
-            // For now, we only handle mesh entities and empty entities
-            let name = node.name.as_ref().map(|x| x.as_str()).unwrap_or("Untitled Node");
-            let entity = if let Some(mesh_index) = node.mesh {
-                let mesh = &meshes[mesh_index.value()];
-                let meshes = &mapped_meshes[mesh_index.value()];
-
-                // Sub-Surfaces that we must render
-                let mut subsurfaces = Vec::<SubSurface<PbrMaterial>>::new();
-
-                // Create the sub-surfaces of the entity (mesh)
-                for (submesh_index, primitive) in mesh.primitives.iter().enumerate() {
-                    let mesh = &meshes[submesh_index];
-                    let material = primitive
-                        .material
-                        .as_ref()
-                        .map(|i| mapped_materials[i.value()].clone())
-                        .unwrap_or(default.clone());
-                    subsurfaces.push(SubSurface {
-                        mesh: mesh.clone(),
-                        material: material.clone(),
-                        culled: CullResult::Visible,
-                        visible: true,
-                        shadow_culled: 0,
-                        shadow_caster: true,
-                    });
+    // Iterate until there are no more nodes to pass through
+    while let Some((node, parent)) = {
+        if !eval.is_empty() {
+            Some(eval.remove(0))
+        } else {
+            None
+        }
+    } {
+        // Convert translation, rotation, and scale to proper components
+        let position = node
+            .translation
+            .map(vek::Vec3::from)
+            .unwrap_or(vek::Vec3::zero());
+        let rotation = node
+            .rotation
+            .map(|quat| vek::Quaternion::from_vec4(vek::Vec4::from(quat.0)))
+            .unwrap_or(vek::Quaternion::identity());
+        let scale = node
+            .scale
+            .map(|scale| {
+                // Biggest scalar in the vector
+                let uniform = vek::Vec3::from_slice(&scale).reduce_partial_max();
+
+                // Non-uniform scale isn't supported in the engine
+                if scale.iter().any(|&x| x != uniform) {
+                    log::warn!(
+                        "Non-uniform scale is not supported in the engine. Given scale: {:?}",
+                        scale
+                    );
                 }
 
-                // Create a proper surface
-                let surface = Surface::from_iter(subsurfaces, id);
-
-                match parent {
-                    // Local coordinates if we have a parent
-                    Some(_) => {
-                        // Add the renderable entity
-                        context.scene.insert((
-                            coords::LocalPosition::from(position),
-                            coords::LocalRotation::from(rotation),
-                            coords::LocalScale::from(scale),
-                            coords::Position::default(),
-                            coords::Rotation::default(),
-                            coords::Scale::default(),
-                            surface,
-                            crate::Renderer::default(),
-                            ecs::Named(name.to_string()),
-                        ))
-                    }
+                uniform
+            })
+            .unwrap_or(1.0f32);
+
+        // For now, we only handle mesh entities and empty entities
+        let name = node
+            .name
+            .as_ref()
+            .map(|x| x.as_str())
+            .unwrap_or("Untitled Node");
+        let entity = if let Some(mesh_index) = node.mesh {
+            let mesh = &meshes[mesh_index.value()];
+            let meshes = &mapped_meshes[mesh_index.value()];
+
+            // Sub-Surfaces that we must render
+            let mut subsurfaces = Vec::<SubSurface<PbrMaterial>>::new();
+
+            // Create the sub-surfaces of the entity (mesh)
+            for (submesh_index, primitive) in mesh.primitives.iter().enumerate() {
+                let mesh = &meshes[submesh_index];
+                let material = primitive
+                    .material
+                    .as_ref()
+                    .map(|i| mapped_materials[i.value()].clone())
+                    .unwrap_or(default.clone());
+                subsurfaces.push(SubSurface {
+                    mesh: mesh.clone(),
+                    material: material.clone(),
+                    culled: CullResult::Visible,
+                    visible: true,
+                    shadow_culled: 0,
+                    shadow_caster: true,
+                });
+            }
 
-                    // Global coordinates if we do not have a parent
-                    None => {
-                        // Add the renderable entity
-                        context.scene.insert((
-                            coords::Position::from(position),
-                            coords::Rotation::from(rotation),
-                            coords::Scale::from(scale),
-                            surface,
-                            crate::Renderer::default(),
-                            ecs::Named(name.to_string()),
-                        ))
-                    }
+            // Create a proper surface
+            let surface = Surface::from_iter(subsurfaces, id);
+
+            match parent {
+                // Local coordinates if we have a parent
+                Some(_) => {
+                    // Add the renderable entity
+                    context.scene.insert((
+                        coords::LocalPosition::from(position),
+                        coords::LocalRotation::from(rotation),
+                        coords::LocalScale::from(scale),
+                        coords::Position::default(),
+                        coords::Rotation::default(),
+                        coords::Scale::default(),
+                        surface,
+                        crate::Renderer::default(),
+                        ecs::Named(name.to_string()),
+                    ))
                 }
-            } else {
-                match parent {
-                    // Local coordinates if we have a parent
-                    Some(_) => {
-                        // Add the renderable entity
-                        context.scene.insert((
-                            coords::LocalPosition::from(position),
-                            coords::LocalRotation::from(rotation),
-                            coords::LocalScale::from(scale),
-                            coords::Position::default(),
-                            coords::Rotation::default(),
-                            coords::Scale::default(),
-                            ecs::Named(name.to_string()),
-                        ))
-                    }
 
-                    // Global coordinates if we do not have a parent
-                    None => {
-                        // Add the renderable entity
-                        context.scene.insert((
-                            coords::Position::from(position),
-                            coords::Rotation::from(rotation),
-                            coords::Scale::from(scale),
-                            ecs::Named(name.to_string()),
-                        ))
-                    }
+                // Global coordinates if we do not have a parent
+                None => {
+                    // Add the renderable entity
+                    context.scene.insert((
+                        coords::Position::from(position),
+                        coords::Rotation::from(rotation),
+                        coords::Scale::from(scale),
+                        surface,
+                        crate::Renderer::default(),
+                        ecs::Named(name.to_string()),
+                    ))
                 }
-            };
-
-            // Attach this entity to its parent if it had any
-            if let Some(parent) = parent {
-                context.scene.attach(entity, parent).unwrap();
             }
+        } else {
+            match parent {
+                // Local coordinates if we have a parent
+                Some(_) => {
+                    // Add the renderable entity
+                    context.scene.insert((
+                        coords::LocalPosition::from(position),
+                        coords::LocalRotation::from(rotation),
+                        coords::LocalScale::from(scale),
+                        coords::Position::default(),
+                        coords::Rotation::default(),
+                        coords::Scale::default(),
+                        ecs::Named(name.to_string()),
+                    ))
+                }
 
-            // Pass along the children nodes
-            if let Some(children) = node.children.as_ref() {
-                eval.extend(children.iter().map(|x| (&nodes[x.value()], Some(entity))));
+                // Global coordinates if we do not have a parent
+                None => {
+                    // Add the renderable entity
+                    context.scene.insert((
+                        coords::Position::from(position),
+                        coords::Rotation::from(rotation),
+                        coords::Scale::from(scale),
+                        ecs::Named(name.to_string()),
+                    ))
+                }
             }
+        };
+
+        // Attach this entity to its parent if it had any
+        if let Some(parent) = parent {
+            context.scene.attach(entity, parent).unwrap();
+        }
 
-            count += 1;
+        // Pass along the children nodes
+        if let Some(children) = node.children.as_ref() {
+            eval.extend(children.iter().map(|x| (&nodes[x.value()], Some(entity))));
         }
 
-        log::debug!("Loaded {} entities into the world", count);
-        log::debug!("Loaded {} unique meshes into the world", meshes.len());
-        log::debug!(
-            "Loaded {} unique material instances into the world",
-            mapped_materials.len()
-        );
-        log::debug!(
-            "Loaded {} albedo maps into the world",
-            cached_albedo_maps.len()
-        );
-        log::debug!(
-            "Loaded {} normal maps into the world",
-            cached_normal_maps.len()
-        );
-        log::debug!("Loaded {} mask maps into the world", cached_mask_maps.len());
-
-        Ok(GltfScene)
+        entities.push(entity);
     }
+
+    log::debug!("Loaded {} entities into the world", entities.len());
+    log::debug!("Loaded {} unique meshes into the world", meshes.len());
+    log::debug!(
+        "Loaded {} unique material instances into the world",
+        mapped_materials.len()
+    );
+    log::debug!(
+        "Loaded {} albedo maps into the world",
+        cached_albedo_maps.len()
+    );
+    log::debug!(
+        "Loaded {} normal maps into the world",
+        cached_normal_maps.len()
+    );
+    log::debug!("Loaded {} mask maps into the world", cached_mask_maps.len());
+
+    Ok(GltfScene { entities })
 }
 
 type MinMax<'b> = (Option<&'b gltf::json::Value>, Option<&'b gltf::json::Value>);
 
-type Value<'a, 'b> = &'a (&'b [u8], (&'b Type, &'b ComponentType), MinMax<'b>);
+type Accessor<'b> = (&'b [u8], (&'b Type, &'b ComponentType), MinMax<'b>);
+
+type Value<'a, 'b> = &'a Accessor<'b>;
+
+// Decode the vertices and triangles of a mesh from its accessors and optimize it
+fn decode_mesh(key: CachedMeshKey, accessors: &[Accessor]) -> DecodedMesh {
+    // Create buffers and AABB
+    let (mut positions, aabb) = create_positions_vec(&accessors[key.0]);
+
+    // TODO: Generate normals / tangents / uvs if missing
+    let mut normals = key.1.map(|index| create_normals_vec(&accessors[index]));
+    let mut tangents = key.2.map(|index| create_tangents_vec(&accessors[index]));
+    let mut tex_coords = Some(
+        key.3
+            .map(|index| create_tex_coords_vec(&accessors[index]))
+            .unwrap_or_else(|| vec![vek::Vec2::zero(); positions.len()]),
+    );
+    let mut triangles = create_triangles_vec(&accessors[key.4]);
+
+    // Optionally generate the tangents
+    if let (Some(normals), Some(tex_coords)) = (normals.as_ref(), tex_coords.as_ref()) {
+        tangents =
+            Some(super::compute_tangents(&positions, normals, tex_coords, &triangles).unwrap());
+    }
+
+    let mut temp_positions = Some(positions.as_mut_slice());
+    let mut temp_normals = normals.as_deref_mut();
+    let mut temp_tangents = tangents.as_deref_mut();
+    let mut temp_tex_coords = tex_coords.as_deref_mut();
+
+    // Optimize the mesh after we load it
+    super::optimize(
+        true,
+        true,
+        true,
+        &mut temp_positions,
+        &mut temp_normals,
+        &mut temp_tangents,
+        &mut temp_tex_coords,
+        &mut triangles,
+    );
+
+    DecodedMesh {
+        positions,
+        normals,
+        tangents,
+        tex_coords,
+        triangles,
+        aabb,
+    }
+}
 
 // Create the position vertices required by all meshes and also create an AABB
 fn create_positions_vec(value: Value) -> (Vec<vek::Vec4<f32>>, Option<math::Aabb<f32>>) {
@@ -859,14 +1058,14 @@ fn map_wrapping_mode(wrap: gltf::json::texture::WrappingMode) -> SamplerWrap {
     }
 }
 
-// Create a texture used for a material used in the glTF scene
+// Decode the texels of a texture used for a material used in the glTF scene
 // This should be executed in multiple threads for maximum efficency
-fn create_material_texture<T: Texel + ImageTexel>(
-    graphics: Graphics,
+fn decode_material_texture<T: ImageTexel>(
     texture: &gltf::json::Texture,
     samplers: &[gltf::json::texture::Sampler],
     images: &[(&[u8], &str)],
-) -> Texture2D<T> {
+    imports: Option<&ImportCache>,
+) -> DecodedTexture<T> {
     let (bytes, extension) = &images[texture.source.value()];
     let name = texture.name.as_deref().unwrap_or("Untitled Texture");
 
@@ -876,34 +1075,66 @@ fn create_material_texture<T: Texel + ImageTexel>(
         Arc::from(bytes.to_vec()),
         Path::new(name),
         None,
-    );
+    )
+    .with_import_cache(imports.cloned());
 
-    let sampler = sampling(samplers, texture.sampler);
+    let raw = RawTexels::<T>::deserialize(data, (), TextureScale::Default).unwrap();
+    decoded_texture(raw, sampling(samplers, texture.sampler))
+}
 
-    Texture2D::<T>::deserialize(
-        data,
+// Generate the mip levels of decoded texels (disabled for non power of two textures)
+fn decoded_texture<T: ImageTexel>(
+    raw: RawTexels<T>,
+    sampling: SamplerSettings,
+) -> DecodedTexture<T> {
+    let mips = generate_mip_map::<T, (vek::Vec2<u32>, vek::Extent2<u32>)>(&raw.0, raw.1);
+    DecodedTexture {
+        raw,
+        mips,
+        sampling,
+    }
+}
+
+// Create the GPU texture of decoded texels on the main thread
+fn create_texture<T: ImageTexel>(graphics: &Graphics, decoded: DecodedTexture<T>) -> Texture2D<T> {
+    let DecodedTexture {
+        raw: RawTexels(texels, dimensions),
+        mips,
+        sampling,
+    } = decoded;
+
+    // Convert the vecs to slices
+    let mips = mips
+        .as_ref()
+        .map(|mips| mips.iter().map(Vec::as_slice).collect::<Vec<_>>());
+    let mipmaps = match mips.as_deref() {
+        Some(mips) => TextureMipMaps::Manual { mips },
+        None => TextureMipMaps::Disabled,
+    };
+
+    Texture2D::<T>::from_texels(
         graphics,
-        TextureImportSettings {
-            sampling: Some(sampler),
-            views: &[TextureViewSettings::whole::<
-                <Texture2D<T> as Texture>::Region,
-            >()],
-            usage: TextureUsage::SAMPLED | TextureUsage::COPY_DST,
-            scale: TextureScale::Default,
-            mipmaps: TextureMipMaps::Manual { mips: &[] },
-        },
+        Some(&texels),
+        dimensions,
+        TextureUsage::SAMPLED | TextureUsage::COPY_DST,
+        &[TextureViewSettings::whole::<
+            <Texture2D<T> as Texture>::Region,
+        >()],
+        Some(sampling),
+        mipmaps,
     )
     .unwrap()
 }
 
+// Decode the texels of a mask texture
 // r: ambient occlusion, g: roughness, b: metallic
-fn create_material_mask_texture(
-    graphics: Graphics,
+fn decode_material_mask_texture(
     metallic_roughness: Option<&gltf::json::Texture>,
     occlusion: Option<&gltf::json::Texture>,
     samplers: &[gltf::json::texture::Sampler],
     images: &[(&[u8], &str)],
-) -> MaskMap {
+    imports: Option<&ImportCache>,
+) -> DecodedTexture<MaskTexel> {
     assert!(metallic_roughness.is_some() || occlusion.is_some());
 
     let sampler = match (metallic_roughness, occlusion) {
@@ -923,7 +1154,8 @@ fn create_material_mask_texture(
             Arc::from(bytes.to_vec()),
             Path::new(name),
             None,
-        );
+        )
+        .with_import_cache(imports.cloned());
 
         RawTexels::<RGBA<Normalized<u8>>>::deserialize(data, (), TextureScale::Default).unwrap()
     });
@@ -937,7 +1169,8 @@ fn create_material_mask_texture(
             Arc::from(bytes.to_vec()),
             Path::new(name),
             None,
-        );
+        )
+        .with_import_cache(imports.cloned());
 
         RawTexels::<R<Normalized<u8>>>::deserialize(data, (), TextureScale::Default).unwrap()
     });
@@ -973,17 +1206,5 @@ fn create_material_mask_texture(
     };
 
     let raw = RawTexels(data, extent.unwrap());
-
-    texture2d_from_raw(
-        graphics,
-        TextureImportSettings {
-            sampling: Some(sampler),
-            views: &[TextureViewSettings::whole::<<MaskMap as Texture>::Region>()],
-            usage: TextureUsage::SAMPLED | TextureUsage::COPY_DST,
-            scale: TextureScale::Default,
-            mipmaps: TextureMipMaps::Manual { mips: &[] },
-        },
-        raw,
-    )
-    .unwrap()
+    decoded_texture(raw, sampler)
 }
@@ -1,5 +1,6 @@
 pub mod attributes;
 mod errors;
+mod import;
 mod mesh;
 pub mod settings;
 mod triangles;
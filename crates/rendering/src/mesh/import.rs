@@ -0,0 +1,159 @@
+use super::attributes::{RawNormal, RawPosition, RawTangent, RawTexCoord};
+use crate::{MeshImportError, MeshImportSettings};
+use bytemuck::Pod;
+use graphics::Triangle;
+use obj::TexturedVertex;
+
+// Vertices and triangles of a mesh after it was parsed, transformed and optimized
+// This is what gets stored within the import cache so we can skip parsing and optimizing on the next run
+pub(crate) struct ProcessedMesh {
+    pub(crate) positions: Vec<RawPosition>,
+    pub(crate) normals: Option<Vec<RawNormal>>,
+    pub(crate) tangents: Option<Vec<RawTangent>>,
+    pub(crate) tex_coords: Option<Vec<RawTexCoord>>,
+    pub(crate) triangles: Vec<Triangle<u32>>,
+}
+
+impl ProcessedMesh {
+    // Parse an .obj mesh and apply the import settings
+    pub(crate) fn from_obj(
+        name: &str,
+        bytes: &[u8],
+        settings: MeshImportSettings,
+    ) -> Result<Self, MeshImportError> {
+        let parsed = obj::load_obj::<TexturedVertex, &[u8], u32>(bytes)
+            .map_err(MeshImportError::ObjError)?;
+        log::debug!(
+            "Parsed mesh from file '{}', vertex count: {}, index count: {}",
+            name,
+            parsed.vertices.len(),
+            parsed.indices.len()
+        );
+
+        // Create temporary slicetors containing the vertex attributes
+        let capacity = parsed.vertices.len();
+        let mut positions = Vec::<RawPosition>::with_capacity(capacity);
+        let mut normals = settings
+            .use_normals
+            .then(|| Vec::<RawNormal>::with_capacity(capacity));
+        let mut tex_coords = settings
+            .use_tex_coords
+            .then(|| Vec::<RawTexCoord>::with_capacity(capacity));
+        let mut indices = parsed.indices;
+        let triangles = bytemuck::cast_slice_mut(&mut indices);
+        use vek::{Vec2, Vec3};
+
+        // Convert the vertices into the separate buffer
+        for vertex in parsed.vertices {
+            // Read and add the position
+            positions.push(vek::Vec3::from_slice(&vertex.position).with_w(0f32));
+
+            // Read and add the normal
+            if let Some(normals) = &mut normals {
+                let read = Vec3::from_slice(&vertex.normal);
+                let viewed = read.map(|f| (f * 127.0) as i8);
+                normals.push(viewed.with_w(0));
+            }
+
+            // Read and add the texture coordinate
+            if let Some(tex_coords) = &mut tex_coords {
+                let read = Vec2::from_slice(&vertex.texture);
+                tex_coords.push(read);
+            }
+        }
+
+        // Optionally generate the tangents
+        let mut tangents = settings.use_tangents.then(|| {
+            super::compute_tangents(
+                &positions,
+                normals.as_ref().unwrap(),
+                tex_coords.as_ref().unwrap(),
+                triangles,
+            )
+            .unwrap()
+        });
+
+        // Remap the attributes into a slices and options
+        let mut normals = normals.as_deref_mut();
+        let mut tangents = tangents.as_deref_mut();
+        let mut tex_coords = tex_coords.as_deref_mut();
+
+        // Apply the mesh settings to the attributes
+        let mut positions = Some(positions.as_mut_slice());
+        super::apply_vec_settings(
+            settings,
+            &mut positions,
+            &mut normals,
+            &mut tangents,
+            &mut tex_coords,
+            triangles,
+        );
+
+        // Optimize the mesh after we load it
+        let triangles = bytemuck::cast_slice_mut(&mut indices);
+        super::optimize(
+            settings.optimize_vertex_cache,
+            settings.optimize_vertex_fetch,
+            settings.optimize_overdraw,
+            &mut positions,
+            &mut normals,
+            &mut tangents,
+            &mut tex_coords,
+            triangles,
+        );
+
+        Ok(Self {
+            positions: positions.unwrap().to_vec(),
+            normals: normals.map(|slice| slice.to_vec()),
+            tangents: tangents.map(|slice| slice.to_vec()),
+            tex_coords: tex_coords.map(|slice| slice.to_vec()),
+            triangles: triangles.to_vec(),
+        })
+    }
+
+    // Convert the processed mesh to the bytes stored within the import cache
+    pub(crate) fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        write_section(&mut bytes, Some(&self.positions));
+        write_section(&mut bytes, self.normals.as_deref());
+        write_section(&mut bytes, self.tangents.as_deref());
+        write_section(&mut bytes, self.tex_coords.as_deref());
+        write_section(&mut bytes, Some(&self.triangles));
+        bytes
+    }
+
+    // Convert the bytes stored within the import cache back to a processed mesh
+    pub(crate) fn from_bytes(mut bytes: &[u8]) -> Option<Self> {
+        let bytes = &mut bytes;
+        Some(Self {
+            positions: read_section(bytes)??,
+            normals: read_section(bytes)?,
+            tangents: read_section(bytes)?,
+            tex_coords: read_section(bytes)?,
+            triangles: read_section(bytes)??,
+        })
+    }
+}
+
+// Write an optional attribute slice (flag, element count, then the raw elements)
+fn write_section<T: Pod>(bytes: &mut Vec<u8>, slice: Option<&[T]>) {
+    bytes.push(slice.is_some() as u8);
+    let slice = slice.unwrap_or_default();
+    bytes.extend_from_slice(&(slice.len() as u32).to_le_bytes());
+    bytes.extend_from_slice(bytemuck::cast_slice(slice));
+}
+
+// Read an optional attribute slice. Returns None if the bytes are invalid
+fn read_section<T: Pod>(bytes: &mut &[u8]) -> Option<Option<Vec<T>>> {
+    let (&present, rest) = bytes.split_first()?;
+    let count = u32::from_le_bytes(rest.get(..4)?.try_into().unwrap()) as usize;
+    let size = count * std::mem::size_of::<T>();
+    let data = rest.get(4..(4 + size))?;
+    *bytes = &rest[(4 + size)..];
+
+    let elements = data
+        .chunks_exact(std::mem::size_of::<T>())
+        .map(bytemuck::pod_read_unaligned)
+        .collect();
+    Some((present != 0).then_some(elements))
+}
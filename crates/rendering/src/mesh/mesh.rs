@@ -1,18 +1,18 @@
 use super::attributes::*;
+use super::import::ProcessedMesh;
 use crate::mesh::attributes::{Normal, Position, Tangent, TexCoord};
 use crate::{
     AttributeBuffer, Direct, Indirect, IndirectMeshArgs, MeshAttribute, MeshAttributes,
-    MeshImportError, MeshImportSettings, MeshInitializationError, MultiDrawIndirect,
+    MeshImportError, MeshImportSettings, MeshInitializationError, MeshMeta, MultiDrawIndirect,
     MultiDrawIndirectArgs, MultiDrawIndirectCount, MultiDrawIndirectCountArgs, RenderPath,
     TrianglesMut, TrianglesRef, VerticesMut, VerticesRef,
 };
-use assets::Asset;
+use assets::{Asset, ImportKey, Reload};
 
 use graphics::{
     BufferMode, BufferUsage, DrawCountIndirectBuffer, DrawIndexedIndirectBuffer, Graphics,
     Triangle, TriangleBuffer,
 };
-use obj::TexturedVertex;
 
 use std::cell::{Cell, RefCell};
 use utils::Handle;
@@ -485,122 +485,60 @@ impl Asset for Mesh {
         settings: Self::Settings<'_>,
     ) -> Result<Self, Self::Err> {
         let graphics = context;
-
-        // Load the .Obj mesh
         let name = data.path().file_name().unwrap().to_str().unwrap();
-        let parsed = obj::load_obj::<TexturedVertex, &[u8], u32>(data.bytes())
-            .map_err(MeshImportError::ObjError)?;
-        log::debug!(
-            "Parsed mesh from file '{}', vertex count: {}, index count: {}",
-            name,
-            parsed.vertices.len(),
-            parsed.indices.len()
-        );
-
-        // Create temporary slicetors containing the vertex attributes
-        let capacity = parsed.vertices.len();
-        let mut positions = Vec::<RawPosition>::with_capacity(capacity);
-        let mut normals = settings
-            .use_normals
-            .then(|| Vec::<RawNormal>::with_capacity(capacity));
-        let mut tex_coords = settings
-            .use_tex_coords
-            .then(|| Vec::<RawTexCoord>::with_capacity(capacity));
-        let mut indices = parsed.indices;
-        let triangles = bytemuck::cast_slice_mut(&mut indices);
-        use vek::{Vec2, Vec3};
-
-        // Convert the vertices into the separate buffer
-        for vertex in parsed.vertices {
-            // Read and add the position
-            positions.push(vek::Vec3::from_slice(&vertex.position).with_w(0f32));
-
-            // Read and add the normal
-            if let Some(normals) = &mut normals {
-                let read = Vec3::from_slice(&vertex.normal);
-                let viewed = read.map(|f| (f * 127.0) as i8);
-                normals.push(viewed.with_w(0));
-            }
 
-            // Read and add the texture coordinate
-            if let Some(tex_coords) = &mut tex_coords {
-                let read = Vec2::from_slice(&vertex.texture);
-                tex_coords.push(read);
+        // Reuse the mesh that was processed in a previous run if possible
+        let key = ImportKey::new("mesh", data.bytes(), &settings);
+        let cached = data
+            .cached_import(key)
+            .and_then(|bytes| ProcessedMesh::from_bytes(&bytes));
+
+        // Otherwise parse and optimize the mesh, then store it in the import cache for the next run
+        let processed = match cached {
+            Some(processed) => processed,
+            None => {
+                let processed = ProcessedMesh::from_obj(name, data.bytes(), settings)?;
+                data.store_import(key, &processed.to_bytes());
+                processed
             }
-        }
-
-        // Optionally generate the tangents
-        let mut tangents = settings.use_tangents.then(|| {
-            super::compute_tangents(
-                &positions,
-                normals.as_ref().unwrap(),
-                tex_coords.as_ref().unwrap(),
-                triangles,
-            )
-            .unwrap()
-        });
-
-        // Remap the attributes into a slices and options
-        let mut normals = normals.as_deref_mut();
-        let mut tangents = tangents.as_deref_mut();
-        let mut tex_coords = tex_coords.as_deref_mut();
-
-        // Apply the mesh settings to the attributes
-        let mut positions = Some(positions.as_mut_slice());
-        super::apply_vec_settings(
-            settings,
-            &mut positions,
-            &mut normals,
-            &mut tangents,
-            &mut tex_coords,
-            triangles,
-        );
-
-        // Optimize the mesh after we load it
-        let triangles = bytemuck::cast_slice_mut(&mut indices);
-        super::optimize(
-            settings.optimize_vertex_cache,
-            settings.optimize_vertex_fetch,
-            settings.optimize_overdraw,
-            &mut positions,
-            &mut normals,
-            &mut tangents,
-            &mut tex_coords,
-            triangles,
-        );
+        };
 
-        log::debug!(
-            "Loaded {} position vertices",
-            positions.as_ref().unwrap().len()
-        );
+        log::debug!("Loaded {} position vertices", processed.positions.len());
         log::debug!(
             "Loaded {} normal vertices",
-            normals.as_ref().map(|tc| tc.len()).unwrap_or_default()
+            processed.normals.as_ref().map_or(0, Vec::len)
         );
         log::debug!(
             "Loaded {} tangent vertices",
-            tangents.as_ref().map(|tc| tc.len()).unwrap_or_default()
+            processed.tangents.as_ref().map_or(0, Vec::len)
         );
         log::debug!(
             "Loaded {} texture coordinate vertices",
-            tex_coords.as_ref().map(|tc| tc.len()).unwrap_or_default()
+            processed.tex_coords.as_ref().map_or(0, Vec::len)
         );
 
         // Create an AABB for this mesh
-        let aabb = crate::aabb_from_points(positions.as_ref().unwrap());
+        let aabb = crate::aabb_from_points(&processed.positions);
 
         // Generate the mesh and it's corresponding data
         Mesh::from_slices(
             &graphics,
             settings.buffer_mode,
             settings.buffer_usage,
-            positions.as_deref(),
-            normals.as_deref(),
-            tangents.as_deref(),
-            tex_coords.as_deref(),
-            triangles,
+            Some(&processed.positions),
+            processed.normals.as_deref(),
+            processed.tangents.as_deref(),
+            processed.tex_coords.as_deref(),
+            &processed.triangles,
             aabb,
         )
         .map_err(MeshImportError::Initialization)
     }
+
+    fn from_meta<'s>(meta: &assets::Meta) -> Option<Result<Self::Settings<'s>, assets::MetaError>> {
+        Some(meta.parse::<MeshMeta>().map(MeshImportSettings::from))
+    }
 }
+
+// Reloaded meshes replace the old vertex and index buffers
+impl Reload for Mesh {}
@@ -1,4 +1,6 @@
 use graphics::{BufferMode, BufferUsage};
+use serde::Deserialize;
+use std::hash::{Hash, Hasher};
 
 // Mesh settings that we will use whenever we import a new mesh from a file
 #[derive(Debug, Clone, Copy, PartialEq)]
@@ -52,3 +54,93 @@ impl Default for MeshImportSettings {
         }
     }
 }
+
+// Only hashes the settings that change the processed vertices and triangles
+// Used to find the processed mesh within the import cache
+impl Hash for MeshImportSettings {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.use_normals.hash(state);
+        self.use_tangents.hash(state);
+        self.use_tex_coords.hash(state);
+        self.invert_normals.hash(state);
+        self.invert_tangents.hash(state);
+        self.invert_tex_coords.hash(state);
+        self.invert_triangle_ordering.hash(state);
+        self.translation.map(f32::to_bits).hash(state);
+        self.rotation.into_vec4().map(f32::to_bits).hash(state);
+        self.scale.map(f32::to_bits).hash(state);
+        self.optimize_overdraw.hash(state);
+        self.optimize_vertex_cache.hash(state);
+        self.optimize_vertex_fetch.hash(state);
+    }
+}
+
+// Mesh import settings that artists can write within the "<mesh>.meta.ron" sidecar file
+// Missing fields use the same values as the default mesh import settings
+#[derive(Deserialize)]
+#[serde(default)]
+pub struct MeshMeta {
+    // Optional attributes we can discard when loading
+    pub use_normals: bool,
+    pub use_tangents: bool,
+    pub use_tex_coords: bool,
+
+    // We can invert all of the attributes if we want to
+    pub invert_normals: bool,
+    pub invert_tangents: bool,
+    pub invert_tex_coords: [bool; 2],
+    pub invert_triangle_ordering: bool,
+
+    // Custom transformations (rotation is given as a XYZW quaternion)
+    pub translation: [f32; 3],
+    pub rotation: [f32; 4],
+    pub scale: [f32; 3],
+
+    // Custom mesh optimization
+    pub optimize_overdraw: bool,
+    pub optimize_vertex_cache: bool,
+    pub optimize_vertex_fetch: bool,
+}
+
+impl Default for MeshMeta {
+    fn default() -> Self {
+        let settings = MeshImportSettings::default();
+
+        Self {
+            use_normals: settings.use_normals,
+            use_tangents: settings.use_tangents,
+            use_tex_coords: settings.use_tex_coords,
+            invert_normals: settings.invert_normals,
+            invert_tangents: settings.invert_tangents,
+            invert_tex_coords: settings.invert_tex_coords.into_array(),
+            invert_triangle_ordering: settings.invert_triangle_ordering,
+            translation: settings.translation.into_array(),
+            rotation: settings.rotation.into_vec4().into_array(),
+            scale: settings.scale.into_array(),
+            optimize_overdraw: settings.optimize_overdraw,
+            optimize_vertex_cache: settings.optimize_vertex_cache,
+            optimize_vertex_fetch: settings.optimize_vertex_fetch,
+        }
+    }
+}
+
+impl From<MeshMeta> for MeshImportSettings {
+    fn from(meta: MeshMeta) -> Self {
+        Self {
+            use_normals: meta.use_normals,
+            use_tangents: meta.use_tangents,
+            use_tex_coords: meta.use_tex_coords,
+            invert_normals: meta.invert_normals,
+            invert_tangents: meta.invert_tangents,
+            invert_tex_coords: meta.invert_tex_coords.into(),
+            invert_triangle_ordering: meta.invert_triangle_ordering,
+            translation: meta.translation.into(),
+            rotation: vek::Quaternion::from_vec4(meta.rotation.into()),
+            scale: meta.scale.into(),
+            optimize_overdraw: meta.optimize_overdraw,
+            optimize_vertex_cache: meta.optimize_vertex_cache,
+            optimize_vertex_fetch: meta.optimize_vertex_fetch,
+            ..Default::default()
+        }
+    }
+}
@@ -1,5 +1,5 @@
 use crate::{DefaultMaterialResources, Material, Pass, RenderPath, Renderer, SubSurface, Surface};
-use ecs::Scene;
+use ecs::{Scene, Scenes};
 use math::ExplicitVertices;
 use rayon::prelude::ParallelIterator;
 use smallvec::SmallVec;
@@ -128,6 +128,20 @@ pub(super) fn cull_surfaces<'r, P: Pass, M: Material>(
 
     // Get all the entities that contain a visible surface
     let mut scene = world.get_mut::<Scene>().unwrap();
+    let mut scenes = world.get_mut::<Scenes>().unwrap();
+    cull_scene_surfaces::<P, M>(&mut scene, defaults);
+
+    // Also cull the surfaces of the rendered resident scenes
+    for (_, scene) in scenes.targeted_mut(|t| t.rendering) {
+        cull_scene_surfaces::<P, M>(scene, defaults);
+    }
+}
+
+// Update the "culled" parameter of each surface within a single scene
+fn cull_scene_surfaces<'r, P: Pass, M: Material>(
+    scene: &mut Scene,
+    defaults: &DefaultMaterialResources<'r>,
+) {
     let query = scene.query_mut::<(&mut Surface<M>, &Renderer)>();
 
     // Iterate over the surfaces of this material and update their culled state
@@ -122,6 +122,9 @@ pub trait DynPipeline {
         stats: &mut PassStats,
         render_pass: &mut ActiveRenderPass<'r, '_, (), ShadowDepthLayout>,
     );
+
+    // Check if any of the shaders of the pipeline were modified since the last frame
+    fn is_changed(&self, assets: &Assets) -> bool;
 }
 
 impl<M: Material> DynPipeline for Pipeline<M> {
@@ -154,4 +157,12 @@ impl<M: Material> DynPipeline for Pipeline<M> {
             super::render_surfaces::<ShadowPass, M>(world, pipeline, default, stats, render_pass);
         }
     }
+
+    fn is_changed(&self, assets: &Assets) -> bool {
+        self.shader.is_changed(assets)
+            || self
+                .shadow_shader
+                .as_ref()
+                .is_some_and(|shader| shader.is_changed(assets))
+    }
 }
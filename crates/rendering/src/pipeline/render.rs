@@ -2,7 +2,7 @@ use crate::{
     DefaultMaterialResources, Material, Mesh, MeshAttribute, MeshAttributes, Pass, PassStats,
     RenderPath, Renderer, Surface,
 };
-use ecs::Scene;
+use ecs::{Scene, Scenes};
 use graphics::{
     ActivePipeline, ActiveRenderPass, ActiveRenderPipeline, ColorLayout, DepthStencilLayout,
     RenderPipeline,
@@ -89,17 +89,23 @@ pub(super) fn render_surfaces<'r, P: Pass, M: Material>(
     let materials = world.get::<Storage<M>>().unwrap();
     let mut resources = M::fetch::<P>(world);
 
-    // Get all the entities that contain a visible surface
+    // Get all the entities that contain a visible surface (from the main scene and the rendered resident scenes)
     let scene = world.get::<Scene>().unwrap();
-    let filter = ecs::contains::<M::Query<'r>>();
-    let query = scene.query_with::<(&Surface<M>, &Renderer)>(filter);
-
-    // Get custom user components
-    let filter = ecs::contains::<(&Surface<M>, &Renderer)>();
-    let user = scene.query_with::<M::Query<'r>>(filter);
-
-    // Due to the filters, these MUST have the same length
-    debug_assert_eq!(query.len(), user.len());
+    let scenes = world.get::<Scenes>().unwrap();
+    let residents = scenes.targeted(|t| t.rendering).map(|(_, scene)| scene);
+    let mut vec = Vec::new();
+    for scene in std::iter::once(&*scene).chain(residents) {
+        let filter = ecs::contains::<M::Query<'r>>();
+        let query = scene.query_with::<(&Surface<M>, &Renderer)>(filter);
+
+        // Get custom user components
+        let filter = ecs::contains::<(&Surface<M>, &Renderer)>();
+        let user = scene.query_with::<M::Query<'r>>(filter);
+
+        // Due to the filters, these MUST have the same length
+        debug_assert_eq!(query.len(), user.len());
+        vec.extend(query.into_iter().zip(user));
+    }
 
     // Keep track of the last material
     let mut last_material: Option<Handle<M>> = None;
@@ -123,9 +129,6 @@ pub(super) fn render_surfaces<'r, P: Pass, M: Material>(
     let mut last_index_buffer: Option<&<M::RenderPath as RenderPath>::TriangleBuffer<u32>> = None;
 
     // Convert to sub-surfaces and discard invisible / culled surfaces
-    let iter = query.into_iter().zip(user);
-
-    let vec = iter.collect::<Vec<_>>();
     let sub_surfaces = vec.iter().flat_map(|((surface, renderer), user)| {
         surface
             .subsurfaces
@@ -28,6 +28,12 @@ fn load_lighting_shader(assets: &Assets, graphics: &Graphics) -> Shader {
         .unwrap();
 
     // Create the bind layout for the compositor shader
+    let compiler = create_lighting_compiler(assets, graphics);
+    Shader::new(vertex, fragment, &compiler).unwrap()
+}
+
+// Create the compiler that contains the bind layout of the compositor shader
+fn create_lighting_compiler<'a>(assets: &'a Assets, graphics: &'a Graphics) -> Compiler<'a> {
     let mut compiler = Compiler::new(assets, graphics);
 
     // Gbuffer textures and depth map
@@ -51,8 +57,7 @@ fn load_lighting_shader(assets: &Assets, graphics: &Graphics) -> Shader {
     compiler.use_uniform_buffer::<vek::Vec4<vek::Vec4<f32>>>("shadow_lightspace_matrices");
     compiler.use_sampled_texture::<ShadowMap>("shadow_map", true);
     compiler.use_sampler::<ShadowDepthLayout>("shadow_map_sampler", true);
-
-    Shader::new(vertex, fragment, &compiler).unwrap()
+    compiler
 }
 
 fn load_lighting_pass(
@@ -275,4 +280,19 @@ impl Compositor {
             lighting_pipeline,
         }
     }
+    // Rebuild the lighting pipeline if the lighting shader (or the files it includes) was modified
+    // The old pipeline is kept if the new shader fails to compile
+    pub(crate) fn reload(&mut self, graphics: &Graphics, assets: &Assets) {
+        if !self.lighting_pipeline.shader().is_changed(assets) {
+            return;
+        }
+
+        let mut shader = self.lighting_pipeline.shader().clone();
+        let compiler = create_lighting_compiler(assets, graphics);
+        match shader.reload(&compiler) {
+            Some(Ok(())) => self.lighting_pipeline = load_lighting_pipeline(graphics, shader),
+            Some(Err(error)) => log::error!("Lighting Shader Error: {:?}", error),
+            None => {}
+        }
+    }
 }
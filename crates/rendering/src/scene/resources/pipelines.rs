@@ -59,6 +59,42 @@ impl Pipelines {
         Ok(MaterialId(PhantomData))
     }
 
+    // Rebuild a registered material pipeline if its shaders (or the files they include) were modified
+    // Assumes that the material does not use any custom settings
+    pub fn reload<M: Material>(&mut self, graphics: &Graphics, assets: &Assets)
+    where
+        for<'x> M::Settings<'x>: Default,
+    {
+        self.reload_with::<M>(graphics, Default::default(), assets)
+    }
+
+    // Rebuild a registered material pipeline if its shaders (or the files they include) were modified
+    // The old pipeline is kept if the new one fails to initialize
+    pub fn reload_with<M: Material>(
+        &mut self,
+        graphics: &Graphics,
+        settings: M::Settings<'_>,
+        assets: &Assets,
+    ) {
+        let key = TypeId::of::<M>();
+        let Some(pipeline) = self.pipelines.get(&key) else {
+            return;
+        };
+
+        if !pipeline.is_changed(assets) {
+            return;
+        }
+
+        let name = utils::pretty_type_name::<M>();
+        match Pipeline::<M>::new(settings, graphics, assets) {
+            Ok(pipeline) => {
+                self.pipelines.insert(key, Rc::new(pipeline));
+                log::debug!("Reloaded pipeline for material {name}");
+            }
+            Err(error) => log::error!("Could not reload pipeline for material {name}: {error}"),
+        }
+    }
+
     // Get a MaterialID from a pre-initialized pipeline
     pub fn get<M: Material>(&self) -> Option<MaterialId<M>> {
         let key = TypeId::of::<M>();
@@ -25,6 +25,13 @@ fn update(world: &mut World) {
     let environment = world.get::<Environment>().unwrap();
     let shadow = world.get::<ShadowMapping>().unwrap();
     let _time = world.get::<Time>().unwrap();
+    let graphics = world.get::<Graphics>().unwrap();
+    let assets = world.get::<Assets>().unwrap();
+
+    // Recompile the lighting shader if it was modified
+    if !assets.changed().is_empty() {
+        compositor.reload(&graphics, &assets);
+    }
 
     // Write the post process settings to the buffer
     let value = compositor.post_process;
@@ -6,7 +6,18 @@ use world::{post_user, System, World};
 // Update the global mesh matrices of objects that have been modified
 // This will also handle frustum culling
 fn update(world: &mut World) {
-    let mut scene = world.get_mut::<Scene>().unwrap();
+    let mut scene = world.get_mut::<ecs::Scene>().unwrap();
+    let mut scenes = world.get_mut::<ecs::Scenes>().unwrap();
+    update_matrices(&mut scene);
+
+    // Resident scenes that get rendered need their matrices updated as well
+    for (_, scene) in scenes.targeted_mut(|t| t.rendering) {
+        update_matrices(scene);
+    }
+}
+
+// Update the matrices of the modified renderers within a single scene
+fn update_matrices(scene: &mut ecs::Scene) {
     use ecs::*;
 
     // Filter the objects that have changed only
@@ -364,6 +364,17 @@ fn render(world: &mut World) {
     drop(render_pass);
 }
 
+// Rebuild the material pipelines whose shaders were modified
+fn reload(world: &mut World) {
+    let graphics = world.get::<Graphics>().unwrap();
+    let assets = world.get::<Assets>().unwrap();
+    let mut pipelines = world.get_mut::<Pipelines>().unwrap();
+
+    if !assets.changed().is_empty() {
+        pipelines.reload::<PbrMaterial>(&graphics, &assets);
+    }
+}
+
 // The rendering system will be resposible for iterating through the entities and rendering them to the backbuffer texture
 pub fn system(system: &mut System) {
     system
@@ -371,6 +382,10 @@ pub fn system(system: &mut System) {
         .before(user)
         .after(assets::system)
         .after(graphics::common);
+    system
+        .insert_update(reload)
+        .after(assets::system)
+        .before(user);
     system
         .insert_update(render)
         .after(post_user)
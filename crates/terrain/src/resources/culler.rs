@@ -17,27 +17,30 @@ impl ChunkCuller {
             .load::<ComputeModule>("engine/shaders/terrain/cull.comp")
             .unwrap();
 
-        let mut compiler = Compiler::new(assets, graphics);
-
-        compiler.use_push_constant_layout(
-            PushConstantLayout::single(u32::size() * 2, ModuleVisibility::Compute).unwrap(),
-        );
-
-        compiler.use_storage_buffer::<u32>("visibility", StorageAccess::ReadOnly);
-        compiler.use_storage_buffer::<u32>("count", StorageAccess::ReadWrite);
-        compiler
-            .use_storage_buffer::<vek::Vec4<f32>>("input_position_scale", StorageAccess::ReadOnly);
-        compiler.use_storage_buffer::<vek::Vec4<f32>>(
-            "output_position_scale",
-            StorageAccess::WriteOnly,
-        );
-        compiler
-            .use_storage_buffer::<DrawIndexedIndirect>("input_indirect", StorageAccess::ReadOnly);
-        compiler
-            .use_storage_buffer::<DrawIndexedIndirect>("output_indirect", StorageAccess::WriteOnly);
-
+        let compiler = create_compute_cull_compiler(assets, graphics);
         Self {
             compute_cull: ComputeShader::new(module, &compiler).unwrap(),
         }
     }
 }
+
+// Create the compiler used by the chunk culling compute shader
+pub(crate) fn create_compute_cull_compiler<'a>(
+    assets: &'a Assets,
+    graphics: &'a Graphics,
+) -> Compiler<'a> {
+    let mut compiler = Compiler::new(assets, graphics);
+
+    compiler.use_push_constant_layout(
+        PushConstantLayout::single(u32::size() * 2, ModuleVisibility::Compute).unwrap(),
+    );
+
+    compiler.use_storage_buffer::<u32>("visibility", StorageAccess::ReadOnly);
+    compiler.use_storage_buffer::<u32>("count", StorageAccess::ReadWrite);
+    compiler.use_storage_buffer::<vek::Vec4<f32>>("input_position_scale", StorageAccess::ReadOnly);
+    compiler
+        .use_storage_buffer::<vek::Vec4<f32>>("output_position_scale", StorageAccess::WriteOnly);
+    compiler.use_storage_buffer::<DrawIndexedIndirect>("input_indirect", StorageAccess::ReadOnly);
+    compiler.use_storage_buffer::<DrawIndexedIndirect>("output_indirect", StorageAccess::WriteOnly);
+    compiler
+}
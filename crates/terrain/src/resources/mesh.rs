@@ -43,37 +43,7 @@ impl MeshGenerator {
         let module = assets
             .load::<ComputeModule>("engine/shaders/terrain/vertices.comp")
             .unwrap();
-        let mut compiler = Compiler::new(assets, graphics);
-
-        compiler.use_push_constant_layout(
-            graphics::PushConstantLayout::single(
-                u32::size() + f32::size(),
-                ModuleVisibility::Compute,
-            )
-            .unwrap(),
-        );
-
-        // Set the voxels texture that we will sample
-        compiler.use_sampled_texture::<Texture3D<RG<f32>>>("voxels", false);
-        compiler.use_sampler::<RG<f32>>("voxels_sampler", false);
-
-        // Set the cached indices that we will use to reuse vertices
-        compiler
-            .use_storage_texture::<Texture3D<R<u32>>>("cached_indices", StorageAccess::WriteOnly);
-
-        // Set storage buffers and counters
-        compiler.use_storage_buffer::<<XYZW<f32> as Vertex>::Storage>(
-            "vertices",
-            StorageAccess::WriteOnly,
-        );
-        compiler.use_storage_buffer::<[u32; 2]>("counters", StorageAccess::ReadWrite);
-
-        // Set vertex generation parameters (constants)
-        compiler.use_constant(0, settings.mesher.size);
-        compiler.use_constant(
-            1,
-            matches!(settings.rendering.mode, TerrainRenderingMode::Blocky),
-        );
+        let compiler = create_compute_vertices_compiler(assets, graphics, settings);
 
         // Create the compute vertices shader
         let compute_vertices = ComputeShader::new(module, &compiler).unwrap();
@@ -82,21 +52,7 @@ impl MeshGenerator {
         let module = assets
             .load::<ComputeModule>("engine/shaders/terrain/quads.comp")
             .unwrap();
-        let mut compiler = Compiler::new(assets, graphics);
-
-        // Set the voxels texture that we will sample
-        compiler.use_storage_texture::<Texture3D<RG<f32>>>("voxels", StorageAccess::ReadOnly);
-
-        // Set the cached indices that we will use to reuse vertices
-        compiler
-            .use_storage_texture::<Texture3D<R<u32>>>("cached_indices", StorageAccess::ReadOnly);
-
-        // Set counters and storage buffers
-        compiler.use_storage_buffer::<[u32; 2]>("counters", StorageAccess::ReadWrite);
-        compiler.use_storage_buffer::<u32>("triangles", StorageAccess::WriteOnly);
-
-        // Set size constants
-        compiler.use_constant(0, settings.mesher.size);
+        let compiler = create_compute_quads_compiler(assets, graphics, settings);
 
         // Create the compute quads shader
         let compute_quads = ComputeShader::new(module, &compiler).unwrap();
@@ -115,3 +71,60 @@ impl MeshGenerator {
         }
     }
 }
+
+// Create the compiler used by the vertex generation compute shader
+pub(crate) fn create_compute_vertices_compiler<'a>(
+    assets: &'a Assets,
+    graphics: &'a Graphics,
+    settings: &TerrainSettings,
+) -> Compiler<'a> {
+    let mut compiler = Compiler::new(assets, graphics);
+
+    compiler.use_push_constant_layout(
+        graphics::PushConstantLayout::single(u32::size() + f32::size(), ModuleVisibility::Compute)
+            .unwrap(),
+    );
+
+    // Set the voxels texture that we will sample
+    compiler.use_sampled_texture::<Texture3D<RG<f32>>>("voxels", false);
+    compiler.use_sampler::<RG<f32>>("voxels_sampler", false);
+
+    // Set the cached indices that we will use to reuse vertices
+    compiler.use_storage_texture::<Texture3D<R<u32>>>("cached_indices", StorageAccess::WriteOnly);
+
+    // Set storage buffers and counters
+    compiler
+        .use_storage_buffer::<<XYZW<f32> as Vertex>::Storage>("vertices", StorageAccess::WriteOnly);
+    compiler.use_storage_buffer::<[u32; 2]>("counters", StorageAccess::ReadWrite);
+
+    // Set vertex generation parameters (constants)
+    compiler.use_constant(0, settings.mesher.size);
+    compiler.use_constant(
+        1,
+        matches!(settings.rendering.mode, TerrainRenderingMode::Blocky),
+    );
+    compiler
+}
+
+// Create the compiler used by the quad generation compute shader
+pub(crate) fn create_compute_quads_compiler<'a>(
+    assets: &'a Assets,
+    graphics: &'a Graphics,
+    settings: &TerrainSettings,
+) -> Compiler<'a> {
+    let mut compiler = Compiler::new(assets, graphics);
+
+    // Set the voxels texture that we will sample
+    compiler.use_storage_texture::<Texture3D<RG<f32>>>("voxels", StorageAccess::ReadOnly);
+
+    // Set the cached indices that we will use to reuse vertices
+    compiler.use_storage_texture::<Texture3D<R<u32>>>("cached_indices", StorageAccess::ReadOnly);
+
+    // Set counters and storage buffers
+    compiler.use_storage_buffer::<[u32; 2]>("counters", StorageAccess::ReadWrite);
+    compiler.use_storage_buffer::<u32>("triangles", StorageAccess::WriteOnly);
+
+    // Set size constants
+    compiler.use_constant(0, settings.mesher.size);
+    compiler
+}
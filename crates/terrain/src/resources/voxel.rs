@@ -1,18 +1,14 @@
-use std::{sync::mpsc::Receiver, thread::JoinHandle};
-
 use crate::{create_texture3d, TerrainSettings};
 use assets::Assets;
 use graphics::{
     Compiler, ComputeModule, ComputeShader, GpuPod, Graphics, ModuleVisibility, PushConstantLayout,
     SamplerSettings, SamplerWrap, StorageAccess, Texel, Texture3D, TextureUsage, Vertex, RG,
 };
-use notify::Watcher;
 
 // Voxel generator that will be solely used for generating voxels
 pub struct VoxelGenerator {
     pub(crate) compute_voxels: ComputeShader,
     pub(crate) voxel_texture: Texture3D<RG<f32>>,
-    pub(crate) hot_reload: Option<(Receiver<()>, JoinHandle<()>)>,
 }
 
 impl VoxelGenerator {
@@ -40,40 +36,9 @@ impl VoxelGenerator {
             }),
         );
 
-        // Create a watcher that will watch the voxels compute shader file for any changes
-        let hot_reload = if !assets.packed() {
-            let (tx, rx) = std::sync::mpsc::channel::<()>();
-            let path = assets.path("engine/shaders/terrain/voxel.glsl").unwrap();
-
-            let handle = std::thread::spawn(move || {
-                let mut watcher = notify::recommended_watcher(
-                    move |event: Result<notify::Event, notify::Error>| {
-                        if let Ok(event) = event {
-                            match event.kind {
-                                notify::EventKind::Modify(_) => {
-                                    tx.send(());
-                                }
-                                _ => {}
-                            }
-                        }
-                    },
-                )
-                .unwrap();
-                watcher
-                    .watch(&path, notify::RecursiveMode::NonRecursive)
-                    .unwrap();
-                std::thread::sleep(std::time::Duration::from_secs(u64::MAX));
-            });
-
-            Some((rx, handle))
-        } else {
-            None
-        };
-
         Self {
             compute_voxels,
             voxel_texture: voxel_textures,
-            hot_reload,
         }
     }
 }
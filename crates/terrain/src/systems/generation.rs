@@ -1,15 +1,27 @@
-use crate::{Chunk, ChunkState, Terrain};
+use crate::{Chunk, ChunkState, Terrain, TerrainMaterial};
 use assets::Assets;
 use coords::{Position, Scale};
 use ecs::{Entity, Scene};
 use graphics::{
-    ActivePipeline, ComputeModule, ComputePass, ComputeShader, GpuPod, Graphics, Texture,
+    ActivePipeline, ComputePass, GpuPod, Graphics, ShaderError, Texture,
     TriangleBuffer, Vertex,
 };
-use rendering::{attributes, AttributeBuffer};
+use rendering::{attributes, AttributeBuffer, Pipelines};
 use utils::{Storage, Time};
 use world::{System, World};
 
+// Log any shader reload errors and return true if the shader was successfully recompiled
+fn reloaded(name: &str, result: Option<Result<(), ShaderError>>) -> bool {
+    match result {
+        Some(Ok(())) => true,
+        Some(Err(error)) => {
+            log::error!("{name} Shader Error: {:?}", error);
+            false
+        }
+        None => false,
+    }
+}
+
 // Look in the world for any chunks that need their mesh generated and generate it
 fn update(world: &mut World) {
     let graphics = world.get::<Graphics>().unwrap();
@@ -30,14 +42,48 @@ fn update(world: &mut World) {
     let mut triangles = world.get_mut::<Storage<TriangleBuffer<u32>>>().unwrap();
 
     // Get the required sub-resources from the terrain resource
-    let (manager, voxelizer, mesher, memory, settings) = (
+    let (manager, voxelizer, mesher, culler, memory, settings) = (
         &mut terrain.manager,
         &mut terrain.voxelizer,
         &mut terrain.mesher,
+        &mut terrain.culler,
         &mut terrain.memory,
         &mut terrain.settings,
     );
 
+    // Recompile the terrain shaders if they (or the files they include) were modified
+    let assets = world.get::<Assets>().unwrap();
+    if !assets.changed().is_empty() {
+        let compiler = crate::create_compute_voxels_compiler(&assets, &graphics);
+        let voxels = voxelizer.compute_voxels.reload(&compiler);
+        let compiler = crate::create_compute_vertices_compiler(&assets, &graphics, settings);
+        let vertices = mesher.compute_vertices.reload(&compiler);
+        let compiler = crate::create_compute_quads_compiler(&assets, &graphics, settings);
+        let quads = mesher.compute_quads.reload(&compiler);
+        let compiler = crate::create_compute_cull_compiler(&assets, &graphics);
+        let cull = culler.compute_cull.reload(&compiler);
+
+        // Only the shaders that generate the meshes require the chunks to be regenerated
+        let regenerate =
+            reloaded("Voxel", voxels) | reloaded("Vertices", vertices) | reloaded("Quads", quads);
+        reloaded("Cull", cull);
+
+        // Rebuild the terrain material pipeline as well
+        let mut pipelines = world.get_mut::<Pipelines>().unwrap();
+        pipelines.reload_with::<TerrainMaterial>(&graphics, (&*settings, &*memory), &assets);
+
+        // Force the regeneration of all chunks
+        if regenerate {
+            let query = scene
+                .query_mut::<&mut Chunk>()
+                .into_iter()
+                .filter(|x| matches!(x.state, ChunkState::Generated { .. }));
+            for x in query {
+                x.regenerate();
+            }
+        }
+    }
+
     // Convert "Dirty" chunks into "Pending", and clears the old memory used by those chunks
     let query = scene.query_mut::<&mut Chunk>().into_iter();
     for chunk in query.filter(|c| c.state == ChunkState::Dirty) {
@@ -68,42 +114,7 @@ fn update(world: &mut World) {
     });
     vec.retain(|(chunk, _, _, _)| chunk.state == ChunkState::Pending);
     let Some((chunk, position, scale, _entity)) = vec.pop() else {
-        // We have no chunks to generate, check if we should hot reload now blud
-        if let Some((rx, _)) = voxelizer.hot_reload.as_ref() {
-            if rx.try_recv().is_ok() {
-                rx.try_iter().count();
-                let assets = world.get::<Assets>().unwrap();
-                let graphics = world.get::<Graphics>().unwrap();
-                
-                // Uncache the old shader so we can force hot reloading
-                assets.uncache("engine/shaders/terrain/voxel.glsl");
-                
-                // Re-load the compute shader and compile it again
-                let module = assets
-                    .load::<ComputeModule>("engine/shaders/terrain/voxels.comp")
-                    .unwrap();
-                let compiler = crate::create_compute_voxels_compiler(&assets, &graphics);
-                match ComputeShader::new(module, &compiler) {
-                    Ok(shader) => {
-                        voxelizer.compute_voxels = shader;
-
-                        // Force the regeneration of all chunks
-                        let query = scene
-                            .query_mut::<&mut Chunk>()
-                            .into_iter()
-                            .filter(|x| matches!(x.state, ChunkState::Generated { .. }));
-                        for x in query {
-                            x.regenerate();
-                        }
-                    },
-                    Err(error) => {
-                        log::error!("Voxel Shader Error: {:?}", error);
-                    },
-                }
-                
-            }
-        }
-
+        // We have no chunks to generate
         return;
     };
 
@@ -86,7 +86,7 @@ impl FileManager {
     }
 
     // Convert a FileType variant and local path to a proper global system path
-    fn local_path_to_global(&self, path: impl AsRef<Path>, variant: FileType) -> PathBuf {
+    pub fn local_path_to_global(&self, path: impl AsRef<Path>, variant: FileType) -> PathBuf {
         let mut base = match variant {
             FileType::Config => self.dirs.config_dir.clone(),
             FileType::Data => self.dirs.state_dir.clone(),
@@ -1,4 +1,4 @@
-use super::Trackers;
+use super::{Trackers, Weak};
 use slotmap::{DefaultKey, Key};
 use std::{
     marker::PhantomData,
@@ -35,13 +35,22 @@ impl<T: 'static> Eq for Handle<T> {}
 
 impl<T: 'static> Handle<T> {
     // Get the current reference count for this handle
+    // Returns 0 if the value was already removed from its storage
     pub fn count(&self) -> u32 {
         self.trackers
             .counters
             .read()
             .get(self.key)
-            .unwrap()
-            .load(Ordering::Relaxed)
+            .map_or(0, |count| count.load(Ordering::Relaxed))
+    }
+
+    // Create a weak handle that does not keep the value alive
+    pub fn downgrade(&self) -> Weak<T> {
+        Weak {
+            _phantom: PhantomData,
+            trackers: self.trackers.clone(),
+            key: self.key,
+        }
     }
 
     // Get the raw key FFI for this weak handle
@@ -50,30 +59,30 @@ impl<T: 'static> Handle<T> {
     }
 
     // Overwrite the current reference counted value directly
+    // Does nothing if the value was already removed from its storage
     pub unsafe fn set_count(&self, count: u32) {
         let borrowed = self.trackers.counters.read();
-        borrowed
-            .get(self.key)
-            .unwrap()
-            .store(count, Ordering::Relaxed);
+        if let Some(counter) = borrowed.get(self.key) {
+            counter.store(count, Ordering::Relaxed);
+        }
     }
 
     // This will manually incremememnt the underlying reference counter
+    // Returns the previous count, or 0 if the value was already removed from its storage
     pub unsafe fn increment_count(&self) -> u32 {
         let borrowed = self.trackers.counters.read();
         borrowed
             .get(self.key)
-            .unwrap()
-            .fetch_add(1, Ordering::Relaxed)
+            .map_or(0, |count| count.fetch_add(1, Ordering::Relaxed))
     }
 
     // This will manually decrement the underlying reference counter
+    // Returns the previous count, or 0 if the value was already removed from its storage
     pub unsafe fn decrement_count(&self) -> u32 {
         let borrowed = self.trackers.counters.read();
         borrowed
             .get(self.key)
-            .unwrap()
-            .fetch_sub(1, Ordering::Relaxed)
+            .map_or(0, |count| count.fetch_sub(1, Ordering::Relaxed))
     }
 }
 
@@ -97,7 +106,8 @@ impl<T: 'static> Clone for Handle<T> {
 impl<T: 'static> Drop for Handle<T> {
     fn drop(&mut self) {
         // If the counter reaches 0, it means that we must drop the inner value
-        if unsafe { self.decrement_count() } == 0 {
+        // The decrement returns the previous count, so this was the last strong handle if it was 1
+        if unsafe { self.decrement_count() } == 1 {
             self.trackers.dropped.lock().push(self.key);
             self.trackers.cleaned.store(false, Ordering::Relaxed);
         }
@@ -1,5 +1,7 @@
+use ahash::AHashMap;
+use crossbeam_channel::{Receiver, Sender};
 use parking_lot::{Mutex, RwLock};
-use slotmap::{DefaultKey, SecondaryMap, SlotMap};
+use slotmap::{DefaultKey, Key, SecondaryMap, SlotMap};
 
 use std::{
     marker::PhantomData,
@@ -20,11 +22,24 @@ pub(super) struct Trackers {
     pub(super) cleaned: AtomicBool,
 }
 
+// Notification that is sent when a value was removed from its storage since its last strong handle was dropped
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Removed {
+    // Raw key of the value (same as Handle::as_raw)
+    pub raw: u64,
+
+    // Name of the value, if it had one
+    pub name: Option<String>,
+}
+
 // Strongly typed utility cache that stores some values that can be fetched by a ``Handle``
-//
+// Values can optionally be given a unique name that we can use to fetch them later on
 pub struct Storage<T: 'static> {
     map: SlotMap<DefaultKey, Option<T>>,
     trackers: Arc<Trackers>,
+    names: AHashMap<String, DefaultKey>,
+    labels: SecondaryMap<DefaultKey, String>,
+    subscribers: Vec<Sender<Removed>>,
 }
 
 impl<T: 'static> Default for Storage<T> {
@@ -36,6 +51,9 @@ impl<T: 'static> Default for Storage<T> {
                 counters: RwLock::new(Default::default()),
                 cleaned: AtomicBool::new(true),
             }),
+            names: Default::default(),
+            labels: Default::default(),
+            subscribers: Default::default(),
         }
     }
 }
@@ -57,6 +75,68 @@ impl<T: 'static> Storage<T> {
         }
     }
 
+    // Insert a new value with a unique name, returning a strong handle
+    // If another value already uses the same name, the name will now point to the new value
+    pub fn insert_named(&mut self, name: impl Into<String>, value: T) -> Handle<T> {
+        let handle = self.insert(value);
+        self.set_name(&handle, name);
+        handle
+    }
+
+    // Fetch the value with the given name, or insert a new one if there isn't any
+    // This can be used to deduplicate values that are created from the same source (like the path of a texture)
+    pub fn get_or_insert_with(&mut self, name: &str, value: impl FnOnce() -> T) -> Handle<T> {
+        match self.find(name) {
+            Some(handle) => handle,
+            None => self.insert_named(name, value()),
+        }
+    }
+
+    // Give a unique name to the value of a handle, replacing its old name
+    pub fn set_name(&mut self, handle: &Handle<T>, name: impl Into<String>) {
+        let name = name.into();
+        if let Some(old) = self.labels.insert(handle.key, name.clone()) {
+            self.names.remove(&old);
+        }
+
+        if let Some(previous) = self.names.insert(name, handle.key) {
+            if previous != handle.key {
+                self.labels.remove(previous);
+            }
+        }
+    }
+
+    // Get the name of the value of a handle, if it has one
+    pub fn name(&self, handle: &Handle<T>) -> Option<&str> {
+        self.labels.get(handle.key).map(String::as_str)
+    }
+
+    // Find a value by its name, returning a new strong handle to it
+    // Returns None if there's no value with this name, or if its last strong handle was dropped
+    pub fn find(&self, name: &str) -> Option<Handle<T>> {
+        self.upgrade(*self.names.get(name)?)
+    }
+
+    // Create a new strong handle for a key, but only if the value is still alive
+    fn upgrade(&self, key: DefaultKey) -> Option<Handle<T>> {
+        self.map.get(key)?.as_ref()?;
+        let counters = self.trackers.counters.read();
+
+        // We must not resurrect values whose last strong handle was already dropped
+        counters
+            .get(key)?
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |count| {
+                (count > 0).then_some(count + 1)
+            })
+            .ok()?;
+
+        Some(Handle {
+            _phantom: PhantomData,
+            trackers: self.trackers.clone(),
+            key,
+        })
+    }
+
     // Reserve a new storage value that might be inserted later
     pub fn reserve(&mut self) -> Weak<T> {
         let key = self.map.insert(None);
@@ -111,6 +191,27 @@ impl<T: 'static> Storage<T> {
         self.map.iter_mut().filter_map(|(_, x)| x.as_mut())
     }
 
+    // Get an iterator over new strong handles of all the values that are still alive
+    pub fn handles(&self) -> impl Iterator<Item = Handle<T>> + '_ {
+        self.map.keys().filter_map(|key| self.upgrade(key))
+    }
+
+    // Get an iterator over new strong handles and the values that they point to
+    pub fn iter_with_handles(&self) -> impl Iterator<Item = (Handle<T>, &T)> {
+        self.handles().map(|handle| {
+            let value = self.get(&handle);
+            (handle, value)
+        })
+    }
+
+    // Get a receiver that is notified whenever a value is removed from the storage
+    // Values are removed when the storage is cleaned (on insertion, or manually using clean)
+    pub fn subscribe(&mut self) -> Receiver<Removed> {
+        let (sender, receiver) = crossbeam_channel::unbounded();
+        self.subscribers.push(sender);
+        receiver
+    }
+
     // Clean the storage of any values that do not have any strong handles any more
     pub fn clean(&mut self) {
         if !self.trackers.cleaned.load(Ordering::Relaxed) {
@@ -120,6 +221,19 @@ impl<T: 'static> Storage<T> {
             for i in dropped.drain(..) {
                 self.map.remove(i);
                 counters.remove(i);
+
+                let name = self.labels.remove(i);
+                if let Some(name) = &name {
+                    self.names.remove(name);
+                }
+
+                // Subscribers that dropped their receiver are not notified anymore
+                let removed = Removed {
+                    raw: slotmap::KeyData::as_ffi(i.data()),
+                    name,
+                };
+                self.subscribers
+                    .retain(|sender| sender.send(removed.clone()).is_ok());
             }
         }
     }
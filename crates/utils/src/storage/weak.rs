@@ -45,24 +45,31 @@ impl<T: 'static> Weak<T> {
 
     // Try to upcast this weak handle to a strong one if it's value is still alive
     pub fn upgrade(self) -> Option<Handle<T>> {
-        self.valid().then(|| unsafe {
-            self.increment_count();
-            Handle {
-                _phantom: PhantomData,
-                trackers: self.trackers.clone(),
-                key: self.key,
-            }
+        // We must not resurrect values whose last strong handle was already dropped
+        self.trackers
+            .counters
+            .read()
+            .get(self.key)?
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |count| {
+                (count > 0).then_some(count + 1)
+            })
+            .ok()?;
+
+        Some(Handle {
+            _phantom: PhantomData,
+            trackers: self.trackers.clone(),
+            key: self.key,
         })
     }
 
     // Get the current reference count for this handle
+    // Returns 0 if the value was already removed from its storage
     pub fn count(&self) -> u32 {
         self.trackers
             .counters
             .read()
             .get(self.key)
-            .unwrap()
-            .load(Ordering::Relaxed)
+            .map_or(0, |count| count.load(Ordering::Relaxed))
     }
 
     // Get the raw key FFI for this weak handle
@@ -71,30 +78,30 @@ impl<T: 'static> Weak<T> {
     }
 
     // Overwrite the current reference counted value directly
+    // Does nothing if the value was already removed from its storage
     pub unsafe fn set_count(&self, count: u32) {
         let borrowed = self.trackers.counters.read();
-        borrowed
-            .get(self.key)
-            .unwrap()
-            .store(count, Ordering::Relaxed);
+        if let Some(counter) = borrowed.get(self.key) {
+            counter.store(count, Ordering::Relaxed);
+        }
     }
 
     // This will manually incremememnt the underlying reference counter
+    // Returns the previous count, or 0 if the value was already removed from its storage
     pub unsafe fn increment_count(&self) -> u32 {
         let borrowed = self.trackers.counters.read();
         borrowed
             .get(self.key)
-            .unwrap()
-            .fetch_add(1, Ordering::Relaxed)
+            .map_or(0, |count| count.fetch_add(1, Ordering::Relaxed))
     }
 
     // This will manually decrement the underlying reference counter
+    // Returns the previous count, or 0 if the value was already removed from its storage
     pub unsafe fn decrement_count(&self) -> u32 {
         let borrowed = self.trackers.counters.read();
         borrowed
             .get(self.key)
-            .unwrap()
-            .fetch_sub(1, Ordering::Relaxed)
+            .map_or(0, |count| count.fetch_sub(1, Ordering::Relaxed))
     }
 }
 
@@ -166,4 +166,59 @@ mod storage {
         assert_eq!(0, *storage.get(&zero));
         assert_eq!(1, *storage.get(&one));
     }
+
+    #[test]
+    fn named() {
+        let mut storage: Storage<u32> = Storage::<u32>::default();
+        let rock = storage.insert_named("rock", 0);
+        assert_eq!(storage.name(&rock), Some("rock"));
+        assert_eq!(*storage.get(&storage.find("rock").unwrap()), 0);
+        assert!(storage.find("grass").is_none());
+
+        // Inserting with the same key twice returns the same value
+        let grass = storage.get_or_insert_with("grass", || 1);
+        let again = storage.get_or_insert_with("grass", || 2);
+        assert!(grass == again);
+        assert_eq!(storage.handles().count(), 2);
+        assert_eq!(storage.iter_with_handles().map(|(_, x)| *x).sum::<u32>(), 1);
+
+        // Subscribers are notified when the last strong handle is dropped
+        let removed = storage.subscribe();
+        let raw = grass.as_raw();
+        drop(grass);
+        drop(again);
+        assert!(storage.find("grass").is_none());
+        storage.clean();
+        let notification = removed.try_recv().unwrap();
+        assert_eq!(notification.raw, raw);
+        assert_eq!(notification.name.as_deref(), Some("grass"));
+        assert!(removed.try_recv().is_err());
+        assert_eq!(storage.handles().count(), 1);
+    }
+
+    #[test]
+    fn weak() {
+        let mut storage: Storage<u32> = Storage::<u32>::default();
+        let handle = storage.insert(0);
+        let weak = handle.downgrade();
+        assert!(weak.valid());
+        assert_eq!(weak.count(), 1);
+
+        // Upgrading a weak handle keeps the value alive
+        let upgraded = weak.clone().upgrade().unwrap();
+        assert_eq!(handle.count(), 2);
+        drop(upgraded);
+
+        // Weak handles must not panic once the value was removed from the storage
+        drop(handle);
+        storage.clean();
+        assert!(!weak.valid());
+        assert_eq!(weak.count(), 0);
+        assert!(weak.clone().upgrade().is_none());
+        unsafe {
+            weak.set_count(5);
+            assert_eq!(weak.increment_count(), 0);
+        }
+        assert_eq!(weak.count(), 0);
+    }
 }
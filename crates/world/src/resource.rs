@@ -1,6 +1,8 @@
 mod entry;
 mod guards;
 mod resource;
+mod tracker;
 pub use entry::*;
 pub use guards::*;
 pub use resource::*;
+pub use tracker::*;
@@ -3,7 +3,7 @@ use std::{
     ops::{Deref, DerefMut},
 };
 
-use crate::Resource;
+use crate::{ChangeTracker, Resource};
 
 // A read guard is an immutable reference to a resource
 pub struct Read<'a, R: Resource>(pub(crate) Ref<'a, R>);
@@ -47,7 +47,8 @@ impl<T> AsRef<T> for ReadShard<'_, T> {
 }
 
 // A write guard is a mutable reference to a resource
-pub struct Write<'a, R: Resource>(pub(crate) RefMut<'a, R>);
+// Mutably dereferencing the guard will mark the resource as modified
+pub struct Write<'a, R: Resource>(pub(crate) RefMut<'a, R>, pub(crate) ChangeTracker<'a>);
 
 impl<R: Resource> Deref for Write<'_, R> {
     type Target = R;
@@ -59,12 +60,14 @@ impl<R: Resource> Deref for Write<'_, R> {
 
 impl<R: Resource> DerefMut for Write<'_, R> {
     fn deref_mut(&mut self) -> &mut Self::Target {
+        self.1.mark();
         &mut self.0
     }
 }
 
 impl<R: Resource> AsMut<R> for Write<'_, R> {
     fn as_mut(&mut self) -> &mut R {
+        self.1.mark();
         &mut self.0
     }
 }
@@ -78,12 +81,12 @@ impl<R: Resource> AsRef<R> for Write<'_, R> {
 impl<'a, R: Resource> Write<'a, R> {
     // Map a write guard to a mapped write shard
     pub fn map<T: 'static>(self, modify: impl FnOnce(&mut R) -> &mut T) -> WriteShard<'a, T> {
-        WriteShard(RefMut::map(self.0, modify))
+        WriteShard(RefMut::map(self.0, modify), self.1)
     }
 }
 
 // A write shard is a sub-guard of a bigger write guard. Most of the time, it is used to write/read a mapped value
-pub struct WriteShard<'a, T>(pub(crate) RefMut<'a, T>);
+pub struct WriteShard<'a, T>(pub(crate) RefMut<'a, T>, pub(crate) ChangeTracker<'a>);
 
 impl<T> Deref for WriteShard<'_, T> {
     type Target = T;
@@ -95,12 +98,14 @@ impl<T> Deref for WriteShard<'_, T> {
 
 impl<T> DerefMut for WriteShard<'_, T> {
     fn deref_mut(&mut self) -> &mut Self::Target {
+        self.1.mark();
         &mut self.0
     }
 }
 
 impl<T> AsMut<T> for WriteShard<'_, T> {
     fn as_mut(&mut self) -> &mut T {
+        self.1.mark();
         &mut self.0
     }
 }
@@ -0,0 +1,114 @@
+use crate::{post_user, user, Resource, System, World};
+use std::cell::{Cell, RefCell};
+
+// Change detection flags of a single resource for a single lifetime (frame or tick)
+#[derive(Default, Clone, Copy)]
+pub(crate) struct ResourceFlags {
+    pub(crate) added: bool,
+    pub(crate) modified: bool,
+}
+
+impl ResourceFlags {
+    // Flags that we set when we insert a new resource
+    pub(crate) fn inserted() -> Self {
+        Self {
+            added: true,
+            modified: true,
+        }
+    }
+}
+
+// A cell that contains a resource and its change detection flags
+// We store the flags in cells so write guards can update them from a shared world reference
+pub(crate) struct ResourceCell {
+    pub(crate) resource: RefCell<Box<dyn Resource>>,
+    pub(crate) frame: Cell<ResourceFlags>,
+    pub(crate) tick: Cell<ResourceFlags>,
+}
+
+impl ResourceCell {
+    // Create a new cell for a newly inserted resource
+    pub(crate) fn new<R: Resource>(resource: R) -> Self {
+        Self {
+            resource: RefCell::new(Box::new(resource)),
+            frame: Cell::new(ResourceFlags::inserted()),
+            tick: Cell::new(ResourceFlags::inserted()),
+        }
+    }
+
+    // Get the flags of the current lifetime
+    pub(crate) fn flags(&self, ticked: bool) -> ResourceFlags {
+        if ticked {
+            self.tick.get()
+        } else {
+            self.frame.get()
+        }
+    }
+}
+
+// Marks a resource as modified whenever we mutably dereference its write guard
+#[derive(Clone, Copy)]
+pub(crate) struct ChangeTracker<'a> {
+    pub(crate) frame: &'a Cell<ResourceFlags>,
+    pub(crate) tick: &'a Cell<ResourceFlags>,
+}
+
+impl ChangeTracker<'_> {
+    // Set the modified flag of both lifetimes
+    pub(crate) fn mark(&self) {
+        for cell in [self.frame, self.tick] {
+            let mut flags = cell.get();
+            flags.modified = true;
+            cell.set(flags);
+        }
+    }
+}
+
+// Called at the start of every tick to tell the world that we are ticking
+pub(crate) fn set_ticked_true(world: &mut World) {
+    world.ticked = true;
+}
+
+// Called at the start of every frame to tell the world that we are not ticking
+pub(crate) fn set_ticked_false(world: &mut World) {
+    world.ticked = false;
+}
+
+// At the end of each frame reset the frame flags of all resources
+pub(crate) fn reset_frame_flags_end(world: &mut World) {
+    for cell in world.resources.values() {
+        cell.frame.set(ResourceFlags::default());
+    }
+}
+
+// At the end of each tick reset the tick flags of all resources
+pub(crate) fn reset_tick_flags_end(world: &mut World) {
+    for cell in world.resources.values() {
+        cell.tick.set(ResourceFlags::default());
+    }
+}
+
+// Sets the lifetime that resource change detection uses at the start of every frame or tick
+pub fn pre_frame_or_tick(system: &mut System) {
+    system
+        .insert_tick(set_ticked_true)
+        .before(user)
+        .before(post_frame_or_tick);
+    system
+        .insert_update(set_ticked_false)
+        .before(user)
+        .before(post_frame_or_tick);
+}
+
+// Resets the resource change detection flags at the end of every frame or tick
+// Systems that check for resource changes after the user systems should run before this
+pub fn post_frame_or_tick(system: &mut System) {
+    system
+        .insert_update(reset_frame_flags_end)
+        .after(post_user)
+        .after(pre_frame_or_tick);
+    system
+        .insert_tick(reset_tick_flags_end)
+        .after(post_user)
+        .after(pre_frame_or_tick);
+}
@@ -1 +1,92 @@
+#[cfg(test)]
+mod tracker {
+    use crate::{
+        reset_frame_flags_end, reset_tick_flags_end, set_ticked_false, set_ticked_true, World,
+    };
 
+    struct Counter {
+        value: u32,
+    }
+
+    #[test]
+    fn added() {
+        let mut world = World::empty();
+        assert!(!world.is_added::<Counter>());
+        world.insert(Counter { value: 0 });
+        assert!(world.is_added::<Counter>());
+        assert!(world.is_changed::<Counter>());
+
+        reset_frame_flags_end(&mut world);
+        assert!(!world.is_added::<Counter>());
+        assert!(!world.is_changed::<Counter>());
+    }
+
+    #[test]
+    fn read_does_not_change() {
+        let mut world = World::empty();
+        world.insert(Counter { value: 0 });
+        reset_frame_flags_end(&mut world);
+
+        assert_eq!(world.get::<Counter>().unwrap().value, 0);
+        assert_eq!(world.get_mut::<Counter>().unwrap().value, 0);
+        assert!(!world.is_changed::<Counter>());
+    }
+
+    #[test]
+    fn write_changes() {
+        let mut world = World::empty();
+        world.insert(Counter { value: 0 });
+        reset_frame_flags_end(&mut world);
+
+        world.get_mut::<Counter>().unwrap().value += 1;
+        assert!(world.is_changed::<Counter>());
+        assert!(!world.is_added::<Counter>());
+
+        reset_frame_flags_end(&mut world);
+        assert!(!world.is_changed::<Counter>());
+    }
+
+    #[test]
+    fn mapped_write_changes() {
+        let mut world = World::empty();
+        world.insert(Counter { value: 0 });
+        reset_frame_flags_end(&mut world);
+
+        let mut shard = world
+            .get_mut::<Counter>()
+            .unwrap()
+            .map(|counter| &mut counter.value);
+        assert!(!world.is_changed::<Counter>());
+        *shard += 1;
+        drop(shard);
+        assert!(world.is_changed::<Counter>());
+    }
+
+    #[test]
+    fn frame_and_tick_lifetimes() {
+        let mut world = World::empty();
+        world.insert(Counter { value: 0 });
+        reset_frame_flags_end(&mut world);
+        reset_tick_flags_end(&mut world);
+
+        // Modified during a frame, seen by both the frame and the next tick
+        world.get_mut::<Counter>().unwrap().value += 1;
+        set_ticked_true(&mut world);
+        assert!(world.is_changed::<Counter>());
+        reset_tick_flags_end(&mut world);
+        assert!(!world.is_changed::<Counter>());
+
+        // Resetting the tick flags must not reset the frame flags
+        set_ticked_false(&mut world);
+        assert!(world.is_changed::<Counter>());
+        reset_frame_flags_end(&mut world);
+        assert!(!world.is_changed::<Counter>());
+    }
+
+    #[test]
+    fn missing_resource() {
+        let world = World::empty();
+        assert!(!world.is_changed::<Counter>());
+        assert!(!world.is_added::<Counter>());
+    }
+}
@@ -10,7 +10,7 @@ pub fn setup() -> (World, Systems) {
     if !INITIALIZED.fetch_or(true, Ordering::Relaxed) {
         (
             // Create a single instance of the world
-            World(Default::default()),
+            World::empty(),
             // Create a single instance of the systems
             Systems {
                 init: Default::default(),
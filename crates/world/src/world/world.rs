@@ -1,13 +1,22 @@
-use crate::{user, Entry, Read, Resource, System, WorldBorrowError, WorldBorrowMutError, Write};
+use crate::{
+    user, ChangeTracker, Entry, Read, Resource, ResourceCell, System, WorldBorrowError,
+    WorldBorrowMutError, Write,
+};
 use ahash::AHashMap;
 use std::{
     any::TypeId,
-    cell::{Ref, RefCell, RefMut},
+    cell::{Ref, RefMut},
 };
 
 // The world is a unique container for multiple resources like ECS and assets
 // Each World can be created using the builder pattern with the help of an App
-pub struct World(pub(crate) AHashMap<TypeId, RefCell<Box<dyn Resource>>>);
+pub struct World {
+    // All the resources and their change detection flags
+    pub(crate) resources: AHashMap<TypeId, ResourceCell>,
+
+    // Are we currently executing the tick event (used for change detection)
+    pub(crate) ticked: bool,
+}
 
 // This is the main world state that the user can manually update to force the engine to stop running
 #[derive(Default, Clone, Copy, PartialEq, Eq)]
@@ -24,10 +33,19 @@ pub enum State {
 }
 
 impl World {
+    // Create an empty world that is not tied to any systems
+    // The app creates the main world using setup(), so this is mostly useful for tests and tools
+    pub fn empty() -> Self {
+        Self {
+            resources: Default::default(),
+            ticked: false,
+        }
+    }
+
     // Insert a new resource into the world
     pub fn insert<R: Resource>(&mut self, resource: R) {
         let id = TypeId::of::<R>();
-        let returned = self.0.insert(id, RefCell::new(Box::new(resource)));
+        let returned = self.resources.insert(id, ResourceCell::new(resource));
         if returned.is_some() {
             let name = pretty_type_name::pretty_type_name::<R>();
             log::warn!("Replaced resource {} since it was already present", name);
@@ -37,10 +55,13 @@ impl World {
     // Get an immutable reference (read guard) to a resource
     pub fn get<R: Resource>(&self) -> Result<Read<R>, WorldBorrowError> {
         let cell = self
-            .0
+            .resources
             .get(&TypeId::of::<R>())
             .ok_or(WorldBorrowError::NotPresent)?;
-        let borrowed = cell.try_borrow().map_err(WorldBorrowError::BorrowError)?;
+        let borrowed = cell
+            .resource
+            .try_borrow()
+            .map_err(WorldBorrowError::BorrowError)?;
         let borrowed = Ref::map(borrowed, |boxed| {
             boxed.as_ref().as_any().downcast_ref::<R>().unwrap()
         });
@@ -50,16 +71,21 @@ impl World {
     // Get a mutable reference (write guard) to a resource
     pub fn get_mut<R: Resource>(&self) -> Result<Write<R>, WorldBorrowMutError> {
         let cell = self
-            .0
+            .resources
             .get(&TypeId::of::<R>())
             .ok_or(WorldBorrowMutError::NotPresent)?;
         let borrowed = cell
+            .resource
             .try_borrow_mut()
             .map_err(WorldBorrowMutError::BorrowMutError)?;
         let borrowed = RefMut::map(borrowed, |boxed| {
             boxed.as_mut().as_any_mut().downcast_mut::<R>().unwrap()
         });
-        Ok(Write(borrowed))
+        let tracker = ChangeTracker {
+            frame: &cell.frame,
+            tick: &cell.tick,
+        };
+        Ok(Write(borrowed, tracker))
     }
 
     // Get an entry for a specific resource
@@ -72,8 +98,8 @@ impl World {
 
     // Remove a specific resource from the world
     pub fn remove<R: Resource>(&mut self) -> Option<R> {
-        self.0.remove(&TypeId::of::<R>()).map(|cell| {
-            let boxed = cell.into_inner();
+        self.resources.remove(&TypeId::of::<R>()).map(|cell| {
+            let boxed = cell.resource.into_inner();
             let any = boxed.into_any();
             let downcasted = any.downcast::<R>().unwrap();
             *downcasted
@@ -82,7 +108,25 @@ impl World {
 
     // Check if a resource is present in the world
     pub fn contains<R: Resource>(&self) -> bool {
-        self.0.contains_key(&TypeId::of::<R>())
+        self.resources.contains_key(&TypeId::of::<R>())
+    }
+
+    // Check if a resource was mutably accessed during the current frame (or tick if we are ticking)
+    // Returns false if the resource is not present in the world
+    pub fn is_changed<R: Resource>(&self) -> bool {
+        self.resources
+            .get(&TypeId::of::<R>())
+            .map(|cell| cell.flags(self.ticked).modified)
+            .unwrap_or_default()
+    }
+
+    // Check if a resource was inserted during the current frame (or tick if we are ticking)
+    // Returns false if the resource is not present in the world
+    pub fn is_added<R: Resource>(&self) -> bool {
+        self.resources
+            .get(&TypeId::of::<R>())
+            .map(|cell| cell.flags(self.ticked).added)
+            .unwrap_or_default()
     }
 }
 
@@ -18,7 +18,6 @@ struct PlayerInputs {
     backward: bool,
     left: bool,
     right: bool,
-    jump: bool,
     rotation_x: f32,
     rotation_y: f32,
 }
@@ -43,6 +42,10 @@ fn init(world: &mut World) {
     input.bind_axis("x rotation", MouseAxis::PositionX);
     input.bind_axis("y rotation", MouseAxis::PositionY);
 
+    // Lock the user's mouse (the UI doesn't need it)
+    input.set_cursor(CursorState::locked());
+    world.get_mut::<Interface>().unwrap().consumes_window_events = false;
+
     asset!(assets, "user/textures/diffuse2.jpg", "/examples/assets/");
     asset!(assets, "user/textures/normal2.jpg", "/examples/assets/");
     asset!(assets, "user/textures/mask2.jpg", "/examples/assets/");
@@ -195,16 +198,6 @@ fn init(world: &mut World) {
 
 // Update the PlayerInputs resource
 fn update(world: &mut World) {
-    // Confine the user's mouse
-    let mut ui = world.get_mut::<Interface>().unwrap();
-    let window = world.get::<Window>().unwrap();
-    window
-        .raw()
-        .set_cursor_grab(winit::window::CursorGrabMode::Confined)
-        .unwrap();
-    window.raw().set_cursor_visible(false);
-    ui.consumes_window_events = true;
-
     // Fetch the user input state
     let mut state = world.get_mut::<State>().unwrap();
     let input = world.get::<Input>().unwrap();
@@ -222,7 +215,6 @@ fn update(world: &mut World) {
     player.right = input.get_button("right").held();
     player.rotation_x = input.get_axis("x rotation");
     player.rotation_y = input.get_axis("y rotation");
-    player.jump |= input.get_button("jump").pressed();
 
     let mut scene = world.get_mut::<Scene>().unwrap();
 
@@ -257,7 +249,8 @@ fn update(world: &mut World) {
 // Set the required player controller force
 fn tick(world: &mut World) {
     let mut scene = world.get_mut::<Scene>().unwrap();
-    let mut inputs = world.get_mut::<PlayerInputs>().unwrap();
+    let inputs = world.get::<PlayerInputs>().unwrap();
+    let input = world.get::<Input>().unwrap();
 
     // Local velocity
     let mut velocity = vek::Vec3::<f32>::zero();
@@ -295,8 +288,8 @@ fn tick(world: &mut World) {
         .find_mut::<(&mut CharacterController, &Rotation)>()
         .unwrap();
 
-    // Handle jumping
-    if std::mem::take(&mut inputs.jump) {
+    // Handle jumping (the tick view never misses quick taps)
+    if input.get_button_tick("jump").pressed() {
         cc.jump();
     }
 